@@ -6,8 +6,23 @@ import Link from "next/link";
 import { useWallet, useConnection } from "@solana/wallet-adapter-react";
 import { useWalletModal } from "@solana/wallet-adapter-react-ui";
 import { Program, AnchorProvider, BN } from "@coral-xyz/anchor";
-import { PublicKey, SystemProgram } from "@solana/web3.js";
-import { PROGRAM_ID, getMarketPDA, getVaultPDA, isMarketOpen, isMarketResolved, getMarketStatusString } from "@/lib/program";
+import { PublicKey, SystemProgram, Transaction } from "@solana/web3.js";
+import { decrypt } from "@inco/solana-sdk/attested-decrypt";
+import {
+  PROGRAM_ID,
+  INCO_LIGHTNING_PROGRAM_ID,
+  getMarketPDA,
+  getVaultPDA,
+  getConfigPDA,
+  getFeeVaultPDAs,
+  deriveAllowancePda,
+  handleToBuffer,
+  isMarketOpen,
+  isMarketResolved,
+  getMarketStatusString,
+  OUTCOME_NO,
+  OUTCOME_YES,
+} from "@/lib/program";
 import { useMarkets, MarketWithPubkey } from "@/hooks/useMarkets";
 import idl from "@/lib/idl.json";
 
@@ -15,7 +30,7 @@ import idl from "@/lib/idl.json";
 const ADMIN_PASSWORD = "dake2026";
 
 export default function AdminPage() {
-  const { publicKey, signTransaction, signAllTransactions, connected } = useWallet();
+  const { publicKey, signTransaction, signAllTransactions, signMessage, connected } = useWallet();
   const { connection } = useConnection();
   const { setVisible } = useWalletModal();
   const { markets, loading: marketsLoading, refetch } = useMarkets();
@@ -91,15 +106,37 @@ export default function AdminPage() {
       // Derive PDAs
       const [marketPda] = getMarketPDA(marketId);
       const [vaultPda] = getVaultPDA(marketPda);
+      const [configPda] = getConfigPDA();
+      const [protocolFeeVault, creatorFeeVault] = getFeeVaultPDAs(marketPda);
 
-      // Create market transaction
+      // Create a binary SOL market resolved by its authority. An empty
+      // initial liquidity list seeds both outcomes with the configured default,
+      // and betting stays open until the resolution time
       const tx = await program.methods
-        .createMarket(marketId, question.trim(), resolutionTime)
+        .createMarket(
+          marketId,
+          question.trim(),
+          ["NO", "YES"],
+          { categorical: {} },
+          [],
+          resolutionTime,
+          resolutionTime,
+          0,
+          { authority: {} }
+        )
         .accounts({
           authority: publicKey,
           market: marketPda,
+          config: configPda,
+          protocolFeeVault,
+          creatorFeeVault,
           vault: vaultPda,
+          collateralMint: null,
+          vaultTokenAccount: null,
+          authorityTokenAccount: null,
+          tokenProgram: null,
           systemProgram: SystemProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
         })
         .rpc();
 
@@ -138,12 +175,26 @@ export default function AdminPage() {
 
       const program = new Program(idl as any, provider);
 
+      // Grant the closer decrypt access to every pool total, so the pools can
+      // be revealed from this wallet afterwards
+      const allowances = market.encryptedTotals.flatMap((handle) => [
+        {
+          pubkey: deriveAllowancePda(BigInt(handle.toString()), publicKey)[0],
+          isSigner: false,
+          isWritable: true,
+        },
+        { pubkey: publicKey, isSigner: false, isWritable: false },
+      ]);
+
       const tx = await program.methods
         .closeMarket()
         .accounts({
-          authority: publicKey,
+          closer: publicKey,
           market: market.publicKey,
+          systemProgram: SystemProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
         })
+        .remainingAccounts(allowances)
         .rpc();
 
       setSuccess(`Market closed! TX: ${tx.slice(0, 8)}...`);
@@ -156,7 +207,69 @@ export default function AdminPage() {
     }
   };
 
-  const handleResolveMarket = async (market: MarketWithPubkey, outcome: boolean) => {
+  const handleRevealPools = async (market: MarketWithPubkey) => {
+    if (!publicKey || !signTransaction || !signAllTransactions || !signMessage) {
+      setError("Please connect your wallet (signMessage required)");
+      return;
+    }
+
+    setActionLoading(market.publicKey.toBase58());
+    setError(null);
+
+    try {
+      const provider = new AnchorProvider(
+        connection,
+        { publicKey, signTransaction, signAllTransactions },
+        { commitment: "confirmed" }
+      );
+
+      const program = new Program(idl as any, provider);
+
+      // Attested decryption of every pool total (Inco SDK expects decimal handles)
+      const decryptResult = await decrypt(
+        market.encryptedTotals.map((handle) => BigInt(handle.toString()).toString()),
+        { address: publicKey, signMessage }
+      );
+
+      // Ed25519 verification instructions must come before reveal_pools
+      const tx = new Transaction();
+      for (const ed25519Ix of decryptResult.ed25519Instructions) {
+        tx.add(ed25519Ix);
+      }
+
+      // Handles and plaintexts are u128 values encoded as 16 little-endian bytes,
+      // one per outcome in outcome order
+      const revealIx = await program.methods
+        .revealPools(
+          decryptResult.handles.map((handle) => handleToBuffer(BigInt(handle.toString()))),
+          decryptResult.plaintexts.map((total) => handleToBuffer(BigInt(total.toString())))
+        )
+        .accounts({
+          revealer: publicKey,
+          market: market.publicKey,
+          instructions: new PublicKey("Sysvar1nstructions1111111111111111111111111"),
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        })
+        .instruction();
+      tx.add(revealIx);
+
+      tx.feePayer = publicKey;
+      tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+      const signedTx = await signTransaction(tx);
+      const txSig = await connection.sendRawTransaction(signedTx.serialize());
+      await connection.confirmTransaction(txSig, "confirmed");
+
+      setSuccess(`Pools revealed! TX: ${txSig.slice(0, 8)}...`);
+      refetch();
+    } catch (err: any) {
+      console.error("Error revealing pools:", err);
+      setError(err.message || "Failed to reveal pools");
+    } finally {
+      setActionLoading(null);
+    }
+  };
+
+  const handleResolveMarket = async (market: MarketWithPubkey, outcome: number) => {
     if (!publicKey || !signTransaction || !signAllTransactions) {
       setError("Please connect your wallet first");
       return;
@@ -182,7 +295,7 @@ export default function AdminPage() {
         })
         .rpc();
 
-      setSuccess(`Market resolved as ${outcome ? "YES" : "NO"}! TX: ${tx.slice(0, 8)}...`);
+      setSuccess(`Market resolved as ${market.outcomes[outcome]}! TX: ${tx.slice(0, 8)}...`);
       refetch();
 
       window.open(
@@ -438,7 +551,7 @@ export default function AdminPage() {
                             : "bg-white/5 text-slate-400 border-white/10"
                         }`}
                       >
-                        {getMarketStatusString(market.status)}
+                        {getMarketStatusString(market)}
                       </span>
                     </div>
 
@@ -472,10 +585,27 @@ export default function AdminPage() {
                       </div>
                     )}
 
+                    {isAuthority && !open && !market.poolsRevealed && (
+                      <div className="flex gap-2 mb-2">
+                        <button
+                          onClick={() => handleRevealPools(market)}
+                          disabled={isLoading}
+                          className="flex-1 flex items-center justify-center gap-1 px-3 py-2 bg-white/5 border border-white/10 text-white rounded-lg text-sm hover:bg-white/10 transition-colors disabled:opacity-50"
+                        >
+                          {isLoading ? (
+                            <Icon icon="solar:refresh-circle-outline" width={16} className="animate-spin" />
+                          ) : (
+                            <Icon icon="solar:eye-outline" width={16} />
+                          )}
+                          Reveal Pools
+                        </button>
+                      </div>
+                    )}
+
                     {isAuthority && !open && !resolved && (
                       <div className="flex gap-2">
                         <button
-                          onClick={() => handleResolveMarket(market, true)}
+                          onClick={() => handleResolveMarket(market, OUTCOME_YES)}
                           disabled={isLoading}
                           className="flex-1 flex items-center justify-center gap-1 px-3 py-2 bg-green-500/10 border border-green-500/20 text-green-400 rounded-lg text-sm hover:bg-green-500/20 transition-colors disabled:opacity-50"
                         >
@@ -487,7 +617,7 @@ export default function AdminPage() {
                           Resolve YES
                         </button>
                         <button
-                          onClick={() => handleResolveMarket(market, false)}
+                          onClick={() => handleResolveMarket(market, OUTCOME_NO)}
                           disabled={isLoading}
                           className="flex-1 flex items-center justify-center gap-1 px-3 py-2 bg-red-500/10 border border-red-500/20 text-red-400 rounded-lg text-sm hover:bg-red-500/20 transition-colors disabled:opacity-50"
                         >
@@ -507,7 +637,7 @@ export default function AdminPage() {
                       </p>
                     )}
 
-                    {resolved && (
+                    {resolved && market.poolsRevealed && (
                       <p className="text-xs text-[#FACC15]">
                         Market resolved - winners can now claim
                       </p>
@@ -531,6 +661,7 @@ export default function AdminPage() {
             <ol className="list-decimal list-inside space-y-1">
               <li><strong>Create</strong> - Set up a YES/NO question with a resolution date</li>
               <li><strong>Close Betting</strong> - Stop accepting new bets when ready to resolve</li>
+              <li><strong>Reveal Pools</strong> - Publish the decrypted pool totals that payouts are computed from</li>
               <li><strong>Resolve</strong> - Declare the outcome (YES or NO won)</li>
               <li><strong>Winners Claim</strong> - Users go to Portfolio to claim winnings</li>
             </ol>
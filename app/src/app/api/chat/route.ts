@@ -9,8 +9,8 @@ function buildSystemPrompt(market: MarketContext): string {
 You are analyzing this market:
 - Question: "${market.question}"
 - Status: ${market.status}
-- YES pool: ${market.totalYesAmount.toFixed(2)} SOL (${market.yesProbability.toFixed(1)}% implied probability)
-- NO pool: ${market.totalNoAmount.toFixed(2)} SOL (${market.noProbability.toFixed(1)}% implied probability)
+- YES pool: ${market.yesPool.toFixed(2)} SOL (${market.yesProbability.toFixed(1)}% implied probability)
+- NO pool: ${market.noPool.toFixed(2)} SOL (${market.noProbability.toFixed(1)}% implied probability)
 - Total participants: ${market.participantCount}
 - Resolution date: ${market.resolutionDate}
 
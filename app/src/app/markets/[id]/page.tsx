@@ -11,11 +11,15 @@ import { encryptValue } from "@inco/solana-sdk/encryption";
 import {
   PROGRAM_ID,
   INCO_LIGHTNING_PROGRAM_ID,
+  getConfigPDA,
   getPositionPDA,
   getVaultPDA,
+  getVisiblePools,
   isMarketOpen,
   isMarketResolved,
   getMarketStatusString,
+  OUTCOME_NO,
+  OUTCOME_YES,
   PositionAccount,
 } from "@/lib/program";
 import { useMarketByPubkey } from "@/hooks/useMarketByPubkey";
@@ -84,11 +88,13 @@ export default function MarketDetailPage({
 
   // Calculate potential payout using parimutuel formula
   // Payout = (your_bet / winning_pool) * total_pool
+  // While betting is open only the seeded pools are public, so this is an estimate
   const calculatePotentialPayout = (side: "yes" | "no", amount: number) => {
     if (!market || amount <= 0) return null;
 
-    const yesPool = market.totalYesAmount.toNumber() / LAMPORTS_PER_SOL;
-    const noPool = market.totalNoAmount.toNumber() / LAMPORTS_PER_SOL;
+    const pools = getVisiblePools(market);
+    const yesPool = pools[OUTCOME_YES].toNumber() / LAMPORTS_PER_SOL;
+    const noPool = pools[OUTCOME_NO].toNumber() / LAMPORTS_PER_SOL;
     const totalPool = yesPool + noPool;
 
     // Add user's bet to their side's pool
@@ -114,8 +120,9 @@ export default function MarketDetailPage({
 
   // Calculate current market odds
   const calculateOdds = () => {
-    const yesPool = market?.totalYesAmount.toNumber() || 0;
-    const noPool = market?.totalNoAmount.toNumber() || 0;
+    const pools = market ? getVisiblePools(market) : [];
+    const yesPool = pools[OUTCOME_YES]?.toNumber() || 0;
+    const noPool = pools[OUTCOME_NO]?.toNumber() || 0;
     const total = yesPool + noPool;
 
     if (total === 0) return { yesOdds: 2.0, noOdds: 2.0, yesProbability: 50, noProbability: 50 };
@@ -168,27 +175,38 @@ export default function MarketDetailPage({
       const program = new Program(idl as any, provider);
 
       // Derive PDAs
-      const [positionPda] = getPositionPDA(market.publicKey, publicKey);
+      const positionNonce = new BN(0);
+      const [positionPda] = getPositionPDA(market.publicKey, publicKey, positionNonce);
       const [vaultPda] = getVaultPDA(market.publicKey);
+      const [configPda] = getConfigPDA();
 
       // Convert amount to lamports
       const lamports = new BN(amount * LAMPORTS_PER_SOL);
 
-      // Side: 1 = YES, 0 = NO
-      const sideValue = selectedSide === "yes" ? 1 : 0;
+      // Side: the outcome index (1 = YES, 0 = NO)
+      const sideValue = selectedSide === "yes" ? OUTCOME_YES : OUTCOME_NO;
 
       // Encrypt the side using Inco SDK
       const encryptedSideHex = await encryptValue(sideValue);
       const encryptedSideBytes = Buffer.from(encryptedSideHex.replace("0x", ""), "hex");
 
+      // No slippage guard (min_payout = 0)
+      const minPayout = new BN(0);
+
       // Place bet transaction
       const tx = await program.methods
-        .placeBet(encryptedSideBytes, lamports, sideValue)
+        .placeBet(encryptedSideBytes, lamports, positionNonce, minPayout)
         .accounts({
           bettor: publicKey,
           market: market.publicKey,
+          config: configPda,
           position: positionPda,
           vault: vaultPda,
+          // SOL markets only: the token accounts are omitted
+          collateralMint: null,
+          vaultTokenAccount: null,
+          bettorTokenAccount: null,
+          tokenProgram: null,
           systemProgram: SystemProgram.programId,
           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
         })
@@ -258,8 +276,9 @@ export default function MarketDetailPage({
 
   const marketOpen = isMarketOpen(market.status);
   const marketResolved = isMarketResolved(market.status);
-  const yesPool = market.totalYesAmount.toNumber() / LAMPORTS_PER_SOL;
-  const noPool = market.totalNoAmount.toNumber() / LAMPORTS_PER_SOL;
+  const pools = getVisiblePools(market);
+  const yesPool = pools[OUTCOME_YES].toNumber() / LAMPORTS_PER_SOL;
+  const noPool = pools[OUTCOME_NO].toNumber() / LAMPORTS_PER_SOL;
   const totalPool = yesPool + noPool;
 
   return (
@@ -285,7 +304,7 @@ export default function MarketDetailPage({
                 : "bg-white/5 text-slate-400 border-white/10"
             }`}
           >
-            {getMarketStatusString(market.status)}
+            {getMarketStatusString(market)}
           </span>
           <span className="text-xs text-slate-500">
             #{market.marketId.toString().slice(-6)}
@@ -355,24 +374,14 @@ export default function MarketDetailPage({
                   </p>
                 </div>
                 <div>
-                  <p className="text-slate-400 text-xs mb-1">Locked Payout (if win)</p>
-                  <p className="text-lg font-bold text-green-400">
-                    {(() => {
-                      try {
-                        const payout = userPosition.lockedPayout;
-                        if (!payout) return "—";
-                        const payoutNum = parseFloat(payout.toString()) / LAMPORTS_PER_SOL;
-                        if (payoutNum > 1000 || payoutNum < 0 || isNaN(payoutNum)) return "—";
-                        return `${payoutNum.toFixed(2)} SOL`;
-                      } catch {
-                        return "—";
-                      }
-                    })()}
+                  <p className="text-slate-400 text-xs mb-1">Placed</p>
+                  <p className="text-lg font-bold text-white">
+                    {new Date(userPosition.openedAt.toNumber() * 1000).toLocaleDateString()}
                   </p>
                 </div>
               </div>
               <p className="text-slate-400 text-xs mb-3">
-                Your side is <span className="text-[#FACC15]">encrypted</span> · Payout is <span className="text-green-400">locked & guaranteed</span>
+                Your side is <span className="text-[#FACC15]">encrypted</span> · Payout is your share of the <span className="text-green-400">revealed pools</span>
               </p>
               <Link
                 href="/portfolio"
@@ -491,9 +500,9 @@ export default function MarketDetailPage({
                   <p className="text-white font-medium">{potentialPayout.impliedProbability?.toFixed(0)}%</p>
                 </div>
               </div>
-              <p className="text-xs text-green-400/70 mt-3 flex items-center gap-1">
-                <Icon icon="solar:lock-outline" width={12} />
-                Your payout is LOCKED when you bet — it won't change even if others bet after you!
+              <p className="text-xs text-slate-400 mt-3 flex items-center gap-1">
+                <Icon icon="solar:info-circle-outline" width={12} />
+                Estimate from the public seed pools — the final payout depends on the pools revealed at close.
               </p>
             </div>
           )}
@@ -586,7 +595,7 @@ export default function MarketDetailPage({
           </h3>
           <p className="text-slate-400 mb-4">
             {marketResolved
-              ? `The outcome was ${getMarketStatusString(market.status).replace("Resolved: ", "")}`
+              ? `The outcome was ${market.outcomes[market.winningOutcome]}`
               : "This market is no longer accepting bets"}
           </p>
           {marketResolved && (
@@ -684,9 +693,9 @@ export default function MarketDetailPage({
           onClose={() => setChatOpen(false)}
           market={{
             question: market.question,
-            status: getMarketStatusString(market.status),
-            totalYesAmount: yesPool,
-            totalNoAmount: noPool,
+            status: getMarketStatusString(market),
+            yesPool,
+            noPool,
             yesProbability: odds.yesProbability,
             noProbability: odds.noProbability,
             participantCount: market.participantCount,
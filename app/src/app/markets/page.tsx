@@ -132,7 +132,7 @@ export default function MarketsPage() {
                       : "bg-white/5 text-slate-400 border-white/10"
                   }`}
                 >
-                  {getMarketStatusString(market.status)}
+                  {getMarketStatusString(market)}
                 </span>
               </div>
 
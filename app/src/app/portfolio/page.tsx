@@ -12,7 +12,9 @@ import {
   PROGRAM_ID,
   INCO_LIGHTNING_PROGRAM_ID,
   getVaultPDA,
+  getFeeVaultPDAs,
   getMarketStatusString,
+  handleToBuffer,
   isMarketResolved,
   PositionAccount,
   MarketAccount,
@@ -31,7 +33,7 @@ interface PositionWithMarket {
 interface WinnerCheckResult {
   checked: boolean;
   isWinner: boolean | null;
-  side: "YES" | "NO" | null;
+  side: string | null;
   // Cache the decrypt result so we don't need to call decrypt() again for claiming
   decryptResult?: {
     plaintexts: string[];
@@ -176,20 +178,13 @@ export default function PortfolioPage() {
           return;
         }
 
-        // Extract is_winner_handle from simulated position account data
-        // Position layout after 8-byte discriminator:
-        //   market: 32, owner: 32, amount: 8, locked_payout: 8,
-        //   encrypted_side_handle: 16, is_winner_handle: 16, ...
-        // is_winner_handle offset = 8 + 32 + 32 + 8 + 8 + 16 = 104
+        // Decode is_winner_handle from the simulated position account
         const accountData = Buffer.from(
           (simulation.value.accounts![0] as any).data[0],
           "base64"
         );
-        const handleBytes = accountData.slice(104, 120);
-        let simHandle = BigInt(0);
-        for (let i = 15; i >= 0; i--) {
-          simHandle = simHandle * BigInt(256) + BigInt(handleBytes[i]);
-        }
+        const simPosition: PositionAccount = program.coder.accounts.decode("position", accountData);
+        const simHandle = BigInt(simPosition.isWinnerHandle.toString());
 
         console.log("Simulated is_winner_handle:", simHandle.toString());
 
@@ -267,12 +262,15 @@ export default function PortfolioPage() {
       const isWinner = plaintext !== "0" && plaintext !== "0x0" && plaintext !== "0x" + "0".repeat(32) && plaintext !== "";
 
       // Determine which side the user bet on based on market outcome
-      const marketStatus = positionData.market.status;
-      let userSide: "YES" | "NO" | null = null;
-      if ("resolvedYes" in marketStatus) {
-        userSide = isWinner ? "YES" : "NO";
-      } else if ("resolvedNo" in marketStatus) {
-        userSide = isWinner ? "NO" : "YES";
+      // (a losing side is only known for binary markets)
+      const { outcomes, winningOutcome } = positionData.market;
+      let userSide: string | null = null;
+      if (isMarketResolved(positionData.market.status)) {
+        if (isWinner) {
+          userSide = outcomes[winningOutcome];
+        } else if (outcomes.length === 2) {
+          userSide = outcomes[1 - winningOutcome];
+        }
       }
 
       setWinnerResults((prev) => ({
@@ -292,7 +290,7 @@ export default function PortfolioPage() {
       if (isWinner) {
         setClaimSuccess(`You WON! You bet ${userSide}. Click "Claim Winnings" to collect your payout.`);
       } else {
-        setClaimSuccess(`You bet ${userSide}. Unfortunately you didn't win this market. Your bet side stays private on-chain.`);
+        setClaimSuccess(`You bet ${userSide ?? "another outcome"}. Unfortunately you didn't win this market. Your bet side stays private on-chain.`);
       }
 
       fetchPositions();
@@ -319,6 +317,12 @@ export default function PortfolioPage() {
       return;
     }
 
+    // Payouts are a share of the final pools, which must be revealed first
+    if (!positionData.market.poolsRevealed) {
+      setClaimError("The market's pool totals haven't been revealed yet. Please try again later.");
+      return;
+    }
+
     setClaiming(posKey);
     setClaimError(null);
     setClaimSuccess(null);
@@ -332,6 +336,7 @@ export default function PortfolioPage() {
 
       const program = new Program(idl as any, provider);
       const [vaultPda] = getVaultPDA(positionData.marketPubkey);
+      const [protocolFeeVault, creatorFeeVault] = getFeeVaultPDAs(positionData.marketPubkey);
 
       // Use cached decrypt result from check_winner, or retry with backoff
       let decryptResult = cachedResult.decryptResult;
@@ -374,46 +379,12 @@ export default function PortfolioPage() {
         tx.add(ed25519Ix);
       }
 
-      // Encode handle and plaintext in the EXACT format the Inco SDK uses:
-      //
-      // Handle: BigInt → hex string → ASCII char codes (variable length)
-      //   e.g. 12345 → "3039" → [51, 48, 51, 57]
-      //
-      // Plaintext: 16 bytes, u128 little-endian
-      //   e.g. 1 → [1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
-      //
-      // is_validsignature recomputes SHA256(handle_bytes || plaintext_bytes)
-      // and checks it matches the Ed25519 message. Bytes must match exactly.
-
-      const handleDecStr = decryptResult.handles[0].toString();
-      const handleHexStr = BigInt(handleDecStr).toString(16);
-      const handleBytes = Buffer.from(handleHexStr.split("").map((c: string) => c.charCodeAt(0)));
-
-      const plaintextVal = BigInt(decryptResult.plaintexts[0].toString());
-      const plaintextBytes = Buffer.alloc(16);
-      const maxU64 = BigInt("18446744073709551615");
-      if (plaintextVal <= maxU64) {
-        let v = plaintextVal;
-        for (let i = 0; i < 8; i++) {
-          plaintextBytes[i] = Number(v & BigInt(0xff));
-          v >>= BigInt(8);
-        }
-      } else {
-        const low = plaintextVal & maxU64;
-        const high = plaintextVal >> BigInt(64);
-        let lv = low;
-        for (let i = 0; i < 8; i++) {
-          plaintextBytes[i] = Number(lv & BigInt(0xff));
-          lv >>= BigInt(8);
-        }
-        let hv = high;
-        for (let i = 8; i < 16; i++) {
-          plaintextBytes[i] = Number(hv & BigInt(0xff));
-          hv >>= BigInt(8);
-        }
-      }
+      // Handle and plaintext are both u128 values encoded as 16 little-endian
+      // bytes; is_validsignature recomputes the signed message from these bytes,
+      // so they must match what the covalidator signed exactly
+      const handleBytes = handleToBuffer(BigInt(decryptResult.handles[0].toString()));
+      const plaintextBytes = handleToBuffer(BigInt(decryptResult.plaintexts[0].toString()));
 
-      console.log("Handle hex string:", handleHexStr);
       console.log("Handle bytes:", Array.from(handleBytes));
       console.log("Plaintext bytes:", Array.from(plaintextBytes));
 
@@ -428,6 +399,13 @@ export default function PortfolioPage() {
           market: positionData.marketPubkey,
           position: positionData.positionPubkey,
           vault: vaultPda,
+          protocolFeeVault,
+          creatorFeeVault,
+          // SOL markets only: the token accounts are omitted
+          collateralMint: null,
+          vaultTokenAccount: null,
+          winnerTokenAccount: null,
+          tokenProgram: null,
           instructions: new PublicKey("Sysvar1nstructions1111111111111111111111111"),
           systemProgram: SystemProgram.programId,
           incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
@@ -598,7 +576,7 @@ export default function PortfolioPage() {
                           : "bg-green-500/10 text-green-400 border-green-500/20"
                       }`}
                     >
-                      {getMarketStatusString(pos.market.status)}
+                      {getMarketStatusString(pos.market)}
                     </span>
                   </div>
                 </div>
@@ -618,22 +596,9 @@ export default function PortfolioPage() {
                     </p>
                   </div>
                   <div>
-                    <p className="text-slate-500 text-xs mb-1">Locked Payout (if win)</p>
-                    <p className="text-green-400 font-medium">
-                      {(() => {
-                        try {
-                          const payout = pos.position.lockedPayout;
-                          if (!payout) return "—";
-                          const payoutBN = payout.toString();
-                          const payoutNum = parseFloat(payoutBN) / 1e9;
-                          if (payoutNum > 1000 || payoutNum < 0 || isNaN(payoutNum)) {
-                            return "—";
-                          }
-                          return `${payoutNum.toFixed(2)} SOL`;
-                        } catch {
-                          return "—";
-                        }
-                      })()}
+                    <p className="text-slate-500 text-xs mb-1">Placed</p>
+                    <p className="text-white font-medium">
+                      {new Date(pos.position.openedAt.toNumber() * 1000).toLocaleDateString()}
                     </p>
                   </div>
                 </div>
@@ -650,10 +615,12 @@ export default function PortfolioPage() {
                       <span>Side encrypted</span>
                     </div>
                   )}
-                  <div className="flex items-center gap-2 text-green-400">
-                    <Icon icon="solar:verified-check-outline" width={16} />
-                    <span>Payout locked</span>
-                  </div>
+                  {pos.market.poolsRevealed && (
+                    <div className="flex items-center gap-2 text-green-400">
+                      <Icon icon="solar:verified-check-outline" width={16} />
+                      <span>Pools revealed</span>
+                    </div>
+                  )}
                   {pos.position.claimed && (
                     <div className="flex items-center gap-2 text-[#FACC15]">
                       <Icon icon="solar:check-circle-outline" width={16} />
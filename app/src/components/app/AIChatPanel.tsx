@@ -143,7 +143,7 @@ export default function AIChatPanel({ isOpen, onClose, market }: AIChatPanelProp
               NO {market.noProbability.toFixed(0)}%
             </span>
             <span className="text-slate-500">
-              {(market.totalYesAmount + market.totalNoAmount).toFixed(2)} SOL pool
+              {(market.yesPool + market.noPool).toFixed(2)} SOL pool
             </span>
           </div>
         </div>
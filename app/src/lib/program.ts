@@ -35,16 +35,40 @@ export function getMarketPDA(marketId: BN): [PublicKey, number] {
   );
 }
 
+export function getConfigPDA(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], PROGRAM_ID);
+}
+
+// Positions are keyed by a nonce so one wallet can hold several per market;
+// the app opens a single position per market with nonce 0
 export function getPositionPDA(
   market: PublicKey,
-  owner: PublicKey
+  owner: PublicKey,
+  nonce: BN = new BN(0)
 ): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
-    [Buffer.from("position"), market.toBuffer(), owner.toBuffer()],
+    [
+      Buffer.from("position"),
+      market.toBuffer(),
+      owner.toBuffer(),
+      nonce.toArrayLike(Buffer, "le", 8),
+    ],
     PROGRAM_ID
   );
 }
 
+export function getFeeVaultPDAs(market: PublicKey): [PublicKey, PublicKey] {
+  const [protocolFeeVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from("protocol_fee_vault"), market.toBuffer()],
+    PROGRAM_ID
+  );
+  const [creatorFeeVault] = PublicKey.findProgramAddressSync(
+    [Buffer.from("creator_fee_vault"), market.toBuffer()],
+    PROGRAM_ID
+  );
+  return [protocolFeeVault, creatorFeeVault];
+}
+
 export function getVaultPDA(market: PublicKey): [PublicKey, number] {
   return PublicKey.findProgramAddressSync(
     [Buffer.from("vault"), market.toBuffer()],
@@ -72,28 +96,52 @@ export function handleToBuffer(handle: BN | bigint): Buffer {
   return bn.toArrayLike(Buffer, "le", 16);
 }
 
+// Outcome indices of a binary market created with outcomes ["NO", "YES"]
+export const OUTCOME_NO = 0;
+export const OUTCOME_YES = 1;
+
 // Market status enum
 export enum MarketStatus {
   Open = 0,
   Closed = 1,
-  ResolvedYes = 2,
-  ResolvedNo = 3,
+  Resolved = 2,
+  Cancelled = 3,
+  PendingResolution = 4,
 }
 
-export function getMarketStatusString(status: { open?: {} } | { closed?: {} } | { resolvedYes?: {} } | { resolvedNo?: {} }): string {
+export type MarketStatusValue =
+  | { open?: {} }
+  | { closed?: {} }
+  | { resolved?: {} }
+  | { cancelled?: {} }
+  | { pendingResolution?: {} };
+
+export function getMarketStatusString(
+  market: Pick<MarketAccount, "status" | "outcomes" | "winningOutcome">
+): string {
+  const { status } = market;
   if ('open' in status) return 'Open';
   if ('closed' in status) return 'Closed';
-  if ('resolvedYes' in status) return 'Resolved: YES';
-  if ('resolvedNo' in status) return 'Resolved: NO';
+  if ('resolved' in status) return `Resolved: ${market.outcomes[market.winningOutcome] ?? market.winningOutcome}`;
+  if ('cancelled' in status) return 'Cancelled';
+  if ('pendingResolution' in status) return 'Pending Resolution';
   return 'Unknown';
 }
 
-export function isMarketOpen(status: { open?: {} } | { closed?: {} } | { resolvedYes?: {} } | { resolvedNo?: {} }): boolean {
+export function isMarketOpen(status: MarketStatusValue): boolean {
   return 'open' in status;
 }
 
-export function isMarketResolved(status: { open?: {} } | { closed?: {} } | { resolvedYes?: {} } | { resolvedNo?: {} }): boolean {
-  return 'resolvedYes' in status || 'resolvedNo' in status;
+export function isMarketResolved(status: MarketStatusValue): boolean {
+  return 'resolved' in status;
+}
+
+// Bets are encrypted until reveal_pools, so before that only the seeded
+// initial liquidity of each outcome is public
+export function getVisiblePools(
+  market: Pick<MarketAccount, "poolsRevealed" | "outcomeTotals" | "initialLiquidity">
+): BN[] {
+  return market.poolsRevealed ? market.outcomeTotals : market.initialLiquidity;
 }
 
 // Market account type
@@ -102,10 +150,22 @@ export interface MarketAccount {
   marketId: BN;
   question: string;
   resolutionTime: BN;
-  status: { open?: {} } | { closed?: {} } | { resolvedYes?: {} } | { resolvedNo?: {} };
-  totalYesAmount: BN;
-  totalNoAmount: BN;
+  bettingClosesAt: BN;
+  status: MarketStatusValue;
+  collateralMint: PublicKey;
+  outcomes: string[];
+  winningOutcome: number;
+  initialLiquidity: BN[];
+  encryptedTotals: BN[];
+  outcomeTotals: BN[];
+  poolsRevealed: boolean;
+  totalDeposited: BN;
+  totalPaidOut: BN;
+  protocolFeeBps: number;
+  creatorFeeBps: number;
   participantCount: number;
+  openPositions: number;
+  createdAt: BN;
   bump: number;
 }
 
@@ -113,11 +173,14 @@ export interface MarketAccount {
 export interface PositionAccount {
   market: PublicKey;
   owner: PublicKey;
+  nonce: BN;
+  openedAt: BN;
   amount: BN;
-  lockedPayout: BN; // Locked at bet time - guaranteed payout if winner
   encryptedSideHandle: BN;
   isWinnerHandle: BN;
   claimed: boolean;
+  outcome: number;
+  shares: BN;
   bump: number;
 }
 
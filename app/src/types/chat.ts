@@ -6,8 +6,8 @@ export interface ChatMessage {
 export interface MarketContext {
   question: string;
   status: string;
-  totalYesAmount: number;
-  totalNoAmount: number;
+  yesPool: number;
+  noPool: number;
   yesProbability: number;
   noProbability: number;
   participantCount: number;
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use solana_sdk::commitment_config::CommitmentConfig;
+
+const DEFAULT_RPC_URL: &str = "https://api.mainnet-beta.solana.com";
+
+/// The fields of the Solana CLI config file (~/.config/solana/cli/config.yml) we use
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct SolanaCliConfig {
+    json_rpc_url: Option<String>,
+    keypair_path: Option<String>,
+    commitment: Option<String>,
+}
+
+/// RPC URL, keypair and commitment, resolved like the Solana CLI does:
+/// command-line flags first, then the config file, then the Solana defaults
+pub struct Settings {
+    pub rpc_url: String,
+    pub keypair_path: PathBuf,
+    pub commitment: CommitmentConfig,
+}
+
+fn solana_config_dir() -> PathBuf {
+    let home = std::env::var_os("HOME").unwrap_or_default();
+    PathBuf::from(home).join(".config").join("solana")
+}
+
+impl Settings {
+    pub fn load(
+        config_file: Option<PathBuf>,
+        url: Option<String>,
+        keypair: Option<PathBuf>,
+    ) -> Result<Self> {
+        let default_file = solana_config_dir().join("cli").join("config.yml");
+        let explicit = config_file.is_some();
+        let path = config_file.unwrap_or(default_file);
+
+        let file = if path.exists() {
+            let raw = std::fs::read_to_string(&path)
+                .with_context(|| format!("reading {}", path.display()))?;
+            serde_yaml::from_str(&raw).with_context(|| format!("parsing {}", path.display()))?
+        } else if explicit {
+            anyhow::bail!("config file {} not found", path.display());
+        } else {
+            SolanaCliConfig::default()
+        };
+
+        let rpc_url = url
+            .or(file.json_rpc_url)
+            .unwrap_or_else(|| DEFAULT_RPC_URL.to_string());
+        let keypair_path = keypair
+            .or(file.keypair_path.map(PathBuf::from))
+            .unwrap_or_else(|| solana_config_dir().join("id.json"));
+        let commitment = match file.commitment.as_deref() {
+            Some(level) => level
+                .parse()
+                .map_err(|_| anyhow::anyhow!("invalid commitment '{level}' in config"))?,
+            None => CommitmentConfig::confirmed(),
+        };
+
+        Ok(Self {
+            rpc_url: expand_moniker(&rpc_url),
+            keypair_path,
+            commitment,
+        })
+    }
+}
+
+/// Expand the Solana CLI cluster monikers (-u devnet, -u l, ...)
+fn expand_moniker(url: &str) -> String {
+    match url {
+        "m" | "mainnet-beta" => "https://api.mainnet-beta.solana.com",
+        "d" | "devnet" => "https://api.devnet.solana.com",
+        "t" | "testnet" => "https://api.testnet.solana.com",
+        "l" | "localhost" => "http://localhost:8899",
+        other => other,
+    }
+    .to_string()
+}
@@ -0,0 +1,169 @@
+use anchor_lang::prelude::Pubkey;
+use dake::lmsr;
+use dake::state::{Market, MarketKind, MarketStatus, OracleComparator, Position, ResolutionSource};
+
+/// Share of the pool bet on an outcome, in percent (None for an empty pool)
+pub fn implied_probability(outcome_total: u64, total_pool: u64) -> Option<f64> {
+    (total_pool > 0).then(|| outcome_total as f64 * 100.0 / total_pool as f64)
+}
+
+/// Gross decimal odds of an outcome: what one unit returns if it wins, before fees
+pub fn decimal_odds(outcome_total: u64, total_pool: u64) -> Option<f64> {
+    (outcome_total > 0).then(|| total_pool as f64 / outcome_total as f64)
+}
+
+fn status(market: &Market) -> &'static str {
+    match market.status {
+        MarketStatus::Open => "Open",
+        MarketStatus::Closed => "Closed",
+        MarketStatus::Resolved => "Resolved",
+        MarketStatus::Cancelled => "Cancelled",
+        MarketStatus::PendingResolution => "Pending resolution",
+    }
+}
+
+fn resolution(source: &ResolutionSource) -> String {
+    match source {
+        ResolutionSource::Authority => "Authority".to_string(),
+        ResolutionSource::Oracle(oracle) => {
+            let comparator = match oracle.comparator {
+                OracleComparator::GreaterThan => "price >",
+                OracleComparator::LessThan => "price <",
+                OracleComparator::Between => "price between",
+            };
+            format!("Oracle {} ({comparator} {})", oracle.feed, oracle.threshold)
+        }
+        ResolutionSource::Optimistic => "Optimistic (bonded proposal)".to_string(),
+        ResolutionSource::Committee {
+            resolver_set,
+            vote_window_secs,
+        } => format!("Committee {resolver_set} ({vote_window_secs}s vote window)"),
+    }
+}
+
+pub fn print_market(key: &Pubkey, market: &Market) {
+    println!("Dake Market #{} ({key})", market.market_id);
+    println!("   Question:      {}", market.question);
+    println!("   Status:        {}", status(market));
+    match market.kind {
+        MarketKind::Categorical => println!("   Kind:          Categorical"),
+        MarketKind::Scalar {
+            lower_bound,
+            upper_bound,
+        } => println!("   Kind:          Scalar [{lower_bound}, {upper_bound}]"),
+        MarketKind::Lmsr { liquidity } => println!("   Kind:          LMSR (liquidity {liquidity})"),
+    }
+    if market.uses_token_collateral() {
+        println!("   Collateral:    {}", market.collateral_mint);
+    } else {
+        println!("   Collateral:    SOL (lamports)");
+    }
+    println!("   Resolution:    {}", resolution(&market.resolution));
+    println!("   Authority:     {}", market.authority);
+    println!("   Betting closes: {}", market.betting_closes_at);
+    println!("   Resolves at:   {}", market.resolution_time);
+    println!(
+        "   Fees:          {} bps protocol, {} bps creator",
+        market.protocol_fee_bps, market.creator_fee_bps
+    );
+    println!(
+        "   Positions:     {} placed, {} open",
+        market.participant_count, market.open_positions
+    );
+
+    println!();
+    if let MarketKind::Lmsr { liquidity } = market.kind {
+        let prices = lmsr::prices_bps(&market.outcome_totals, liquidity).unwrap_or_default();
+        println!("Shares outstanding (LMSR):");
+        for (i, label) in market.outcomes.iter().enumerate() {
+            let shares = market.outcome_totals.get(i).copied().unwrap_or(0);
+            let price = prices
+                .get(i)
+                .map_or("-".to_string(), |bps| format!("{:.2}%", *bps as f64 / 100.0));
+            println!("   [{i}] {label:<16} {shares:>20}  {price:>8}");
+        }
+    } else if market.pools_revealed {
+        let total_pool = market.total_pool();
+        println!("Pools (revealed, total {total_pool}):");
+        for (i, label) in market.outcomes.iter().enumerate() {
+            let total = market.outcome_totals.get(i).copied().unwrap_or(0);
+            let probability = implied_probability(total, total_pool)
+                .map_or("-".to_string(), |p| format!("{p:.2}%"));
+            let odds = decimal_odds(total, total_pool)
+                .map_or("-".to_string(), |o| format!("{o:.4}x"));
+            println!("   [{i}] {label:<16} {total:>20}  {probability:>8}  {odds:>12}");
+        }
+    } else {
+        println!("Pools (encrypted until reveal_pools - odds unknown):");
+        for (i, label) in market.outcomes.iter().enumerate() {
+            let seed = market.initial_liquidity.get(i).copied().unwrap_or(0);
+            println!("   [{i}] {label:<16} seed {seed}");
+        }
+    }
+
+    println!();
+    println!("Liabilities:");
+    println!("   Deposited:     {}", market.total_deposited);
+    println!("   Paid out:      {}", market.total_paid_out);
+    println!("   Outstanding:   {}", market.outstanding_liability());
+    if market.is_resolved() {
+        match market.kind {
+            MarketKind::Categorical | MarketKind::Lmsr { .. } => {
+                let label = market
+                    .outcomes
+                    .get(market.winning_outcome as usize)
+                    .map_or("?", String::as_str);
+                println!(
+                    "   Winner:        [{}] {label} (pool {})",
+                    market.winning_outcome,
+                    market.winning_pool()
+                );
+            }
+            MarketKind::Scalar { .. } => println!(
+                "   Resolved value: {} (LONG share {})",
+                market.resolved_value,
+                market.scalar_long_share()
+            ),
+        }
+    }
+}
+
+pub fn print_positions(market: &Market, positions: &[(Pubkey, Position)]) {
+    println!(
+        "{:<44}  {:<44}  {:>6}  {:>20}  {:<9}  {:<7}",
+        "position", "owner", "nonce", "amount", "checked", "claimed"
+    );
+    let mut total = 0u64;
+    for (key, position) in positions {
+        let stale = if market.is_current(position) { "" } else { "  (stale)" };
+        println!(
+            "{:<44}  {:<44}  {:>6}  {:>20}  {:<9}  {:<7}{stale}",
+            key.to_string(),
+            position.owner.to_string(),
+            position.nonce,
+            position.amount,
+            position.is_winner_handle != 0,
+            position.claimed,
+        );
+        total = total.saturating_add(position.amount);
+    }
+    println!();
+    println!("{} positions, {} staked", positions.len(), total);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn odds_follow_pool_shares() {
+        assert_eq!(implied_probability(25, 100), Some(25.0));
+        assert_eq!(decimal_odds(25, 100), Some(4.0));
+    }
+
+    #[test]
+    fn empty_pools_have_no_odds() {
+        assert_eq!(implied_probability(0, 0), None);
+        assert_eq!(decimal_odds(0, 100), None);
+    }
+}
@@ -0,0 +1,558 @@
+//! dake-cli - command-line tool for Dake market operators
+//!
+//! Reads the RPC URL and keypair from the Solana CLI config
+//! (~/.config/solana/cli/config.yml) unless overridden with --url / --keypair.
+//! Every transaction command accepts --dry-run to simulate instead of sending.
+
+mod config;
+mod display;
+
+use std::cell::OnceCell;
+use std::path::PathBuf;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Discriminator;
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use dake::state::{MarketKind, Position, ResolutionSource};
+use dake_client::instructions::{self as ix, CreateMarketArgs, TokenAccounts};
+use dake_client::{accounts, pda, Market};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::config::{
+    RpcProgramAccountsConfig, RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+};
+use solana_rpc_client_api::response::RpcSimulateTransactionResult;
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signer};
+use solana_sdk::transaction::Transaction;
+
+use config::Settings;
+
+/// Offsets of Position.market and Position.owner (after the discriminator)
+const POSITION_MARKET_OFFSET: usize = 8;
+const POSITION_OWNER_OFFSET: usize = 8 + 32;
+
+#[derive(Parser)]
+#[command(name = "dake-cli", version, about = "Operate Dake prediction markets")]
+struct Cli {
+    /// Solana CLI config file [default: ~/.config/solana/cli/config.yml]
+    #[arg(short = 'C', long, global = true)]
+    config: Option<PathBuf>,
+
+    /// RPC URL or moniker (mainnet-beta, devnet, testnet, localhost)
+    #[arg(short = 'u', long, global = true)]
+    url: Option<String>,
+
+    /// Keypair file of the signer
+    #[arg(short = 'k', long, global = true)]
+    keypair: Option<PathBuf>,
+
+    /// Simulate the transaction and print its logs instead of sending it
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a market (resolved by its authority unless --optimistic)
+    CreateMarket(CreateMarketCmd),
+    /// Close a market for betting and grant the authority decrypt access to the pools
+    Close {
+        #[arg(long)]
+        market_id: u64,
+    },
+    /// Publish a closed market's pool totals with an attested decryption of every pool
+    RevealPools {
+        #[arg(long)]
+        market_id: u64,
+        /// Decrypted pool total (from the Inco SDK), one per outcome in outcome order
+        #[arg(long = "plaintext", required = true)]
+        plaintexts: Vec<u64>,
+        /// Base64 data of an Ed25519 signature-verification instruction from the Inco SDK
+        /// (repeat for each instruction, in order)
+        #[arg(long = "ed25519-ix", required = true)]
+        ed25519_ixs: Vec<String>,
+    },
+    /// Resolve a market with the winning outcome (categorical) or value (scalar)
+    Resolve {
+        #[arg(long)]
+        market_id: u64,
+        #[arg(long, conflicts_with = "value", required_unless_present = "value")]
+        outcome: Option<u8>,
+        #[arg(long, allow_hyphen_values = true)]
+        value: Option<i64>,
+    },
+    /// Show a market with its pool odds and liabilities
+    ShowMarket {
+        #[arg(long)]
+        market_id: u64,
+    },
+    /// List a market's positions
+    ListPositions {
+        #[arg(long)]
+        market_id: u64,
+        /// Only positions of this owner
+        #[arg(long)]
+        owner: Option<Pubkey>,
+    },
+    /// Run the encrypted winner check for a position and grant its owner decrypt access
+    CheckWinner(PositionArgs),
+    /// Claim a position's winnings with an attested decryption of its is_winner handle
+    Claim {
+        #[command(flatten)]
+        position: PositionArgs,
+        /// Decrypted is_winner plaintext (from the Inco SDK)
+        #[arg(long)]
+        plaintext: u128,
+        /// Base64 data of an Ed25519 signature-verification instruction from the Inco SDK
+        /// (repeat for each instruction, in order)
+        #[arg(long = "ed25519-ix", required = true)]
+        ed25519_ixs: Vec<String>,
+        /// Winner's token account (token markets only)
+        #[arg(long)]
+        token_account: Option<Pubkey>,
+    },
+}
+
+#[derive(Args)]
+struct CreateMarketCmd {
+    #[arg(long)]
+    market_id: u64,
+    #[arg(long)]
+    question: String,
+    /// Comma-separated outcome labels
+    #[arg(long, value_delimiter = ',', default_value = "NO,YES")]
+    outcomes: Vec<String>,
+    /// Comma-separated seed liquidity per outcome [default: Config default liquidity]
+    #[arg(long, value_delimiter = ',')]
+    liquidity: Vec<u64>,
+    /// Resolution time (Unix timestamp)
+    #[arg(long)]
+    resolution_time: i64,
+    /// Betting close time (Unix timestamp) [default: resolution time]
+    #[arg(long)]
+    betting_closes_at: Option<i64>,
+    #[arg(long, default_value_t = 0)]
+    creator_fee_bps: u16,
+    /// Scalar market lower bound (requires --upper-bound; outcomes become SHORT,LONG)
+    #[arg(long, requires = "upper_bound", allow_hyphen_values = true)]
+    lower_bound: Option<i64>,
+    #[arg(long, requires = "lower_bound", allow_hyphen_values = true)]
+    upper_bound: Option<i64>,
+    /// Price shares with an LMSR market maker of this liquidity instead of
+    /// parimutuel pools (the signer funds b * ln(outcomes))
+    #[arg(long, conflicts_with_all = ["lower_bound", "liquidity"])]
+    lmsr_liquidity: Option<u64>,
+    /// Resolve through bonded proposals instead of by the authority
+    #[arg(long)]
+    optimistic: bool,
+    /// Collateral mint (omit for SOL)
+    #[arg(long, requires = "token_account")]
+    mint: Option<Pubkey>,
+    /// Authority's token account for the mint
+    #[arg(long, requires = "mint")]
+    token_account: Option<Pubkey>,
+}
+
+#[derive(Args)]
+struct PositionArgs {
+    #[arg(long)]
+    market_id: u64,
+    /// Position owner [default: the signer]
+    #[arg(long)]
+    owner: Option<Pubkey>,
+    #[arg(long, default_value_t = 0)]
+    nonce: u64,
+}
+
+struct Operator {
+    rpc: RpcClient,
+    keypair_path: PathBuf,
+    payer: OnceCell<Keypair>,
+    dry_run: bool,
+}
+
+impl Operator {
+    /// The signer keypair, read on first use (show-market / list-positions need none)
+    fn payer(&self) -> Result<&Keypair> {
+        if let Some(payer) = self.payer.get() {
+            return Ok(payer);
+        }
+        let payer = read_keypair_file(&self.keypair_path).map_err(|err| {
+            anyhow::anyhow!("reading keypair {}: {err}", self.keypair_path.display())
+        })?;
+        Ok(self.payer.get_or_init(|| payer))
+    }
+
+    fn fetch_market(&self, market_id: u64) -> Result<(Pubkey, Market)> {
+        let key = pda::market(market_id).0;
+        let data = self
+            .rpc
+            .get_account_data(&key)
+            .with_context(|| format!("market #{market_id} ({key}) not found"))?;
+        Ok((key, accounts::market(&data)?))
+    }
+
+    fn fetch_position(&self, key: &Pubkey) -> Result<Position> {
+        let data = self
+            .rpc
+            .get_account_data(key)
+            .with_context(|| format!("position {key} not found"))?;
+        Ok(accounts::position(&data)?)
+    }
+
+    fn position_key(&self, market: &Pubkey, args: &PositionArgs) -> Result<Pubkey> {
+        let owner = match args.owner {
+            Some(owner) => owner,
+            None => self.payer()?.pubkey(),
+        };
+        Ok(pda::position(market, &owner, args.nonce).0)
+    }
+
+    /// Collateral accounts of a token market; the token program is the mint's owner
+    fn token_accounts(&self, mint: Pubkey, token_account: Option<Pubkey>) -> Result<TokenAccounts> {
+        let user_token_account = token_account.context("--token-account is required for token markets")?;
+        let token_program = self
+            .rpc
+            .get_account(&mint)
+            .with_context(|| format!("mint {mint} not found"))?
+            .owner;
+        Ok(TokenAccounts {
+            mint,
+            user_token_account,
+            token_program,
+        })
+    }
+
+    fn transaction(&self, instructions: &[Instruction]) -> Result<Transaction> {
+        let payer = self.payer()?;
+        let blockhash = self.rpc.get_latest_blockhash()?;
+        Ok(Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            blockhash,
+        ))
+    }
+
+    /// Simulate and return the program logs, failing if the simulation failed
+    fn simulate(&self, instructions: &[Instruction]) -> Result<Vec<String>> {
+        let result = self.simulate_with(instructions, RpcSimulateTransactionConfig::default())?;
+        Ok(result.logs.unwrap_or_default())
+    }
+
+    /// Simulate and return the data `account` would hold afterwards
+    fn simulate_account(&self, instructions: &[Instruction], account: &Pubkey) -> Result<Vec<u8>> {
+        let config = RpcSimulateTransactionConfig {
+            accounts: Some(RpcSimulateTransactionAccountsConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                addresses: vec![account.to_string()],
+            }),
+            ..RpcSimulateTransactionConfig::default()
+        };
+        self.simulate_with(instructions, config)?
+            .accounts
+            .and_then(|accounts| accounts.into_iter().next().flatten())
+            .and_then(|ui_account| ui_account.data.decode())
+            .with_context(|| format!("account {account} missing from the simulation result"))
+    }
+
+    fn simulate_with(
+        &self,
+        instructions: &[Instruction],
+        config: RpcSimulateTransactionConfig,
+    ) -> Result<RpcSimulateTransactionResult> {
+        let result = self
+            .rpc
+            .simulate_transaction_with_config(&self.transaction(instructions)?, config)?
+            .value;
+        if let Some(err) = &result.err {
+            for log in result.logs.iter().flatten() {
+                eprintln!("   {log}");
+            }
+            bail!("simulation failed: {err}");
+        }
+        Ok(result)
+    }
+
+    /// Send the instructions, or only simulate them with --dry-run
+    fn execute(&self, instructions: &[Instruction]) -> Result<()> {
+        if self.dry_run {
+            println!("Dry run - simulating only:");
+            for log in self.simulate(instructions)? {
+                println!("   {log}");
+            }
+            return Ok(());
+        }
+
+        let signature = self
+            .rpc
+            .send_and_confirm_transaction(&self.transaction(instructions)?)?;
+        println!("Signature: {signature}");
+        Ok(())
+    }
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let settings = Settings::load(cli.config, cli.url, cli.keypair)?;
+    let ctx = Operator {
+        rpc: RpcClient::new_with_commitment(settings.rpc_url, settings.commitment),
+        keypair_path: settings.keypair_path,
+        payer: OnceCell::new(),
+        dry_run: cli.dry_run,
+    };
+
+    match cli.command {
+        Command::CreateMarket(cmd) => create_market(&ctx, cmd),
+        Command::Close { market_id } => close(&ctx, market_id),
+        Command::RevealPools {
+            market_id,
+            plaintexts,
+            ed25519_ixs,
+        } => reveal_pools(&ctx, market_id, plaintexts, ed25519_ixs),
+        Command::Resolve {
+            market_id,
+            outcome,
+            value,
+        } => resolve(&ctx, market_id, outcome, value),
+        Command::ShowMarket { market_id } => {
+            let (key, market) = ctx.fetch_market(market_id)?;
+            display::print_market(&key, &market);
+            Ok(())
+        }
+        Command::ListPositions { market_id, owner } => list_positions(&ctx, market_id, owner),
+        Command::CheckWinner(args) => check_winner(&ctx, args),
+        Command::Claim {
+            position,
+            plaintext,
+            ed25519_ixs,
+            token_account,
+        } => claim(&ctx, position, plaintext, ed25519_ixs, token_account),
+    }
+}
+
+fn create_market(ctx: &Operator, cmd: CreateMarketCmd) -> Result<()> {
+    let (kind, outcomes) = match (cmd.lower_bound, cmd.upper_bound, cmd.lmsr_liquidity) {
+        (_, _, Some(liquidity)) => (MarketKind::Lmsr { liquidity }, cmd.outcomes),
+        (Some(lower_bound), Some(upper_bound), _) => (
+            MarketKind::Scalar {
+                lower_bound,
+                upper_bound,
+            },
+            vec!["SHORT".to_string(), "LONG".to_string()],
+        ),
+        _ => (MarketKind::Categorical, cmd.outcomes),
+    };
+    let token = cmd
+        .mint
+        .map(|mint| ctx.token_accounts(mint, cmd.token_account))
+        .transpose()?;
+
+    let args = CreateMarketArgs {
+        market_id: cmd.market_id,
+        question: cmd.question,
+        outcomes,
+        kind,
+        initial_liquidity: cmd.liquidity,
+        resolution_time: cmd.resolution_time,
+        betting_closes_at: cmd.betting_closes_at.unwrap_or(cmd.resolution_time),
+        creator_fee_bps: cmd.creator_fee_bps,
+        resolution: if cmd.optimistic {
+            ResolutionSource::Optimistic
+        } else {
+            ResolutionSource::Authority
+        },
+    };
+
+    println!("Creating Dake Market #{} ({})", cmd.market_id, pda::market(cmd.market_id).0);
+    ctx.execute(&[ix::create_market(&ctx.payer()?.pubkey(), args, token)])
+}
+
+fn close(ctx: &Operator, market_id: u64) -> Result<()> {
+    let (key, market) = ctx.fetch_market(market_id)?;
+    ctx.execute(&[ix::close_market(
+        &ctx.payer()?.pubkey(),
+        &key,
+        &market.encrypted_totals,
+    )])
+}
+
+fn resolve(ctx: &Operator, market_id: u64, outcome: Option<u8>, value: Option<i64>) -> Result<()> {
+    let (key, market) = ctx.fetch_market(market_id)?;
+    let authority = ctx.payer()?.pubkey();
+
+    let instruction = match (market.kind, outcome, value) {
+        (MarketKind::Categorical | MarketKind::Lmsr { .. }, Some(outcome), _) => {
+            ix::resolve_market(&authority, &key, outcome)
+        }
+        (MarketKind::Scalar { .. }, _, Some(value)) => {
+            ix::resolve_scalar_market(&authority, &key, value)
+        }
+        (MarketKind::Categorical | MarketKind::Lmsr { .. }, None, _) => {
+            bail!("categorical markets resolve with --outcome")
+        }
+        (MarketKind::Scalar { .. }, _, None) => bail!("scalar markets resolve with --value"),
+    };
+    ctx.execute(&[instruction])
+}
+
+fn list_positions(ctx: &Operator, market_id: u64, owner: Option<Pubkey>) -> Result<()> {
+    let (key, market) = ctx.fetch_market(market_id)?;
+
+    let mut filters = vec![
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, Position::DISCRIMINATOR.to_vec())),
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            POSITION_MARKET_OFFSET,
+            key.to_bytes().to_vec(),
+        )),
+    ];
+    if let Some(owner) = owner {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            POSITION_OWNER_OFFSET,
+            owner.to_bytes().to_vec(),
+        )));
+    }
+
+    let mut positions = ctx
+        .rpc
+        .get_program_accounts_with_config(
+            &dake::ID,
+            RpcProgramAccountsConfig {
+                filters: Some(filters),
+                ..Default::default()
+            },
+        )?
+        .into_iter()
+        .map(|(key, account)| Ok((key, accounts::position(&account.data)?)))
+        .collect::<Result<Vec<_>>>()?;
+    positions.sort_by_key(|(_, position)| (position.owner, position.nonce));
+
+    display::print_positions(&market, &positions);
+    Ok(())
+}
+
+fn check_winner(ctx: &Operator, args: PositionArgs) -> Result<()> {
+    let (market, _) = ctx.fetch_market(args.market_id)?;
+    let position = ctx.position_key(&market, &args)?;
+    let owner = ctx.fetch_position(&position)?.owner;
+    let checker = ctx.payer()?.pubkey();
+
+    // The result handle is only known once the comparison runs, so simulate first
+    // and read it from the simulated position to derive the owner's allowance PDA
+    let data = ctx.simulate_account(
+        &[ix::check_winner(&checker, &market, &position, None)],
+        &position,
+    )?;
+    let handle = accounts::position(&data)?.is_winner_handle;
+
+    ctx.execute(&[ix::check_winner(
+        &checker,
+        &market,
+        &position,
+        Some((handle, owner)),
+    )])?;
+    if !ctx.dry_run {
+        let stored = ctx.fetch_position(&position)?.is_winner_handle;
+        if stored != handle {
+            bail!("position {position} stored is_winner handle {stored}, expected {handle}");
+        }
+    }
+
+    println!("Is winner handle: {handle}");
+    println!("   Decrypt it with the Inco SDK as {owner}, then run `dake-cli claim`");
+    Ok(())
+}
+
+fn reveal_pools(
+    ctx: &Operator,
+    market_id: u64,
+    plaintexts: Vec<u64>,
+    ed25519_ixs: Vec<String>,
+) -> Result<()> {
+    let (key, market) = ctx.fetch_market(market_id)?;
+    if market.pools_revealed {
+        bail!("market #{market_id} pools are already revealed");
+    }
+    if plaintexts.len() != market.outcome_count() {
+        bail!(
+            "market #{market_id} has {} outcomes, got {} --plaintext values",
+            market.outcome_count(),
+            plaintexts.len()
+        );
+    }
+
+    let mut instructions = ed25519_instructions(&ed25519_ixs)?;
+    instructions.push(ix::reveal_pools(
+        &ctx.payer()?.pubkey(),
+        &key,
+        market
+            .encrypted_totals
+            .iter()
+            .map(|handle| handle.to_le_bytes().to_vec())
+            .collect(),
+        plaintexts
+            .iter()
+            .map(|total| (*total as u128).to_le_bytes().to_vec())
+            .collect(),
+    ));
+
+    ctx.execute(&instructions)
+}
+
+/// Ed25519 signature-verification instructions from their base64 data
+fn ed25519_instructions(ed25519_ixs: &[String]) -> Result<Vec<Instruction>> {
+    use base64::Engine;
+
+    ed25519_ixs
+        .iter()
+        .map(|data| {
+            let data = base64::engine::general_purpose::STANDARD
+                .decode(data)
+                .context("--ed25519-ix must be base64")?;
+            Ok(Instruction::new_with_bytes(
+                solana_sdk::ed25519_program::ID,
+                &data,
+                vec![],
+            ))
+        })
+        .collect()
+}
+
+fn claim(
+    ctx: &Operator,
+    args: PositionArgs,
+    plaintext: u128,
+    ed25519_ixs: Vec<String>,
+    token_account: Option<Pubkey>,
+) -> Result<()> {
+    let (market_key, market) = ctx.fetch_market(args.market_id)?;
+    let position_key = ctx.position_key(&market_key, &args)?;
+    let position = ctx.fetch_position(&position_key)?;
+    if position.is_winner_handle == 0 {
+        bail!("position {position_key} has not been checked - run `dake-cli check-winner` first");
+    }
+
+    let token = if market.uses_token_collateral() {
+        Some(ctx.token_accounts(market.collateral_mint, token_account)?)
+    } else {
+        None
+    };
+
+    let mut instructions = ed25519_instructions(&ed25519_ixs)?;
+    instructions.push(ix::claim_winnings(
+        &ctx.payer()?.pubkey(),
+        &market_key,
+        &position_key,
+        position.is_winner_handle.to_le_bytes().to_vec(),
+        plaintext.to_le_bytes().to_vec(),
+        token,
+    ));
+
+    ctx.execute(&instructions)
+}
@@ -0,0 +1,13 @@
+use anchor_lang::AccountDeserialize;
+use anchor_lang::Result;
+use dake::state::{Market, Position};
+
+/// Deserialize a Market from raw account data (discriminator checked)
+pub fn market(data: &[u8]) -> Result<Market> {
+    Market::try_deserialize(&mut &data[..])
+}
+
+/// Deserialize a Position from raw account data (discriminator checked)
+pub fn position(data: &[u8]) -> Result<Position> {
+    Position::try_deserialize(&mut &data[..])
+}
@@ -0,0 +1,1151 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use dake::state::{ConfigParams, MarketKind, ResolutionSource};
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+
+use crate::pda;
+
+/// Collateral accounts of a token market (omit for native SOL markets)
+#[derive(Clone, Copy, Debug)]
+pub struct TokenAccounts {
+    /// Collateral mint
+    pub mint: Pubkey,
+    /// The signer's token account for the mint
+    pub user_token_account: Pubkey,
+    /// SPL Token or Token-2022 program owning the mint
+    pub token_program: Pubkey,
+}
+
+/// Arguments of create_market
+#[derive(Clone)]
+pub struct CreateMarketArgs {
+    pub market_id: u64,
+    pub question: String,
+    pub outcomes: Vec<String>,
+    pub kind: MarketKind,
+    pub initial_liquidity: Vec<u64>,
+    pub resolution_time: i64,
+    pub betting_closes_at: i64,
+    pub creator_fee_bps: u16,
+    pub resolution: ResolutionSource,
+}
+
+/// Allowance remaining accounts letting `allowed` decrypt `handle`
+pub fn allowance_accounts(handle: u128, allowed: &Pubkey) -> [AccountMeta; 2] {
+    [
+        AccountMeta::new(pda::allowance(handle, allowed).0, false),
+        AccountMeta::new_readonly(*allowed, false),
+    ]
+}
+
+fn instruction(accounts: Vec<AccountMeta>, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: dake::ID,
+        accounts,
+        data: data.data(),
+    }
+}
+
+/// create_market
+pub fn create_market(
+    authority: &Pubkey,
+    args: CreateMarketArgs,
+    token: Option<TokenAccounts>,
+) -> Instruction {
+    let market = pda::market(args.market_id).0;
+    let accounts = dake::accounts::CreateMarket {
+        authority: *authority,
+        market,
+        config: pda::config().0,
+        protocol_fee_vault: pda::protocol_fee_vault(&market).0,
+        creator_fee_vault: pda::creator_fee_vault(&market).0,
+        vault: pda::vault(&market).0,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(&market).0),
+        authority_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        system_program: system_program::ID,
+        inco_lightning_program: INCO_LIGHTNING_ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::CreateMarket {
+            market_id: args.market_id,
+            question: args.question,
+            outcomes: args.outcomes,
+            kind: args.kind,
+            initial_liquidity: args.initial_liquidity,
+            resolution_time: args.resolution_time,
+            betting_closes_at: args.betting_closes_at,
+            creator_fee_bps: args.creator_fee_bps,
+            resolution: args.resolution,
+        },
+    )
+}
+
+/// place_bet
+/// - min_payout: slippage guard, 0 for none (see dake::instructions::place_bet)
+/// - side_handle: the side handle this bet will create (e.g. from a simulation) -
+///   adds the allowance accounts so the bettor can decrypt their own side
+#[allow(clippy::too_many_arguments)]
+pub fn place_bet(
+    bettor: &Pubkey,
+    market: &Pubkey,
+    encrypted_side: Vec<u8>,
+    amount: u64,
+    position_nonce: u64,
+    min_payout: u64,
+    token: Option<TokenAccounts>,
+    side_handle: Option<u128>,
+) -> Instruction {
+    let accounts = dake::accounts::PlaceBet {
+        bettor: *bettor,
+        market: *market,
+        config: pda::config().0,
+        position: pda::position(market, bettor, position_nonce).0,
+        vault: pda::vault(market).0,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(market).0),
+        bettor_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        system_program: system_program::ID,
+        inco_lightning_program: INCO_LIGHTNING_ID,
+    };
+
+    let mut metas = accounts.to_account_metas(None);
+    if let Some(handle) = side_handle {
+        metas.extend(allowance_accounts(handle, bettor));
+    }
+
+    instruction(
+        metas,
+        dake::instruction::PlaceBet {
+            encrypted_side,
+            amount,
+            position_nonce,
+            min_payout,
+        },
+    )
+}
+
+/// buy_shares (LMSR markets)
+/// - max_cost: the trade fails if it costs more (quote with dake::lmsr::buy_cost)
+pub fn buy_shares(
+    buyer: &Pubkey,
+    market: &Pubkey,
+    outcome: u8,
+    shares: u64,
+    max_cost: u64,
+    position_nonce: u64,
+    token: Option<TokenAccounts>,
+) -> Instruction {
+    let accounts = dake::accounts::BuyShares {
+        buyer: *buyer,
+        market: *market,
+        config: pda::config().0,
+        position: pda::position(market, buyer, position_nonce).0,
+        vault: pda::vault(market).0,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(market).0),
+        buyer_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        system_program: system_program::ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::BuyShares {
+            outcome,
+            shares,
+            max_cost,
+            position_nonce,
+        },
+    )
+}
+
+/// close_market
+/// - encrypted_totals: the market's pool handles - adds one allowance pair per
+///   outcome so the closer can decrypt the totals (empty to skip)
+///
+/// The closer must be the market authority until betting_closes_at, anyone after.
+pub fn close_market(closer: &Pubkey, market: &Pubkey, encrypted_totals: &[u128]) -> Instruction {
+    let accounts = dake::accounts::CloseMarket {
+        closer: *closer,
+        market: *market,
+        system_program: system_program::ID,
+        inco_lightning_program: INCO_LIGHTNING_ID,
+    };
+
+    let mut metas = accounts.to_account_metas(None);
+    for handle in encrypted_totals {
+        metas.extend(allowance_accounts(*handle, closer));
+    }
+
+    instruction(metas, dake::instruction::CloseMarket {})
+}
+
+/// reveal_pools - must follow the Ed25519 signature-verification instructions
+pub fn reveal_pools(
+    revealer: &Pubkey,
+    market: &Pubkey,
+    handles: Vec<Vec<u8>>,
+    plaintexts: Vec<Vec<u8>>,
+) -> Instruction {
+    let accounts = dake::accounts::RevealPools {
+        revealer: *revealer,
+        market: *market,
+        instructions: sysvar::instructions::ID,
+        inco_lightning_program: INCO_LIGHTNING_ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::RevealPools {
+            handles,
+            plaintexts,
+        },
+    )
+}
+
+/// grant_pool_access - lets the caller decrypt the pool totals of a closed market
+/// - encrypted_totals: the market's pool handles (one allowance pair per outcome)
+pub fn grant_pool_access(caller: &Pubkey, market: &Pubkey, encrypted_totals: &[u128]) -> Instruction {
+    let accounts = dake::accounts::GrantPoolAccess {
+        caller: *caller,
+        market: *market,
+        system_program: system_program::ID,
+        inco_lightning_program: INCO_LIGHTNING_ID,
+    };
+
+    let mut metas = accounts.to_account_metas(None);
+    for handle in encrypted_totals {
+        metas.extend(allowance_accounts(*handle, caller));
+    }
+
+    instruction(metas, dake::instruction::GrantPoolAccess {})
+}
+
+/// resolve_market
+pub fn resolve_market(authority: &Pubkey, market: &Pubkey, outcome: u8) -> Instruction {
+    let accounts = dake::accounts::ResolveMarket {
+        authority: *authority,
+        market: *market,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::ResolveMarket { outcome },
+    )
+}
+
+/// resolve_scalar_market
+pub fn resolve_scalar_market(authority: &Pubkey, market: &Pubkey, value: i64) -> Instruction {
+    let accounts = dake::accounts::ResolveScalarMarket {
+        authority: *authority,
+        market: *market,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::ResolveScalarMarket { value },
+    )
+}
+
+/// resolve_from_oracle - permissionless, reads the market's Pyth price feed
+pub fn resolve_from_oracle(resolver: &Pubkey, market: &Pubkey, price_feed: &Pubkey) -> Instruction {
+    let accounts = dake::accounts::ResolveFromOracle {
+        resolver: *resolver,
+        market: *market,
+        price_feed: *price_feed,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::ResolveFromOracle {},
+    )
+}
+
+/// propose_resolution - the proposer posts Config::resolution_bond
+/// - created_at: the market's created_at (scopes the proposal PDA)
+pub fn propose_resolution(
+    proposer: &Pubkey,
+    market: &Pubkey,
+    created_at: i64,
+    outcome: u8,
+    value: i64,
+) -> Instruction {
+    let accounts = dake::accounts::ProposeResolution {
+        proposer: *proposer,
+        market: *market,
+        config: pda::config().0,
+        proposal: pda::proposal(market, created_at).0,
+        system_program: system_program::ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::ProposeResolution { outcome, value },
+    )
+}
+
+/// dispute_resolution - the disputer matches the proposer's bond
+pub fn dispute_resolution(disputer: &Pubkey, market: &Pubkey, created_at: i64) -> Instruction {
+    let accounts = dake::accounts::DisputeResolution {
+        disputer: *disputer,
+        market: *market,
+        proposal: pda::proposal(market, created_at).0,
+        system_program: system_program::ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::DisputeResolution {},
+    )
+}
+
+/// finalize_resolution - once the dispute window passed undisputed
+/// - proposer: receives the bond back
+pub fn finalize_resolution(
+    finalizer: &Pubkey,
+    market: &Pubkey,
+    created_at: i64,
+    proposer: &Pubkey,
+) -> Instruction {
+    let accounts = dake::accounts::FinalizeResolution {
+        finalizer: *finalizer,
+        market: *market,
+        proposal: pda::proposal(market, created_at).0,
+        proposer: *proposer,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::FinalizeResolution {},
+    )
+}
+
+/// arbitrate_resolution - the Config admin rules on a disputed proposal
+pub fn arbitrate_resolution(
+    admin: &Pubkey,
+    market: &Pubkey,
+    created_at: i64,
+    proposer: &Pubkey,
+    disputer: &Pubkey,
+    outcome: u8,
+    value: i64,
+) -> Instruction {
+    let accounts = dake::accounts::ArbitrateResolution {
+        admin: *admin,
+        config: pda::config().0,
+        market: *market,
+        proposal: pda::proposal(market, created_at).0,
+        proposer: *proposer,
+        disputer: *disputer,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::ArbitrateResolution { outcome, value },
+    )
+}
+
+/// expire_arbitration - cancels an optimistic market whose dispute was not ruled on in time
+/// - authority: the market authority, which gets the seed liquidity back
+/// - token: user_token_account is the authority's token account
+pub fn expire_arbitration(
+    caller: &Pubkey,
+    market: &Pubkey,
+    created_at: i64,
+    authority: &Pubkey,
+    proposer: &Pubkey,
+    disputer: &Pubkey,
+    token: Option<TokenAccounts>,
+) -> Instruction {
+    let accounts = dake::accounts::ExpireArbitration {
+        caller: *caller,
+        market: *market,
+        authority: *authority,
+        proposal: pda::proposal(market, created_at).0,
+        proposer: *proposer,
+        disputer: *disputer,
+        vault: pda::vault(market).0,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(market).0),
+        authority_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        system_program: system_program::ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::ExpireArbitration {},
+    )
+}
+
+/// create_resolver_set
+pub fn create_resolver_set(
+    authority: &Pubkey,
+    set_id: u64,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Instruction {
+    let accounts = dake::accounts::CreateResolverSet {
+        authority: *authority,
+        resolver_set: pda::resolver_set(authority, set_id).0,
+        system_program: system_program::ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::CreateResolverSet {
+            set_id,
+            members,
+            threshold,
+        },
+    )
+}
+
+/// vote_resolution
+/// - resolver_set: the set named by the market's Committee resolution
+pub fn vote_resolution(
+    resolver: &Pubkey,
+    market: &Pubkey,
+    created_at: i64,
+    resolver_set: &Pubkey,
+    outcome: u8,
+    value: i64,
+) -> Instruction {
+    let accounts = dake::accounts::VoteResolution {
+        resolver: *resolver,
+        market: *market,
+        resolver_set: *resolver_set,
+        votes: pda::votes(market, created_at).0,
+        system_program: system_program::ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::VoteResolution { outcome, value },
+    )
+}
+
+/// expire_resolution_vote - cancels a committee market whose vote deadline passed
+/// - authority: the market authority, which gets the seed liquidity back
+/// - token: user_token_account is the authority's token account
+pub fn expire_resolution_vote(
+    caller: &Pubkey,
+    market: &Pubkey,
+    authority: &Pubkey,
+    token: Option<TokenAccounts>,
+) -> Instruction {
+    let accounts = dake::accounts::ExpireResolutionVote {
+        caller: *caller,
+        market: *market,
+        authority: *authority,
+        vault: pda::vault(market).0,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(market).0),
+        authority_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        system_program: system_program::ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::ExpireResolutionVote {},
+    )
+}
+
+/// check_winner
+/// - result: (is_winner handle, position owner) - adds the allowance accounts so
+///   the owner can decrypt the result in the same transaction
+pub fn check_winner(
+    checker: &Pubkey,
+    market: &Pubkey,
+    position: &Pubkey,
+    result: Option<(u128, Pubkey)>,
+) -> Instruction {
+    let accounts = dake::accounts::CheckWinner {
+        checker: *checker,
+        market: *market,
+        position: *position,
+        system_program: system_program::ID,
+        inco_lightning_program: INCO_LIGHTNING_ID,
+    };
+
+    let mut metas = accounts.to_account_metas(None);
+    if let Some((handle, owner)) = result {
+        metas.extend(allowance_accounts(handle, &owner));
+    }
+
+    instruction(metas, dake::instruction::CheckWinner {})
+}
+
+/// claim_winnings - must follow the Ed25519 signature-verification instruction
+pub fn claim_winnings(
+    winner: &Pubkey,
+    market: &Pubkey,
+    position: &Pubkey,
+    handle: Vec<u8>,
+    plaintext: Vec<u8>,
+    token: Option<TokenAccounts>,
+) -> Instruction {
+    let accounts = dake::accounts::ClaimWinnings {
+        winner: *winner,
+        market: *market,
+        position: *position,
+        vault: pda::vault(market).0,
+        protocol_fee_vault: pda::protocol_fee_vault(market).0,
+        creator_fee_vault: pda::creator_fee_vault(market).0,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(market).0),
+        winner_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        instructions: sysvar::instructions::ID,
+        system_program: system_program::ID,
+        inco_lightning_program: INCO_LIGHTNING_ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::ClaimWinnings { handle, plaintext },
+    )
+}
+
+/// redeem_shares (LMSR markets)
+pub fn redeem_shares(
+    winner: &Pubkey,
+    market: &Pubkey,
+    position: &Pubkey,
+    token: Option<TokenAccounts>,
+) -> Instruction {
+    let accounts = dake::accounts::RedeemShares {
+        winner: *winner,
+        market: *market,
+        position: *position,
+        vault: pda::vault(market).0,
+        protocol_fee_vault: pda::protocol_fee_vault(market).0,
+        creator_fee_vault: pda::creator_fee_vault(market).0,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(market).0),
+        winner_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        system_program: system_program::ID,
+    };
+
+    instruction(accounts.to_account_metas(None), dake::instruction::RedeemShares {})
+}
+
+/// grant_decrypt_access
+/// - is_winner_handle: the position's is_winner_handle (set by check_winner)
+pub fn grant_decrypt_access(owner: &Pubkey, position: &Pubkey, is_winner_handle: u128) -> Instruction {
+    let accounts = dake::accounts::GrantDecryptAccess {
+        owner: *owner,
+        position: *position,
+        system_program: system_program::ID,
+        inco_lightning_program: INCO_LIGHTNING_ID,
+    };
+
+    let mut metas = accounts.to_account_metas(None);
+    metas.extend(allowance_accounts(is_winner_handle, owner));
+
+    instruction(metas, dake::instruction::GrantDecryptAccess {})
+}
+
+/// cancel_market - voids the market and returns the seed liquidity to the authority
+pub fn cancel_market(authority: &Pubkey, market: &Pubkey, token: Option<TokenAccounts>) -> Instruction {
+    let accounts = dake::accounts::CancelMarket {
+        authority: *authority,
+        market: *market,
+        vault: pda::vault(market).0,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(market).0),
+        authority_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        system_program: system_program::ID,
+    };
+
+    instruction(accounts.to_account_metas(None), dake::instruction::CancelMarket {})
+}
+
+/// refund_position - returns the stake of a position in a cancelled market
+pub fn refund_position(
+    owner: &Pubkey,
+    market: &Pubkey,
+    position: &Pubkey,
+    token: Option<TokenAccounts>,
+) -> Instruction {
+    let accounts = dake::accounts::RefundPosition {
+        owner: *owner,
+        market: *market,
+        position: *position,
+        vault: pda::vault(market).0,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(market).0),
+        owner_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        system_program: system_program::ID,
+    };
+
+    instruction(accounts.to_account_metas(None), dake::instruction::RefundPosition {})
+}
+
+/// exit_position - returns the stake minus Config::exit_fee_bps while betting is open
+pub fn exit_position(
+    owner: &Pubkey,
+    market: &Pubkey,
+    position: &Pubkey,
+    token: Option<TokenAccounts>,
+) -> Instruction {
+    let accounts = dake::accounts::ExitPosition {
+        owner: *owner,
+        market: *market,
+        config: pda::config().0,
+        position: *position,
+        vault: pda::vault(market).0,
+        creator_fee_vault: pda::creator_fee_vault(market).0,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(market).0),
+        owner_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        system_program: system_program::ID,
+        inco_lightning_program: INCO_LIGHTNING_ID,
+    };
+
+    instruction(accounts.to_account_metas(None), dake::instruction::ExitPosition {})
+}
+
+/// withdraw_protocol_fees (Config admin)
+pub fn withdraw_protocol_fees(admin: &Pubkey, market: &Pubkey, token: Option<TokenAccounts>) -> Instruction {
+    let accounts = dake::accounts::WithdrawProtocolFees {
+        admin: *admin,
+        config: pda::config().0,
+        market: *market,
+        protocol_fee_vault: pda::protocol_fee_vault(market).0,
+        vault: pda::vault(market).0,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(market).0),
+        admin_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        system_program: system_program::ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::WithdrawProtocolFees {},
+    )
+}
+
+/// withdraw_creator_fees (market authority)
+pub fn withdraw_creator_fees(
+    authority: &Pubkey,
+    market: &Pubkey,
+    token: Option<TokenAccounts>,
+) -> Instruction {
+    let accounts = dake::accounts::WithdrawCreatorFees {
+        authority: *authority,
+        market: *market,
+        creator_fee_vault: pda::creator_fee_vault(market).0,
+        vault: pda::vault(market).0,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(market).0),
+        authority_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        system_program: system_program::ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::WithdrawCreatorFees {},
+    )
+}
+
+/// close_position - returns a settled position's rent to its owner
+pub fn close_position(owner: &Pubkey, market: &Pubkey, position: &Pubkey) -> Instruction {
+    let accounts = dake::accounts::ClosePosition {
+        owner: *owner,
+        market: *market,
+        position: *position,
+    };
+
+    instruction(accounts.to_account_metas(None), dake::instruction::ClosePosition {})
+}
+
+/// finalize_market - sweeps the vault to the authority and closes the market
+/// - admin: the Config admin, which gets any protocol fees it has not withdrawn
+/// - admin_token_account: the admin's token account for those fees (token markets only)
+pub fn finalize_market(
+    authority: &Pubkey,
+    market: &Pubkey,
+    admin: &Pubkey,
+    token: Option<TokenAccounts>,
+    admin_token_account: Option<Pubkey>,
+) -> Instruction {
+    let accounts = dake::accounts::FinalizeMarket {
+        authority: *authority,
+        market: *market,
+        vault: pda::vault(market).0,
+        protocol_fee_vault: pda::protocol_fee_vault(market).0,
+        creator_fee_vault: pda::creator_fee_vault(market).0,
+        config: pda::config().0,
+        admin: *admin,
+        collateral_mint: token.map(|t| t.mint),
+        vault_token_account: token.map(|_| pda::vault_token(market).0),
+        authority_token_account: token.map(|t| t.user_token_account),
+        token_program: token.map(|t| t.token_program),
+        admin_token_account,
+        system_program: system_program::ID,
+    };
+
+    instruction(accounts.to_account_metas(None), dake::instruction::FinalizeMarket {})
+}
+
+/// initialize_config - the signer must be the program's upgrade authority
+pub fn initialize_config(admin: &Pubkey, params: ConfigParams) -> Instruction {
+    let accounts = dake::accounts::InitializeConfig {
+        admin: *admin,
+        config: pda::config().0,
+        program: dake::ID,
+        program_data: pda::program_data().0,
+        system_program: system_program::ID,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::InitializeConfig { params },
+    )
+}
+
+/// update_config (Config admin)
+pub fn update_config(admin: &Pubkey, params: ConfigParams) -> Instruction {
+    let accounts = dake::accounts::UpdateConfig {
+        admin: *admin,
+        config: pda::config().0,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::UpdateConfig { params },
+    )
+}
+
+/// set_paused (Config admin)
+pub fn set_paused(admin: &Pubkey, paused: bool) -> Instruction {
+    let accounts = dake::accounts::SetPaused {
+        admin: *admin,
+        config: pda::config().0,
+    };
+
+    instruction(accounts.to_account_metas(None), dake::instruction::SetPaused { paused })
+}
+
+/// propose_admin - first step of an admin handover (Config admin)
+pub fn propose_admin(admin: &Pubkey, new_admin: Pubkey) -> Instruction {
+    let accounts = dake::accounts::ProposeAdmin {
+        admin: *admin,
+        config: pda::config().0,
+    };
+
+    instruction(
+        accounts.to_account_metas(None),
+        dake::instruction::ProposeAdmin { new_admin },
+    )
+}
+
+/// accept_admin - second step, signed by the proposed admin
+pub fn accept_admin(new_admin: &Pubkey) -> Instruction {
+    let accounts = dake::accounts::AcceptAdmin {
+        new_admin: *new_admin,
+        config: pda::config().0,
+    };
+
+    instruction(accounts.to_account_metas(None), dake::instruction::AcceptAdmin {})
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    fn keys(ix: &Instruction) -> Vec<Pubkey> {
+        ix.accounts.iter().map(|meta| meta.pubkey).collect()
+    }
+
+    fn token() -> TokenAccounts {
+        TokenAccounts {
+            mint: Pubkey::new_unique(),
+            user_token_account: Pubkey::new_unique(),
+            token_program: Pubkey::new_unique(),
+        }
+    }
+
+    /// collateral_mint, vault_token_account, <user>_token_account, token_program
+    fn token_keys(market: &Pubkey, token: TokenAccounts) -> [Pubkey; 4] {
+        [token.mint, pda::vault_token(market).0, token.user_token_account, token.token_program]
+    }
+
+    fn params() -> ConfigParams {
+        ConfigParams {
+            protocol_fee_bps: 100,
+            max_creator_fee_bps: 500,
+            exit_fee_bps: 200,
+            min_bet: 1,
+            max_bet: 0,
+            default_liquidity: 0,
+            allowed_mints: vec![],
+            resolution_bond: 1,
+            dispute_window_secs: 60,
+        }
+    }
+
+    #[test]
+    fn close_market_adds_allowance_pair_per_outcome() {
+        let closer = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let ix = close_market(&closer, &market, &[11, 22, 33]);
+
+        assert_eq!(ix.accounts.len(), 4 + 3 * 2);
+        assert_eq!(ix.accounts[4].pubkey, pda::allowance(11, &closer).0);
+        assert!(ix.accounts[4].is_writable);
+        assert_eq!(ix.accounts[5].pubkey, closer);
+        assert_eq!(ix.data, dake::instruction::CloseMarket::DISCRIMINATOR);
+    }
+
+    #[test]
+    fn sol_market_omits_token_accounts() {
+        let bettor = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let ix = place_bet(&bettor, &market, vec![1], 10, 3, 0, None, None);
+
+        // Omitted optional accounts are passed as the program id
+        assert_eq!(ix.accounts[5].pubkey, dake::ID);
+        assert_eq!(ix.accounts[3].pubkey, pda::position(&market, &bettor, 3).0);
+    }
+
+    #[test]
+    fn lmsr_trades_pass_token_accounts_in_order() {
+        let buyer = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let token = token();
+        let ix = buy_shares(&buyer, &market, 1, 100, 60, 2, Some(token));
+        let position = pda::position(&market, &buyer, 2).0;
+        assert_eq!(ix.accounts[3].pubkey, position);
+        assert_eq!(ix.accounts[5].pubkey, token.mint);
+        assert_eq!(ix.accounts[6].pubkey, pda::vault_token(&market).0);
+        assert_eq!(ix.accounts[7].pubkey, token.user_token_account);
+        assert_eq!(ix.accounts.len(), 10);
+
+        let ix = redeem_shares(&buyer, &market, &position, None);
+        assert_eq!(ix.accounts[3].pubkey, pda::vault(&market).0);
+        assert_eq!(ix.accounts[5].pubkey, pda::creator_fee_vault(&market).0);
+        assert_eq!(ix.accounts[6].pubkey, dake::ID);
+        assert_eq!(ix.data, dake::instruction::RedeemShares::DISCRIMINATOR);
+    }
+
+    #[test]
+    fn grant_pool_access_adds_allowance_pair_per_outcome() {
+        let caller = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let ix = grant_pool_access(&caller, &market, &[11, 22]);
+
+        assert_eq!(
+            keys(&ix),
+            vec![
+                caller,
+                market,
+                system_program::ID,
+                INCO_LIGHTNING_ID,
+                pda::allowance(11, &caller).0,
+                caller,
+                pda::allowance(22, &caller).0,
+                caller,
+            ]
+        );
+        assert!(ix.accounts[0].is_signer && ix.accounts[0].is_writable);
+        assert_eq!(ix.data, dake::instruction::GrantPoolAccess::DISCRIMINATOR);
+    }
+
+    #[test]
+    fn cancel_market_passes_the_authority_token_account() {
+        let authority = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let token = token();
+        let ix = cancel_market(&authority, &market, Some(token));
+
+        let mut expected = vec![authority, market, pda::vault(&market).0];
+        expected.extend(token_keys(&market, token));
+        expected.push(system_program::ID);
+        assert_eq!(keys(&ix), expected);
+        assert!(ix.accounts[0].is_signer);
+        assert_eq!(ix.data, dake::instruction::CancelMarket::DISCRIMINATOR);
+    }
+
+    #[test]
+    fn refund_position_passes_the_owner_token_account() {
+        let owner = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let position = pda::position(&market, &owner, 0).0;
+        let token = token();
+        let ix = refund_position(&owner, &market, &position, Some(token));
+
+        let mut expected = vec![owner, market, position, pda::vault(&market).0];
+        expected.extend(token_keys(&market, token));
+        expected.push(system_program::ID);
+        assert_eq!(keys(&ix), expected);
+        assert!(ix.accounts[2].is_writable);
+    }
+
+    #[test]
+    fn exit_position_passes_config_and_creator_fee_vault() {
+        let owner = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let position = pda::position(&market, &owner, 0).0;
+        let ix = exit_position(&owner, &market, &position, None);
+
+        assert_eq!(
+            keys(&ix),
+            vec![
+                owner,
+                market,
+                pda::config().0,
+                position,
+                pda::vault(&market).0,
+                pda::creator_fee_vault(&market).0,
+                dake::ID,
+                dake::ID,
+                dake::ID,
+                dake::ID,
+                system_program::ID,
+                INCO_LIGHTNING_ID,
+            ]
+        );
+    }
+
+    #[test]
+    fn withdraw_protocol_fees_passes_config_before_market() {
+        let admin = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let token = token();
+        let ix = withdraw_protocol_fees(&admin, &market, Some(token));
+
+        let mut expected = vec![
+            admin,
+            pda::config().0,
+            market,
+            pda::protocol_fee_vault(&market).0,
+            pda::vault(&market).0,
+        ];
+        expected.extend(token_keys(&market, token));
+        expected.push(system_program::ID);
+        assert_eq!(keys(&ix), expected);
+    }
+
+    #[test]
+    fn withdraw_creator_fees_passes_the_creator_fee_vault() {
+        let authority = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let token = token();
+        let ix = withdraw_creator_fees(&authority, &market, Some(token));
+
+        let mut expected = vec![
+            authority,
+            market,
+            pda::creator_fee_vault(&market).0,
+            pda::vault(&market).0,
+        ];
+        expected.extend(token_keys(&market, token));
+        expected.push(system_program::ID);
+        assert_eq!(keys(&ix), expected);
+    }
+
+    #[test]
+    fn close_position_passes_owner_market_and_position() {
+        let owner = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let position = pda::position(&market, &owner, 4).0;
+        let ix = close_position(&owner, &market, &position);
+
+        assert_eq!(keys(&ix), vec![owner, market, position]);
+        assert!(ix.accounts[0].is_writable && ix.accounts[2].is_writable);
+    }
+
+    #[test]
+    fn finalize_market_passes_both_fee_vaults_and_the_admin() {
+        let authority = Pubkey::new_unique();
+        let admin = Pubkey::new_unique();
+        let admin_token_account = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let token = token();
+        let ix = finalize_market(&authority, &market, &admin, Some(token), Some(admin_token_account));
+
+        let mut expected = vec![
+            authority,
+            market,
+            pda::vault(&market).0,
+            pda::protocol_fee_vault(&market).0,
+            pda::creator_fee_vault(&market).0,
+            pda::config().0,
+            admin,
+        ];
+        expected.extend(token_keys(&market, token));
+        expected.extend([admin_token_account, system_program::ID]);
+        assert_eq!(keys(&ix), expected);
+        assert!(ix.accounts[6].is_writable && !ix.accounts[6].is_signer);
+    }
+
+    #[test]
+    fn initialize_config_passes_the_program_data() {
+        let admin = Pubkey::new_unique();
+        let ix = initialize_config(&admin, params());
+
+        assert_eq!(
+            keys(&ix),
+            vec![admin, pda::config().0, dake::ID, pda::program_data().0, system_program::ID]
+        );
+        assert!(ix.accounts[0].is_signer);
+    }
+
+    #[test]
+    fn config_admin_builders_pass_the_signer_and_config() {
+        let admin = Pubkey::new_unique();
+        let successor = Pubkey::new_unique();
+        for (ix, signer) in [
+            (update_config(&admin, params()), admin),
+            (set_paused(&admin, true), admin),
+            (propose_admin(&admin, successor), admin),
+            (accept_admin(&successor), successor),
+        ] {
+            assert_eq!(keys(&ix), vec![signer, pda::config().0]);
+            assert!(ix.accounts[0].is_signer);
+            assert!(ix.accounts[1].is_writable);
+        }
+    }
+
+    #[test]
+    fn resolve_from_oracle_passes_the_price_feed() {
+        let resolver = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let feed = Pubkey::new_unique();
+        let ix = resolve_from_oracle(&resolver, &market, &feed);
+
+        assert_eq!(keys(&ix), vec![resolver, market, feed]);
+        assert!(!ix.accounts[2].is_writable);
+    }
+
+    #[test]
+    fn propose_resolution_scopes_the_proposal_to_the_market_instance() {
+        let proposer = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let ix = propose_resolution(&proposer, &market, 100, 1, 0);
+
+        assert_eq!(
+            keys(&ix),
+            vec![proposer, market, pda::config().0, pda::proposal(&market, 100).0, system_program::ID]
+        );
+    }
+
+    #[test]
+    fn dispute_resolution_passes_the_proposal() {
+        let disputer = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let ix = dispute_resolution(&disputer, &market, 100);
+
+        assert_eq!(
+            keys(&ix),
+            vec![disputer, market, pda::proposal(&market, 100).0, system_program::ID]
+        );
+    }
+
+    #[test]
+    fn finalize_resolution_pays_the_proposer() {
+        let finalizer = Pubkey::new_unique();
+        let proposer = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let ix = finalize_resolution(&finalizer, &market, 100, &proposer);
+
+        assert_eq!(
+            keys(&ix),
+            vec![finalizer, market, pda::proposal(&market, 100).0, proposer]
+        );
+        assert!(ix.accounts[3].is_writable);
+    }
+
+    #[test]
+    fn arbitrate_resolution_passes_both_bond_holders() {
+        let admin = Pubkey::new_unique();
+        let proposer = Pubkey::new_unique();
+        let disputer = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let ix = arbitrate_resolution(&admin, &market, 100, &proposer, &disputer, 0, 0);
+
+        assert_eq!(
+            keys(&ix),
+            vec![
+                admin,
+                pda::config().0,
+                market,
+                pda::proposal(&market, 100).0,
+                proposer,
+                disputer,
+            ]
+        );
+    }
+
+    #[test]
+    fn create_resolver_set_derives_the_set_from_its_authority() {
+        let authority = Pubkey::new_unique();
+        let ix = create_resolver_set(&authority, 3, vec![authority], 1);
+
+        assert_eq!(
+            keys(&ix),
+            vec![authority, pda::resolver_set(&authority, 3).0, system_program::ID]
+        );
+    }
+
+    #[test]
+    fn vote_resolution_scopes_the_votes_to_the_market_instance() {
+        let resolver = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let set = pda::resolver_set(&Pubkey::new_unique(), 0).0;
+        let ix = vote_resolution(&resolver, &market, 100, &set, 1, 0);
+
+        assert_eq!(
+            keys(&ix),
+            vec![resolver, market, set, pda::votes(&market, 100).0, system_program::ID]
+        );
+    }
+
+    #[test]
+    fn expire_resolution_vote_refunds_the_market_authority() {
+        let caller = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let token = token();
+        let ix = expire_resolution_vote(&caller, &market, &authority, Some(token));
+
+        let mut expected = vec![caller, market, authority, pda::vault(&market).0];
+        expected.extend(token_keys(&market, token));
+        expected.push(system_program::ID);
+        assert_eq!(keys(&ix), expected);
+        assert!(ix.accounts[2].is_writable && !ix.accounts[2].is_signer);
+    }
+
+    #[test]
+    fn expire_arbitration_returns_both_bonds() {
+        let caller = Pubkey::new_unique();
+        let authority = Pubkey::new_unique();
+        let proposer = Pubkey::new_unique();
+        let disputer = Pubkey::new_unique();
+        let market = pda::market(1).0;
+        let ix = expire_arbitration(&caller, &market, 100, &authority, &proposer, &disputer, None);
+
+        assert_eq!(ix.accounts[3].pubkey, pda::proposal(&market, 100).0);
+        assert_eq!(ix.accounts[4].pubkey, proposer);
+        assert_eq!(ix.accounts[5].pubkey, disputer);
+        assert!(ix.accounts[4].is_writable && ix.accounts[5].is_writable);
+    }
+}
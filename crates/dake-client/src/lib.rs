@@ -0,0 +1,13 @@
+//! Rust client for the Dake program
+//!
+//! - `pda`: PDA derivation for markets, vaults, positions, resolutions and Inco allowances
+//! - `instructions`: typed instruction builders
+//! - `accounts`: Market / Position deserializers
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use dake::state::{Market, MarketKind, MarketStatus, Position, ResolutionSource};
+pub use dake::ID as PROGRAM_ID;
+pub use inco_lightning::ID as INCO_LIGHTNING_ID;
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::Pubkey;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+
+/// Market PDA: ["market", market_id]
+pub fn market(market_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"market", market_id.to_le_bytes().as_ref()], &dake::ID)
+}
+
+/// Vault PDA holding the market's funds: ["vault", market]
+pub fn vault(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault", market.as_ref()], &dake::ID)
+}
+
+/// Vault token account (token markets only): ["vault_token", market]
+pub fn vault_token(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"vault_token", market.as_ref()], &dake::ID)
+}
+
+/// Position PDA: ["position", market, owner, nonce]
+pub fn position(market: &Pubkey, owner: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"position",
+            market.as_ref(),
+            owner.as_ref(),
+            nonce.to_le_bytes().as_ref(),
+        ],
+        &dake::ID,
+    )
+}
+
+/// Program config PDA: ["config"]
+pub fn config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], &dake::ID)
+}
+
+/// Protocol fee vault: ["protocol_fee_vault", market]
+pub fn protocol_fee_vault(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"protocol_fee_vault", market.as_ref()], &dake::ID)
+}
+
+/// Creator fee vault: ["creator_fee_vault", market]
+pub fn creator_fee_vault(market: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"creator_fee_vault", market.as_ref()], &dake::ID)
+}
+
+/// Optimistic resolution proposal: ["resolution", market, created_at]
+///
+/// Scoped to the market instance, so a re-created market with the same id gets a fresh one.
+pub fn proposal(market: &Pubkey, created_at: i64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"resolution", market.as_ref(), created_at.to_le_bytes().as_ref()],
+        &dake::ID,
+    )
+}
+
+/// Committee votes: ["votes", market, created_at]
+pub fn votes(market: &Pubkey, created_at: i64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"votes", market.as_ref(), created_at.to_le_bytes().as_ref()],
+        &dake::ID,
+    )
+}
+
+/// Resolver set: ["resolver_set", authority, set_id]
+pub fn resolver_set(authority: &Pubkey, set_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"resolver_set", authority.as_ref(), set_id.to_le_bytes().as_ref()],
+        &dake::ID,
+    )
+}
+
+/// The program's ProgramData account (upgradeable loader), needed by initialize_config
+#[allow(deprecated)]
+pub fn program_data() -> (Pubkey, u8) {
+    use anchor_lang::solana_program::bpf_loader_upgradeable;
+    Pubkey::find_program_address(&[dake::ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+/// Inco allowance PDA letting `allowed` decrypt `handle`: [handle (u128 LE), allowed]
+pub fn allowance(handle: u128, allowed: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[handle.to_le_bytes().as_ref(), allowed.as_ref()],
+        &INCO_LIGHTNING_ID,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn position_seeds_include_nonce() {
+        let (market, _) = market(7);
+        let owner = Pubkey::new_unique();
+
+        assert_ne!(position(&market, &owner, 0).0, position(&market, &owner, 1).0);
+        assert_eq!(position(&market, &owner, 1), position(&market, &owner, 1));
+    }
+
+    #[test]
+    fn resolution_seeds_include_the_market_instance() {
+        let (market, _) = market(7);
+
+        assert_ne!(proposal(&market, 100).0, proposal(&market, 101).0);
+        assert_ne!(votes(&market, 100).0, votes(&market, 101).0);
+        assert_ne!(proposal(&market, 100).0, votes(&market, 100).0);
+    }
+
+    #[test]
+    fn allowance_is_derived_under_inco() {
+        let owner = Pubkey::new_unique();
+        let (key, bump) = allowance(42, &owner);
+        let expected = Pubkey::create_program_address(
+            &[42u128.to_le_bytes().as_ref(), owner.as_ref(), &[bump]],
+            &INCO_LIGHTNING_ID,
+        )
+        .unwrap();
+
+        assert_eq!(key, expected);
+    }
+}
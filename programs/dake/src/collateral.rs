@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+use crate::state::Market;
+use crate::error::DakeError;
+
+/// Token accounts needed to move SPL / Token-2022 collateral in and out of the vault
+pub struct TokenCollateral<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub user_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+/// Resolve the optional token accounts of an instruction against the market's collateral.
+///
+/// Returns None for native SOL markets (the token accounts are ignored).
+/// For token markets every account must be present and match the market's mint,
+/// the user token account must belong to `owner`, and the mint must be supported
+/// (see require_supported_mint).
+pub fn token_collateral<'a, 'info>(
+    market: &Market,
+    mint: &'a Option<InterfaceAccount<'info, Mint>>,
+    vault_token_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    user_token_account: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+    owner: &Pubkey,
+    token_program: &'a Option<Interface<'info, TokenInterface>>,
+) -> Result<Option<TokenCollateral<'a, 'info>>> {
+    if !market.uses_token_collateral() {
+        return Ok(None);
+    }
+
+    let (Some(mint), Some(vault_token_account), Some(user_token_account), Some(token_program)) =
+        (mint, vault_token_account, user_token_account, token_program)
+    else {
+        return Err(DakeError::MissingCollateralAccounts.into());
+    };
+
+    require_keys_eq!(mint.key(), market.collateral_mint, DakeError::InvalidCollateralMint);
+    require_supported_mint(mint)?;
+    require_keys_eq!(user_token_account.mint, market.collateral_mint, DakeError::InvalidCollateralMint);
+    require_keys_eq!(user_token_account.owner, *owner, DakeError::InvalidTokenAccountOwner);
+
+    Ok(Some(TokenCollateral {
+        mint,
+        vault_token_account,
+        user_token_account,
+        token_program,
+    }))
+}
+
+/// Reject Token-2022 mints with a transfer fee
+///
+/// The vault would receive less than the amount credited to the pools (and pay
+/// out more than it debits), so deposits and liabilities would drift apart.
+/// The fee can be raised after the mint is created, so the extension itself is
+/// rejected, not just a non-zero fee.
+pub fn require_supported_mint(mint: &InterfaceAccount<'_, Mint>) -> Result<()> {
+    let info = mint.to_account_info();
+    if *info.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+    let data = info.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    require!(
+        !state.get_extension_types()?.contains(&ExtensionType::TransferFeeConfig),
+        DakeError::UnsupportedMintExtension
+    );
+    Ok(())
+}
+
+/// Move `amount` of collateral from `payer` into the market vault
+pub fn transfer_to_vault<'info>(
+    payer: &Signer<'info>,
+    vault: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    token: Option<TokenCollateral<'_, 'info>>,
+    amount: u64,
+) -> Result<()> {
+    match token {
+        Some(token) => token_interface::transfer_checked(
+            CpiContext::new(
+                token.token_program.to_account_info(),
+                TransferChecked {
+                    from: token.user_token_account.to_account_info(),
+                    mint: token.mint.to_account_info(),
+                    to: token.vault_token_account.to_account_info(),
+                    authority: payer.to_account_info(),
+                },
+            ),
+            amount,
+            token.mint.decimals,
+        ),
+        None => {
+            anchor_lang::solana_program::program::invoke(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    &payer.key(),
+                    &vault.key(),
+                    amount,
+                ),
+                &[
+                    payer.to_account_info(),
+                    vault.to_account_info(),
+                    system_program.to_account_info(),
+                ],
+            )?;
+            Ok(())
+        }
+    }
+}
+
+/// Move `amount` of collateral from the market vault to `recipient`, signing as the vault PDA
+pub fn transfer_from_vault<'info>(
+    vault: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    token: Option<TokenCollateral<'_, 'info>>,
+    vault_seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    match token {
+        Some(token) => token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token.token_program.to_account_info(),
+                TransferChecked {
+                    from: token.vault_token_account.to_account_info(),
+                    mint: token.mint.to_account_info(),
+                    to: token.user_token_account.to_account_info(),
+                    authority: vault.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            amount,
+            token.mint.decimals,
+        ),
+        None => {
+            anchor_lang::solana_program::program::invoke_signed(
+                &anchor_lang::solana_program::system_instruction::transfer(
+                    &vault.key(),
+                    &recipient.key(),
+                    amount,
+                ),
+                &[
+                    vault.to_account_info(),
+                    recipient.to_account_info(),
+                    system_program.to_account_info(),
+                ],
+                &[vault_seeds],
+            )?;
+            Ok(())
+        }
+    }
+}
+
+/// Close the (empty) vault token account, returning its rent to `destination`
+pub fn close_vault_token_account<'info>(
+    vault: &AccountInfo<'info>,
+    vault_token_account: &InterfaceAccount<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    vault_seeds: &[&[u8]],
+) -> Result<()> {
+    token_interface::close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: vault_token_account.to_account_info(),
+            destination: destination.clone(),
+            authority: vault.clone(),
+        },
+        &[vault_seeds],
+    ))
+}
+
+/// Lamports a SOL vault keeps back so that paying out its last claim does not
+/// leave it below the rent-exempt minimum (swept by finalize_market)
+pub fn vault_reserve() -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(0))
+}
+
+/// Collateral the vault can pay out: its token balance, or its lamports minus the rent reserve
+pub fn vault_balance(vault: &AccountInfo<'_>, token: Option<&TokenCollateral<'_, '_>>) -> Result<u64> {
+    match token {
+        Some(token) => Ok(token.vault_token_account.amount),
+        None => Ok(vault.lamports().saturating_sub(vault_reserve()?)),
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::error::DakeError;
+
+/// Encoded length of handles and plaintexts passed to attested-decryption instructions.
+///
+/// Both are u128 values serialized as exactly 16 little-endian bytes
+/// (what `handleToBuffer` / `plaintextToBuffer` produce in `@inco/solana-sdk`).
+pub const ENCODED_LEN: usize = 16;
+
+/// Parse a handle: exactly 16 little-endian bytes
+pub fn parse_handle(bytes: &[u8]) -> Result<u128> {
+    let bytes: [u8; ENCODED_LEN] = bytes
+        .try_into()
+        .map_err(|_| DakeError::InvalidHandleEncoding)?;
+    Ok(u128::from_le_bytes(bytes))
+}
+
+/// Parse an integer plaintext: exactly 16 little-endian bytes
+pub fn parse_plaintext_u128(bytes: &[u8]) -> Result<u128> {
+    let bytes: [u8; ENCODED_LEN] = bytes
+        .try_into()
+        .map_err(|_| DakeError::InvalidPlaintextEncoding)?;
+    Ok(u128::from_le_bytes(bytes))
+}
+
+/// Parse an integer plaintext that must fit in a u64 (e.g. a pool total)
+pub fn parse_plaintext_u64(bytes: &[u8]) -> Result<u64> {
+    u64::try_from(parse_plaintext_u128(bytes)?)
+        .map_err(|_| DakeError::InvalidPlaintextEncoding.into())
+}
+
+/// Parse a boolean plaintext: the u128 value must be exactly 0 (false) or 1 (true)
+pub fn parse_plaintext_bool(bytes: &[u8]) -> Result<bool> {
+    match parse_plaintext_u128(bytes)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(DakeError::InvalidPlaintextEncoding.into()),
+    }
+}
@@ -30,4 +30,130 @@ pub enum DakeError {
     ResolutionTimeNotReached,
     #[msg("Question too long - max 256 characters")]
     QuestionTooLong,
+    #[msg("Pool totals are not revealed yet - call reveal_pools first")]
+    PoolsNotRevealed,
+    #[msg("Pool totals are already revealed")]
+    PoolsAlreadyRevealed,
+    #[msg("Invalid reveal data - expected one handle and plaintext per outcome")]
+    InvalidRevealData,
+    #[msg("Handle does not match the on-chain encrypted value")]
+    HandleMismatch,
+    #[msg("Invalid outcome count - must be between 2 and 16")]
+    InvalidOutcomeCount,
+    #[msg("Outcome label too long - max 32 characters")]
+    OutcomeLabelTooLong,
+    #[msg("Invalid outcome index")]
+    InvalidOutcome,
+    #[msg("Invalid scalar bounds - lower bound must be below upper bound")]
+    InvalidScalarBounds,
+    #[msg("Wrong market kind for this instruction")]
+    WrongMarketKind,
+    #[msg("Token collateral accounts are required for this market")]
+    MissingCollateralAccounts,
+    #[msg("Collateral mint does not match the market")]
+    InvalidCollateralMint,
+    #[msg("Invalid initial liquidity - expected one seed amount per outcome")]
+    InvalidInitialLiquidity,
+    #[msg("Vault cannot cover this payout")]
+    InsufficientVaultFunds,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Market is cancelled")]
+    MarketCancelled,
+    #[msg("Market is not cancelled")]
+    MarketNotCancelled,
+    #[msg("Position does not belong to this market")]
+    PositionMarketMismatch,
+    #[msg("Betting has closed for this market")]
+    BettingClosed,
+    #[msg("Betting cutoff must not be after the resolution time")]
+    InvalidBettingCutoff,
+    #[msg("Invalid handle encoding - expected 16 little-endian bytes")]
+    InvalidHandleEncoding,
+    #[msg("Invalid plaintext encoding - expected 16 little-endian bytes")]
+    InvalidPlaintextEncoding,
+    #[msg("Fee rate exceeds the maximum")]
+    InvalidFeeBps,
+    #[msg("No fees to withdraw")]
+    NoFeesToWithdraw,
+    #[msg("Fee vault does not match this market")]
+    FeeVaultMismatch,
+    #[msg("Program is paused")]
+    ProgramPaused,
+    #[msg("Bet amount is outside the configured limits")]
+    BetOutOfRange,
+    #[msg("Invalid bet limits - min_bet must not exceed max_bet")]
+    InvalidBetLimits,
+    #[msg("Too many allowed collateral mints")]
+    TooManyAllowedMints,
+    #[msg("Collateral mint is not allowed")]
+    CollateralMintNotAllowed,
+    #[msg("No admin change is pending")]
+    NoPendingAdmin,
+    #[msg("Invalid oracle config")]
+    InvalidOracleConfig,
+    #[msg("Market has no oracle")]
+    NoOracle,
+    #[msg("Market does not resolve this way")]
+    WrongResolutionSource,
+    #[msg("Invalid oracle price account")]
+    InvalidOracleAccount,
+    #[msg("Oracle price exponent does not match the market")]
+    OracleExponentMismatch,
+    #[msg("Oracle price is too far from the evaluation time")]
+    StaleOraclePrice,
+    #[msg("Oracle confidence interval is too wide")]
+    OracleConfidenceTooWide,
+    #[msg("Invalid dispute settings - bond and window must be non-zero")]
+    InvalidDisputeSettings,
+    #[msg("Market has a resolution pending")]
+    ResolutionPending,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    #[msg("Resolution is already disputed")]
+    AlreadyDisputed,
+    #[msg("Resolution is disputed - the arbitrator must rule")]
+    ResolutionDisputed,
+    #[msg("Resolution is not disputed")]
+    NotDisputed,
+    #[msg("Invalid resolver set - need 1..=10 unique members and a majority threshold <= members")]
+    InvalidResolverSet,
+    #[msg("Signer is not a member of the resolver set")]
+    NotResolver,
+    #[msg("Resolver has already voted")]
+    AlreadyVoted,
+    #[msg("Voting deadline has passed")]
+    VoteDeadlinePassed,
+    #[msg("Voting is still open")]
+    VoteStillOpen,
+    #[msg("Position is not settled - claim or refund it first")]
+    PositionNotSettled,
+    #[msg("Market is not resolved or cancelled")]
+    MarketNotSettled,
+    #[msg("Claim period is not over and positions are still open")]
+    ClaimPeriodNotOver,
+    #[msg("Fee vaults must be withdrawn first")]
+    FeesNotWithdrawn,
+    #[msg("Token account is not owned by the expected wallet")]
+    InvalidTokenAccountOwner,
+    #[msg("Collateral mints with a transfer fee are not supported")]
+    UnsupportedMintExtension,
+    #[msg("Only authority-resolved markets, or markets without bets, can be cancelled")]
+    CancelNotAllowed,
+    #[msg("Trade costs more than max_cost")]
+    MaxCostExceeded,
+    #[msg("No payout from this market can reach min_payout")]
+    PayoutBelowMinimum,
+    #[msg("Arbitration deadline has passed")]
+    ArbitrationDeadlinePassed,
+    #[msg("Arbitration is still open")]
+    ArbitrationStillOpen,
+    #[msg("Winning pool is empty")]
+    EmptyWinningPool,
+    #[msg("Expected an allowance account and grantee per outcome")]
+    MissingAllowanceAccounts,
+    #[msg("Pool access can only be granted to the caller")]
+    GranteeNotCaller,
 }
@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+use crate::state::{FeeKind, MarketKind, ResolutionSource};
+
+/// Emitted by create_market
+#[event]
+pub struct MarketCreated {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub authority: Pubkey,
+    pub question: String,
+    pub outcomes: Vec<String>,
+    pub kind: MarketKind,
+    /// Pubkey::default() for native SOL
+    pub collateral_mint: Pubkey,
+    pub initial_liquidity: Vec<u64>,
+    pub encrypted_totals: Vec<u128>,
+    pub resolution_time: i64,
+    pub betting_closes_at: i64,
+    pub protocol_fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub resolution: ResolutionSource,
+}
+
+/// Emitted by place_bet
+#[event]
+pub struct BetPlaced {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub amount: u64,
+    /// Slippage guard the bet was placed with (0 = none)
+    pub min_payout: u64,
+    /// Encrypted outcome index handle (UNFILLED_SIDE if the guard failed)
+    pub side_handle: u128,
+    /// Encrypted pool totals after the bet
+    pub encrypted_totals: Vec<u128>,
+    pub participant_count: u32,
+}
+
+/// Emitted by buy_shares
+#[event]
+pub struct SharesBought {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub nonce: u64,
+    pub outcome: u8,
+    pub shares: u64,
+    pub cost: u64,
+    /// Shares outstanding per outcome after the trade
+    pub outcome_totals: Vec<u64>,
+}
+
+/// Emitted by close_market
+#[event]
+pub struct MarketClosed {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub encrypted_totals: Vec<u128>,
+    pub participant_count: u32,
+}
+
+/// Emitted by reveal_pools
+#[event]
+pub struct PoolsRevealed {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub outcome_totals: Vec<u64>,
+}
+
+/// Emitted by resolve_market and resolve_scalar_market
+#[event]
+pub struct MarketResolved {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub winning_outcome: u8,
+    /// Clamped resolved value (scalar markets only, 0 otherwise)
+    pub resolved_value: i64,
+    /// Revealed pool totals (all zero if not revealed yet)
+    pub outcome_totals: Vec<u64>,
+}
+
+/// Emitted by propose_resolution
+#[event]
+pub struct ResolutionProposed {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub proposer: Pubkey,
+    pub outcome: u8,
+    pub value: i64,
+    pub bond: u64,
+    pub dispute_deadline: i64,
+}
+
+/// Emitted by dispute_resolution
+#[event]
+pub struct ResolutionDisputed {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub disputer: Pubkey,
+    pub bond: u64,
+    /// The market can be voided with expire_arbitration after this time
+    pub arbitration_deadline: i64,
+}
+
+/// Emitted by arbitrate_resolution
+#[event]
+pub struct DisputeSettled {
+    pub market: Pubkey,
+    pub market_id: u64,
+    /// Whether the arbitrator upheld the proposal
+    pub proposal_upheld: bool,
+    /// Receives both bonds
+    pub bond_recipient: Pubkey,
+    pub bonds_paid: u64,
+}
+
+/// Emitted by vote_resolution
+#[event]
+pub struct ResolutionVoted {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub member: Pubkey,
+    pub outcome: u8,
+    pub value: i64,
+    /// Votes matching this one, including it
+    pub matching_votes: u8,
+    pub threshold: u8,
+}
+
+/// Emitted by check_winner
+#[event]
+pub struct WinnerChecked {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub is_winner_handle: u128,
+}
+
+/// Emitted by claim_winnings and redeem_shares
+#[event]
+pub struct WinningsClaimed {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    /// Original bet amount
+    pub amount: u64,
+    /// Amount received by the owner (after fees)
+    pub payout: u64,
+    pub protocol_fee: u64,
+    pub creator_fee: u64,
+}
+
+/// Emitted by grant_decrypt_access
+#[event]
+pub struct DecryptAccessGranted {
+    pub market: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub handle: u128,
+}
+
+/// Emitted by grant_pool_access
+#[event]
+pub struct PoolAccessGranted {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub grantee: Pubkey,
+}
+
+/// Emitted by cancel_market
+#[event]
+pub struct MarketCancelled {
+    pub market: Pubkey,
+    pub market_id: u64,
+    /// Seed liquidity (or LMSR subsidy) returned to the creator
+    pub seed_refund: u64,
+}
+
+/// Emitted by refund_position
+#[event]
+pub struct PositionRefunded {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted by exit_position
+#[event]
+pub struct PositionExited {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub refund: u64,
+    pub fee: u64,
+}
+
+/// Emitted by close_position
+#[event]
+pub struct PositionClosed {
+    pub market: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+}
+
+/// Emitted by finalize_market
+#[event]
+pub struct MarketFinalized {
+    pub market: Pubkey,
+    pub market_id: u64,
+    /// Leftover vault balance sent to the creator
+    pub swept: u64,
+    /// Unwithdrawn protocol fees paid out to the config admin
+    pub protocol_fees: u64,
+    /// Positions still open (past the claim period)
+    pub unclosed_positions: u32,
+}
+
+/// Emitted by withdraw_protocol_fees and withdraw_creator_fees
+#[event]
+pub struct FeesWithdrawn {
+    pub market: Pubkey,
+    pub market_id: u64,
+    pub kind: FeeKind,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::state::FeeVault;
+use crate::collateral::{self, TokenCollateral};
+use crate::error::DakeError;
+
+/// Move `amount` of fees from the market vault into a fee vault
+///
+/// SOL markets transfer the lamports to the fee vault account. Token markets keep
+/// the tokens in the market's vault token account - the fee vault only records them.
+pub fn accrue<'info>(
+    fee_vault: &mut Account<'info, FeeVault>,
+    vault: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    uses_token_collateral: bool,
+    vault_seeds: &[&[u8]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    if !uses_token_collateral {
+        collateral::transfer_from_vault(
+            vault,
+            &fee_vault.to_account_info(),
+            system_program,
+            None,
+            vault_seeds,
+            amount,
+        )?;
+    }
+
+    fee_vault.accrued = fee_vault
+        .accrued
+        .checked_add(amount)
+        .ok_or(DakeError::MathOverflow)?;
+    Ok(())
+}
+
+/// Pay out everything a fee vault has accrued but not yet withdrawn to `recipient`
+///
+/// Returns the amount withdrawn.
+pub fn withdraw<'info>(
+    fee_vault: &mut Account<'info, FeeVault>,
+    vault: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    token: Option<TokenCollateral<'_, 'info>>,
+    vault_seeds: &[&[u8]],
+) -> Result<u64> {
+    let amount = fee_vault.available();
+    require!(amount > 0, DakeError::NoFeesToWithdraw);
+
+    match token {
+        // Token fees are held by the market vault
+        Some(token) => collateral::transfer_from_vault(
+            vault,
+            recipient,
+            system_program,
+            Some(token),
+            vault_seeds,
+            amount,
+        )?,
+        // SOL fees are held by the (program-owned) fee vault itself
+        None => {
+            let fee_vault_info = fee_vault.to_account_info();
+            **fee_vault_info.try_borrow_mut_lamports()? = fee_vault_info
+                .lamports()
+                .checked_sub(amount)
+                .ok_or(DakeError::InsufficientVaultFunds)?;
+            **recipient.try_borrow_mut_lamports()? = recipient
+                .lamports()
+                .checked_add(amount)
+                .ok_or(DakeError::MathOverflow)?;
+        }
+    }
+
+    fee_vault.withdrawn = fee_vault
+        .withdrawn
+        .checked_add(amount)
+        .ok_or(DakeError::MathOverflow)?;
+    Ok(amount)
+}
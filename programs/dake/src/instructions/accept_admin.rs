@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::error::DakeError;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Accept a proposed admin change (step 2 of 2, signed by the pending admin)
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    let pending_admin = config.pending_admin.ok_or(DakeError::NoPendingAdmin)?;
+    require_keys_eq!(
+        pending_admin,
+        ctx.accounts.new_admin.key(),
+        DakeError::Unauthorized
+    );
+
+    config.admin = pending_admin;
+    config.pending_admin = None;
+
+    msg!("Dake admin changed!");
+    msg!("   Admin: {}", config.admin);
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, Market, MarketKind, ResolutionProposal};
+use crate::error::DakeError;
+use crate::events::{DisputeSettled, MarketResolved};
+
+#[derive(Accounts)]
+pub struct ArbitrateResolution<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DakeError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"resolution", market.key().as_ref(), market.created_at.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = market,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+
+    /// CHECK: Proposer (checked against proposal.proposer)
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    /// CHECK: Disputer (checked against proposal.disputer)
+    #[account(
+        mut,
+        constraint = proposal.disputer == Some(disputer.key()) @ DakeError::NotDisputed,
+    )]
+    pub disputer: AccountInfo<'info>,
+}
+
+/// Rule on a disputed resolution (Config admin as arbitrator)
+///
+/// Parameters:
+/// - outcome: the correct winning outcome (categorical markets)
+/// - value: the correct value (scalar markets)
+///
+/// Whichever side was correct receives both bonds: the proposer if the ruling
+/// matches the proposal, the disputer otherwise.
+///
+/// Rulings are accepted until the proposal's arbitration_deadline; after that the
+/// market can only be voided (expire_arbitration).
+pub fn handler(ctx: Context<ArbitrateResolution>, outcome: u8, value: i64) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+
+    require!(
+        ctx.accounts.market.is_pending_resolution(),
+        DakeError::MarketNotResolved
+    );
+    require!(
+        Clock::get()?.unix_timestamp < proposal.arbitration_deadline,
+        DakeError::ArbitrationDeadlinePassed
+    );
+
+    let market = &mut ctx.accounts.market;
+    let proposal_upheld = match market.kind {
+        MarketKind::Categorical | MarketKind::Lmsr { .. } => outcome == proposal.outcome,
+        MarketKind::Scalar { lower_bound, upper_bound } => {
+            value.clamp(lower_bound, upper_bound)
+                == proposal.value.clamp(lower_bound, upper_bound)
+        }
+    };
+    market.apply_resolution(outcome, value)?;
+
+    // Both bonds go to the correct side; closing the proposal pays the proposer
+    let bonds = proposal
+        .bond
+        .checked_mul(2)
+        .ok_or(DakeError::MathOverflow)?;
+    let bond_recipient = if proposal_upheld {
+        ctx.accounts.proposer.key()
+    } else {
+        let proposal_info = ctx.accounts.proposal.to_account_info();
+        **proposal_info.try_borrow_mut_lamports()? = proposal_info
+            .lamports()
+            .checked_sub(bonds)
+            .ok_or(DakeError::MathOverflow)?;
+        let disputer = &ctx.accounts.disputer;
+        **disputer.try_borrow_mut_lamports()? = disputer
+            .lamports()
+            .checked_add(bonds)
+            .ok_or(DakeError::MathOverflow)?;
+        disputer.key()
+    };
+
+    emit!(DisputeSettled {
+        market: market.key(),
+        market_id: market.market_id,
+        proposal_upheld,
+        bond_recipient,
+        bonds_paid: bonds,
+    });
+    emit!(MarketResolved {
+        market: market.key(),
+        market_id: market.market_id,
+        winning_outcome: market.winning_outcome,
+        resolved_value: market.resolved_value,
+        outcome_totals: market.outcome_totals.clone(),
+    });
+
+    msg!("Dake Market #{} RESOLVED by arbitration!", market.market_id);
+    msg!("   Question: {}", market.question);
+    msg!("   Outcome: {} wins!", market.outcomes[market.winning_outcome as usize]);
+    msg!("   Proposal upheld: {}", proposal_upheld);
+    msg!("   Bonds ({} lamports) to: {}", bonds, bond_recipient);
+
+    Ok(())
+}
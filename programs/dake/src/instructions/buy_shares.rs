@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Config, Market, MarketKind, Position};
+use crate::collateral;
+use crate::lmsr;
+use crate::error::DakeError;
+use crate::events::SharesBought;
+
+#[derive(Accounts)]
+#[instruction(outcome: u8, shares: u64, max_cost: u64, position_nonce: u64)]
+pub struct BuyShares<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = buyer,
+        space = Position::SIZE,
+        seeds = [
+            b"position",
+            market.key().as_ref(),
+            buyer.key().as_ref(),
+            position_nonce.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: Vault PDA to hold the market maker's funds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        mut,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub buyer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Buy shares of an outcome from an LMSR market maker
+///
+/// Parameters:
+/// - outcome: outcome index to buy (public)
+/// - shares: number of shares; each pays one base unit if `outcome` wins
+/// - max_cost: the trade fails with MaxCostExceeded if it costs more, so a price
+///   moved by earlier trades cannot fill it at a worse price
+/// - position_nonce: distinguishes positions of the same buyer in the same market
+///
+/// The cost is lmsr::buy_cost at the current shares outstanding (rounded up)
+/// and must be within the Config bet limits. It becomes the position's amount,
+/// which fees are charged against at redeem_shares and which a cancelled market
+/// refunds. Markets with many outcomes need a raised compute unit limit.
+pub fn handler(
+    ctx: Context<BuyShares>,
+    outcome: u8,
+    shares: u64,
+    max_cost: u64,
+    position_nonce: u64,
+) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let MarketKind::Lmsr { liquidity } = market.kind else {
+        return Err(DakeError::WrongMarketKind.into());
+    };
+    require!(market.is_open(), DakeError::MarketNotOpen);
+    require!(
+        market.is_accepting_bets(Clock::get()?.unix_timestamp),
+        DakeError::BettingClosed
+    );
+    require!(!ctx.accounts.config.paused, DakeError::ProgramPaused);
+    require!(
+        (outcome as usize) < market.outcome_count(),
+        DakeError::InvalidOutcome
+    );
+    require!(shares > 0, DakeError::InvalidBetAmount);
+
+    let cost = lmsr::buy_cost(&market.outcome_totals, liquidity, outcome as usize, shares)
+        .ok_or(DakeError::MathOverflow)?;
+    require!(cost <= max_cost, DakeError::MaxCostExceeded);
+    require!(
+        ctx.accounts.config.is_bet_in_range(cost),
+        DakeError::BetOutOfRange
+    );
+
+    // Transfer the cost to the vault
+    let token = collateral::token_collateral(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.buyer_token_account,
+        &ctx.accounts.buyer.key(),
+        &ctx.accounts.token_program,
+    )?;
+    collateral::transfer_to_vault(
+        &ctx.accounts.buyer,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        token,
+        cost,
+    )?;
+
+    let market = &mut ctx.accounts.market;
+    market.total_deposited = market
+        .total_deposited
+        .checked_add(cost)
+        .ok_or(DakeError::MathOverflow)?;
+    let outcome_shares = &mut market.outcome_totals[outcome as usize];
+    *outcome_shares = outcome_shares
+        .checked_add(shares)
+        .ok_or(DakeError::MathOverflow)?;
+
+    // The subsidy plus every cost paid covers the shares of any winning outcome
+    let max_liability = market.outcome_totals.iter().copied().max().unwrap_or(0);
+    require!(
+        max_liability <= market.total_deposited,
+        DakeError::InsufficientVaultFunds
+    );
+
+    market.participant_count += 1;
+    market.open_positions += 1;
+
+    // Store position
+    let position = &mut ctx.accounts.position;
+    position.market = market.key();
+    position.owner = ctx.accounts.buyer.key();
+    position.nonce = position_nonce;
+    position.opened_at = Clock::get()?.unix_timestamp;
+    position.amount = cost;
+    position.encrypted_side_handle = 0;
+    position.is_winner_handle = 0;
+    position.claimed = false;
+    position.outcome = outcome;
+    position.shares = shares;
+    position.bump = ctx.bumps.position;
+
+    emit!(SharesBought {
+        market: market.key(),
+        market_id: market.market_id,
+        position: position.key(),
+        owner: position.owner,
+        nonce: position_nonce,
+        outcome,
+        shares,
+        cost,
+        outcome_totals: market.outcome_totals.clone(),
+    });
+
+    msg!("Shares bought on Dake Market #{}!", market.market_id);
+    msg!("   Outcome: {}", outcome);
+    msg!("   Shares: {}", shares);
+    msg!("   Cost: {}", cost);
+    msg!("   Shares outstanding: {:?}", market.outcome_totals);
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Market, MarketKind, MarketStatus, ResolutionSource};
+use crate::collateral::{self, TokenCollateral};
+use crate::error::DakeError;
+use crate::lmsr;
+use crate::events::MarketCancelled;
+
+#[derive(Accounts)]
+pub struct CancelMarket<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Vault PDA holding the funds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        mut,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Authority's token account - receives the seed liquidity (token markets only)
+    #[account(mut, token::authority = authority)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Void a market whose question turned out ambiguous or invalid
+///
+/// The creator's seed liquidity (or LMSR subsidy) is returned immediately. Every
+/// position owner can then call refund_position to get back exactly their bet amount.
+/// No sides are decrypted.
+///
+/// Oracle, optimistic and committee markets can only be cancelled before the
+/// first bet (a stalled committee vote is voided by expire_resolution_vote, a
+/// stalled arbitration by expire_arbitration).
+pub fn handler(ctx: Context<CancelMarket>) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        DakeError::Unauthorized
+    );
+    require!(!market.is_resolved(), DakeError::MarketAlreadyResolved);
+    require!(!market.is_cancelled(), DakeError::MarketCancelled);
+    require!(!market.is_pending_resolution(), DakeError::ResolutionPending);
+    // Markets that settle without their creator cannot be voided by it once bet on
+    require!(
+        market.resolution == ResolutionSource::Authority || market.participant_count == 0,
+        DakeError::CancelNotAllowed
+    );
+
+    let token = collateral::token_collateral(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.authority_token_account,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.token_program,
+    )?;
+
+    void_market(
+        &mut ctx.accounts.market,
+        &ctx.accounts.vault,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program,
+        token,
+        ctx.bumps.vault,
+    )
+}
+
+/// Mark a market Cancelled and return the seed liquidity or LMSR subsidy to its authority
+pub(crate) fn void_market<'info>(
+    market: &mut Account<'info, Market>,
+    vault: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    token: Option<TokenCollateral<'_, 'info>>,
+    vault_bump: u8,
+) -> Result<()> {
+    // LMSR markets are funded by a subsidy instead of per-outcome seeds
+    let subsidy = match market.kind {
+        MarketKind::Lmsr { liquidity } => {
+            lmsr::max_loss(liquidity, market.outcome_count()).ok_or(DakeError::MathOverflow)?
+        }
+        _ => 0,
+    };
+    let seed_refund = market
+        .total_initial_liquidity()
+        .checked_add(subsidy)
+        .ok_or(DakeError::MathOverflow)?;
+
+    if seed_refund > 0 {
+        let market_key = market.key();
+        let vault_seeds: &[&[u8]] = &[
+            b"vault",
+            market_key.as_ref(),
+            &[vault_bump],
+        ];
+
+        collateral::transfer_from_vault(
+            vault,
+            authority,
+            system_program,
+            token,
+            vault_seeds,
+            seed_refund,
+        )?;
+    }
+
+    market.status = MarketStatus::Cancelled;
+    market.settled_at = Clock::get()?.unix_timestamp;
+    market.total_paid_out = market
+        .total_paid_out
+        .checked_add(seed_refund)
+        .ok_or(DakeError::MathOverflow)?;
+
+    emit!(MarketCancelled {
+        market: market.key(),
+        market_id: market.market_id,
+        seed_refund,
+    });
+
+    msg!("Dake Market #{} CANCELLED!", market.market_id);
+    msg!("   Seed liquidity returned: {}", seed_refund);
+    msg!("   Positions to refund: {}", market.participant_count);
+
+    Ok(())
+}
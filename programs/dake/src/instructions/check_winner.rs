@@ -5,8 +5,11 @@ use inco_lightning::{
     types::{Ebool, Euint128},
     ID as INCO_LIGHTNING_ID,
 };
-use crate::state::{Market, MarketStatus, Position};
+use crate::state::{
+    Market, Position, SCALAR_SETTLE_LONG, SCALAR_SETTLE_SHORT, SETTLE_REFUND, UNFILLED_SIDE,
+};
 use crate::error::DakeError;
+use crate::events::WinnerChecked;
 
 #[derive(Accounts)]
 pub struct CheckWinner<'info> {
@@ -15,7 +18,11 @@ pub struct CheckWinner<'info> {
 
     pub market: Account<'info, Market>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        has_one = market @ DakeError::PositionMarketMismatch,
+        constraint = market.is_current(&position) @ DakeError::PositionMarketMismatch,
+    )]
     pub position: Account<'info, Position>,
 
     pub system_program: Program<'info, System>,
@@ -26,48 +33,80 @@ pub struct CheckWinner<'info> {
 
 /// Check if a position is a winner (encrypted comparison)
 ///
-/// This compares the user's encrypted side with the winning side:
-/// - If market resolved YES (1), check if encrypted_side == 1
-/// - If market resolved NO (0), check if encrypted_side == 0
+/// This compares the user's encrypted outcome with the winning outcome:
+/// - is_winner = (encrypted_side == winning_outcome)
+/// - For a binary market: YES = 1, NO = 0
+///
+/// Scalar markets settle both sides, so the result encodes which side the position is on:
+/// - SCALAR_SETTLE_LONG if encrypted_side == LONG
+/// - SCALAR_SETTLE_SHORT if encrypted_side == SHORT
+/// - 0 otherwise (invalid side, nothing to claim)
+///
+/// Either way, a bet whose min_payout guard failed in place_bet (side UNFILLED_SIDE)
+/// settles to SETTLE_REFUND, so claim_winnings returns its stake.
+///
+/// LMSR positions are public and redeem with redeem_shares instead.
 pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CheckWinner<'info>>) -> Result<()> {
     let market = &ctx.accounts.market;
     let position = &mut ctx.accounts.position;
 
     require!(market.is_resolved(), DakeError::MarketNotResolved);
+    require!(!market.is_lmsr(), DakeError::WrongMarketKind);
 
     let inco = ctx.accounts.inco_lightning_program.to_account_info();
-
-    // Determine winning side value: 1 for YES, 0 for NO
-    let winning_side_value: u128 = match market.status {
-        MarketStatus::ResolvedYes => 1,
-        MarketStatus::ResolvedNo => 0,
-        _ => return Err(DakeError::MarketNotResolved.into()),
+    let signer = ctx.accounts.checker.to_account_info();
+    let operation = || {
+        CpiContext::new(
+            inco.clone(),
+            Operation {
+                signer: signer.clone(),
+            },
+        )
     };
 
+    let winning_side_value = market.winning_outcome as u128;
+
     // Create encrypted winning side value
-    let cpi_ctx = CpiContext::new(
-        inco.clone(),
-        Operation {
-            signer: ctx.accounts.checker.to_account_info(),
-        },
-    );
-    let winning_side_encrypted: Euint128 = cpi::as_euint128(cpi_ctx, winning_side_value)?;
-
-    // Compare: user_side == winning_side
-    let cpi_ctx = CpiContext::new(
-        inco.clone(),
-        Operation {
-            signer: ctx.accounts.checker.to_account_info(),
-        },
-    );
+    let winning_side_encrypted: Euint128 = cpi::as_euint128(operation(), winning_side_value)?;
+
+    // Compare: user_outcome == winning_outcome
     let is_winner: Ebool = cpi::e_eq(
-        cpi_ctx,
+        operation(),
         Euint128(position.encrypted_side_handle),
         winning_side_encrypted,
         0,
     )?;
 
-    position.is_winner_handle = is_winner.0;
+    let result_handle = if market.is_scalar() {
+        // winning_side_encrypted is LONG here; fold in the SHORT comparison as well
+        let zero = cpi::as_euint128(operation(), 0)?;
+        let settle_short = cpi::as_euint128(operation(), SCALAR_SETTLE_SHORT)?;
+        let settle_long = cpi::as_euint128(operation(), SCALAR_SETTLE_LONG)?;
+        let is_short: Ebool = cpi::e_eq(
+            operation(),
+            Euint128(position.encrypted_side_handle),
+            zero,
+            0,
+        )?;
+        let short_or_invalid = cpi::e_select(operation(), is_short, settle_short, zero, 0)?;
+        cpi::e_select(operation(), is_winner, settle_long, short_or_invalid, 0)?
+    } else {
+        let zero = cpi::as_euint128(operation(), 0)?;
+        let one = cpi::as_euint128(operation(), 1)?;
+        cpi::e_select(operation(), is_winner, one, zero, 0)?
+    };
+
+    let unfilled = cpi::as_euint128(operation(), UNFILLED_SIDE)?;
+    let is_unfilled: Ebool = cpi::e_eq(
+        operation(),
+        Euint128(position.encrypted_side_handle),
+        unfilled,
+        0,
+    )?;
+    let refund = cpi::as_euint128(operation(), SETTLE_REFUND)?;
+    let result_handle = cpi::e_select(operation(), is_unfilled, refund, result_handle, 0)?.0;
+
+    position.is_winner_handle = result_handle;
 
     // Grant decrypt access to the position owner (must be in same TX as the operation)
     if ctx.remaining_accounts.len() >= 2 {
@@ -80,12 +119,20 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CheckWinner<'info>>) -> Re
                 system_program: ctx.accounts.system_program.to_account_info(),
             },
         );
-        cpi::allow(cpi_ctx, is_winner.0, true, position.owner)?;
+        cpi::allow(cpi_ctx, result_handle, true, position.owner)?;
     }
 
+    emit!(WinnerChecked {
+        market: market.key(),
+        market_id: market.market_id,
+        position: position.key(),
+        owner: position.owner,
+        is_winner_handle: result_handle,
+    });
+
     msg!("Position checked for Dake Market #{}!", market.market_id);
     msg!("   Owner: {}", position.owner);
-    msg!("   Is winner handle: {}", is_winner.0);
+    msg!("   Is winner handle: {}", result_handle);
 
     Ok(())
 }
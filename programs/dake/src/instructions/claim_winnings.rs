@@ -1,11 +1,20 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use inco_lightning::{
     cpi::{self, accounts::VerifySignature},
     program::IncoLightning,
     ID as INCO_LIGHTNING_ID,
 };
-use crate::state::{Market, MarketStatus, Position};
+use crate::state::{
+    FeeVault, Market, Position, OUTCOME_LONG, OUTCOME_SHORT, SCALAR_SETTLE_LONG, SCALAR_SETTLE_SHORT,
+    SETTLE_REFUND,
+};
+use crate::collateral;
+use crate::decryption;
+use crate::fees;
+use crate::payout;
 use crate::error::DakeError;
+use crate::events::WinningsClaimed;
 
 #[derive(Accounts)]
 pub struct ClaimWinnings<'info> {
@@ -15,7 +24,11 @@ pub struct ClaimWinnings<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        has_one = market @ DakeError::PositionMarketMismatch,
+        constraint = market.is_current(&position) @ DakeError::PositionMarketMismatch,
+    )]
     pub position: Account<'info, Position>,
 
     /// CHECK: Vault PDA holding the funds
@@ -26,6 +39,36 @@ pub struct ClaimWinnings<'info> {
     )]
     pub vault: AccountInfo<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"protocol_fee_vault", market.key().as_ref()],
+        bump = protocol_fee_vault.bump,
+    )]
+    pub protocol_fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", market.key().as_ref()],
+        bump = creator_fee_vault.bump,
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        mut,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub winner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     /// CHECK: Instructions sysvar for Ed25519 signature verification
     #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
     pub instructions: AccountInfo<'info>,
@@ -38,10 +81,25 @@ pub struct ClaimWinnings<'info> {
 
 /// Claim winnings by proving winner status
 ///
-/// Payout calculation (proportional):
+/// Payout calculation (proportional, from the revealed pool totals):
 /// - Winner receives: (their_bet / total_winning_bets) * total_pool
 /// - Example: If you bet 100 on YES, total YES bets = 1000, total pool = 2000
 ///   Your payout = (100/1000) * 2000 = 200 (2x return)
+///
+/// Scalar markets pay both sides (partial payout):
+/// - LONG receives: (their_bet / total_long_bets) * long_share
+/// - SHORT receives: (their_bet / total_short_bets) * (total_pool - long_share)
+/// - The decrypted plaintext is the settlement side from check_winner
+///
+/// A bet whose min_payout guard failed settles to SETTLE_REFUND and gets its stake
+/// back, fee free.
+///
+/// Fees: market.protocol_fee_bps and market.creator_fee_bps of the profit (payout
+/// minus stake) go to the market's protocol / creator fee vaults; the winner
+/// receives the rest.
+///
+/// The handle must be the position's is_winner_handle; handle and plaintext are
+/// encoded as described in crate::decryption.
 pub fn handler(
     ctx: Context<ClaimWinnings>,
     handle: Vec<u8>,
@@ -54,6 +112,11 @@ pub fn handler(
     require!(position.is_winner_handle != 0, DakeError::NotChecked);
     require!(!position.claimed, DakeError::AlreadyClaimed);
     require!(market.is_resolved(), DakeError::MarketNotResolved);
+    require!(market.pools_revealed, DakeError::PoolsNotRevealed);
+    require!(
+        decryption::parse_handle(&handle)? == position.is_winner_handle,
+        DakeError::HandleMismatch
+    );
 
     // Verify the decryption signature on-chain
     let cpi_ctx = CpiContext::new(
@@ -71,25 +134,68 @@ pub fn handler(
         Some(vec![plaintext.clone()]),
     )?;
 
-    // Parse the verified plaintext - should be non-zero (true) for winner
-    let is_winner = parse_plaintext_to_bool(&plaintext)?;
-    require!(is_winner, DakeError::NotWinner);
-
-    // Use the LOCKED payout that was calculated at bet time
-    // This guarantees the user gets exactly what they were promised
     let user_bet = position.amount;
-    let payout = position.locked_payout;
-
-    // Ensure we don't pay out more than vault has
-    let vault_balance = ctx.accounts.vault.lamports();
-    let actual_payout = payout.min(vault_balance);
+    let payout = if market.is_scalar() {
+        // Parse the verified plaintext - the side this position settles on
+        let long_share = market.scalar_long_share();
+        match decryption::parse_plaintext_u128(&plaintext)? {
+            SCALAR_SETTLE_LONG => payout::proportional(
+                user_bet,
+                long_share,
+                market.outcome_totals[OUTCOME_LONG as usize],
+            ),
+            SCALAR_SETTLE_SHORT => payout::proportional(
+                user_bet,
+                market.total_pool().saturating_sub(long_share),
+                market.outcome_totals[OUTCOME_SHORT as usize],
+            ),
+            SETTLE_REFUND => Some(user_bet),
+            _ => return Err(DakeError::NotWinner.into()),
+        }
+    } else {
+        // Parse the verified plaintext - 1 for a winner, SETTLE_REFUND for an unfilled bet
+        match decryption::parse_plaintext_u128(&plaintext)? {
+            // Proportional share of the total pool, based on the revealed winning pool
+            1 => payout::categorical(user_bet, market.total_pool(), market.winning_pool()),
+            SETTLE_REFUND => Some(user_bet),
+            0 => return Err(DakeError::NotWinner.into()),
+            _ => return Err(DakeError::InvalidPlaintextEncoding.into()),
+        }
+    };
+    // The position is part of the revealed pool it claims from, so this only
+    // fails if that pool is empty
+    let payout = payout.ok_or(DakeError::EmptyWinningPool)?;
+    require!(payout > 0, DakeError::NotWinner);
+
+    let token = collateral::token_collateral(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.winner_token_account,
+        &ctx.accounts.winner.key(),
+        &ctx.accounts.token_program,
+    )?;
 
-    require!(actual_payout > 0, DakeError::NoFunds);
+    // Never pay out more than the market still owes, or than the vault holds
+    let vault_balance = collateral::vault_balance(&ctx.accounts.vault, token.as_ref())?;
+    require!(
+        payout <= market.outstanding_liability() && payout <= vault_balance,
+        DakeError::InsufficientVaultFunds
+    );
 
     // Mark as claimed
     position.claimed = true;
+    let market = &mut ctx.accounts.market;
+    market.total_paid_out = market
+        .total_paid_out
+        .checked_add(payout)
+        .ok_or(DakeError::MathOverflow)?;
+
+    let (protocol_fee, creator_fee) = market.fees_on(payout, user_bet);
+    let net_payout = payout - protocol_fee - creator_fee;
+    let uses_token_collateral = market.uses_token_collateral();
 
-    // Transfer payout from vault to winner
+    // Transfer payout (minus fees) from vault to winner
     let market_key = market.key();
     let vault_seeds: &[&[u8]] = &[
         b"vault",
@@ -97,53 +203,51 @@ pub fn handler(
         &[ctx.bumps.vault],
     ];
 
-    anchor_lang::solana_program::program::invoke_signed(
-        &anchor_lang::solana_program::system_instruction::transfer(
-            &ctx.accounts.vault.key(),
-            &ctx.accounts.winner.key(),
-            actual_payout,
-        ),
-        &[
-            ctx.accounts.vault.to_account_info(),
-            ctx.accounts.winner.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-        &[vault_seeds],
+    collateral::transfer_from_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.winner.to_account_info(),
+        &ctx.accounts.system_program,
+        token,
+        vault_seeds,
+        net_payout,
     )?;
 
-    let profit = actual_payout.saturating_sub(user_bet);
-    let multiplier = if user_bet > 0 {
-        (actual_payout as f64 / user_bet as f64 * 100.0) as u64
-    } else {
-        100
-    };
-
-    msg!("Winnings claimed from Dake Market #{}!", market.market_id);
-    msg!("   Original bet: {} lamports", user_bet);
-    msg!("   Payout: {} lamports", actual_payout);
-    msg!("   Profit: {} lamports ({}% return)", profit, multiplier);
-
-    Ok(())
-}
+    fees::accrue(
+        &mut ctx.accounts.protocol_fee_vault,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        uses_token_collateral,
+        vault_seeds,
+        protocol_fee,
+    )?;
+    fees::accrue(
+        &mut ctx.accounts.creator_fee_vault,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        uses_token_collateral,
+        vault_seeds,
+        creator_fee,
+    )?;
 
-/// Parse decrypted boolean plaintext
-fn parse_plaintext_to_bool(plaintext: &[u8]) -> Result<bool> {
-    if plaintext.is_empty() {
-        return Ok(false);
-    }
+    let profit = net_payout.saturating_sub(user_bet);
+    let return_pct = payout::return_pct(net_payout, user_bet);
 
-    // Check if any byte is non-zero (handles u128 LE format where 1 = [1,0,0,0,...])
-    let any_nonzero = plaintext.iter().any(|&b| b != 0 && b != b'0');
+    emit!(WinningsClaimed {
+        market: market.key(),
+        market_id: market.market_id,
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.position.owner,
+        amount: user_bet,
+        payout: net_payout,
+        protocol_fee,
+        creator_fee,
+    });
 
-    // Also check for string "0"/"1" format
-    if let Ok(s) = std::str::from_utf8(plaintext) {
-        if s == "0" || s == "false" {
-            return Ok(false);
-        }
-        if s == "1" || s == "true" {
-            return Ok(true);
-        }
-    }
+    msg!("Winnings claimed from Dake Market #{}!", market.market_id);
+    msg!("   Original bet: {}", user_bet);
+    msg!("   Payout: {}", net_payout);
+    msg!("   Fees: {} protocol, {} creator", protocol_fee, creator_fee);
+    msg!("   Profit: {} ({}% return)", profit, return_pct);
 
-    Ok(any_nonzero)
+    Ok(())
 }
@@ -1,30 +1,70 @@
 use anchor_lang::prelude::*;
+use inco_lightning::{
+    program::IncoLightning,
+    ID as INCO_LIGHTNING_ID,
+};
 use crate::state::{Market, MarketStatus};
+use crate::pools;
 use crate::error::DakeError;
+use crate::events::MarketClosed;
 
 #[derive(Accounts)]
 pub struct CloseMarket<'info> {
+    /// The market authority, or anyone once betting has closed
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub closer: Signer<'info>,
 
     #[account(mut)]
     pub market: Account<'info, Market>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
 }
 
 /// Close a market for betting (no more bets accepted)
-pub fn handler(ctx: Context<CloseMarket>) -> Result<()> {
+///
+/// The authority can close its market at any time. Once betting_closes_at has
+/// passed anyone can, so a creator cannot hold a market open to block settlement.
+///
+/// Remaining accounts (optional), used to let the closer decrypt the pool totals
+/// (anyone else can get access later with grant_pool_access).
+/// One pair per outcome, in outcome order:
+/// - [2i] allowance PDA for encrypted_totals[i], [2i + 1] closer
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, CloseMarket<'info>>) -> Result<()> {
     let market = &mut ctx.accounts.market;
 
     require!(
-        market.authority == ctx.accounts.authority.key(),
+        market.authority == ctx.accounts.closer.key()
+            || Clock::get()?.unix_timestamp >= market.betting_closes_at,
         DakeError::Unauthorized
     );
     require!(market.is_open(), DakeError::MarketNotOpen);
 
     market.status = MarketStatus::Closed;
 
+    // Grant the closer decrypt access to the final pool totals
+    if ctx.remaining_accounts.len() >= 2 * market.outcome_count() {
+        pools::allow_totals(
+            &ctx.accounts.inco_lightning_program.to_account_info(),
+            &ctx.accounts.closer.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.remaining_accounts,
+            &market.encrypted_totals,
+            ctx.accounts.closer.key(),
+        )?;
+    }
+
+    emit!(MarketClosed {
+        market: market.key(),
+        market_id: market.market_id,
+        encrypted_totals: market.encrypted_totals.clone(),
+        participant_count: market.participant_count,
+    });
+
     msg!("Dake Market #{} closed for betting!", market.market_id);
-    msg!("   Final pool - YES: {}, NO: {}", market.total_yes_amount, market.total_no_amount);
+    msg!("   Encrypted pool handles: {:?}", market.encrypted_totals);
     msg!("   Total participants: {}", market.participant_count);
 
     Ok(())
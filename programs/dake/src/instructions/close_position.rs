@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, Position};
+use crate::error::DakeError;
+use crate::events::PositionClosed;
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// CHECK: The position's market - may already be closed by finalize_market
+    #[account(mut, address = position.market @ DakeError::PositionMarketMismatch)]
+    pub market: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = owner @ DakeError::NotOwner,
+        close = owner,
+    )]
+    pub position: Account<'info, Position>,
+}
+
+/// Close a settled position and return its rent to the owner
+///
+/// Allowed once the position is claimed / refunded, or once its market is
+/// resolved - closing an unclaimed position of a resolved market forfeits any
+/// winnings. Positions of a finalized (closed) market can always be closed.
+pub fn handler(ctx: Context<ClosePosition>) -> Result<()> {
+    let position = &ctx.accounts.position;
+    let market_info = ctx.accounts.market.to_account_info();
+
+    // The market still exists: check the position is settled and update its count
+    if market_info.owner == &crate::ID && !market_info.data_is_empty() {
+        let mut market = Market::try_deserialize(&mut &market_info.try_borrow_data()?[..])?;
+
+        if market.is_current(position) {
+            require!(
+                position.claimed || market.is_resolved(),
+                DakeError::PositionNotSettled
+            );
+
+            market.open_positions = market.open_positions.saturating_sub(1);
+            market.try_serialize(&mut &mut market_info.try_borrow_mut_data()?[..])?;
+        }
+    }
+
+    emit!(PositionClosed {
+        market: position.market,
+        position: position.key(),
+        owner: position.owner,
+    });
+
+    msg!("Position closed!");
+    msg!("   Owner: {}", position.owner);
+    msg!("   Claimed: {}", position.claimed);
+
+    Ok(())
+}
@@ -1,12 +1,22 @@
 use anchor_lang::prelude::*;
-use crate::state::{Market, MarketStatus};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use inco_lightning::{
+    cpi::{self, accounts::Operation},
+    program::IncoLightning,
+    types::Euint128,
+    ID as INCO_LIGHTNING_ID,
+};
+use crate::state::{
+    Config, FeeKind, FeeVault, Market, MarketKind, MarketStatus, OracleComparator, ResolutionSource, MAX_OUTCOMES,
+    MAX_OUTCOME_LABEL_LEN, MIN_OUTCOMES,
+};
+use crate::collateral;
+use crate::lmsr;
 use crate::error::DakeError;
-
-/// Default initial liquidity per side (0.5 SOL = 500_000_000 lamports)
-pub const DEFAULT_INITIAL_LIQUIDITY: u64 = 500_000_000;
+use crate::events::MarketCreated;
 
 #[derive(Accounts)]
-#[instruction(market_id: u64, question: String)]
+#[instruction(market_id: u64, question: String, outcomes: Vec<String>)]
 pub struct CreateMarket<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -14,12 +24,33 @@ pub struct CreateMarket<'info> {
     #[account(
         init,
         payer = authority,
-        space = Market::SIZE,
+        space = Market::space(outcomes.len()),
         seeds = [b"market", market_id.to_le_bytes().as_ref()],
         bump
     )]
     pub market: Account<'info, Market>,
 
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = FeeVault::SIZE,
+        seeds = [b"protocol_fee_vault", market.key().as_ref()],
+        bump
+    )]
+    pub protocol_fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = FeeVault::SIZE,
+        seeds = [b"creator_fee_vault", market.key().as_ref()],
+        bump
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+
     /// CHECK: Vault PDA to hold bet funds
     #[account(
         mut,
@@ -28,56 +59,285 @@ pub struct CreateMarket<'info> {
     )]
     pub vault: AccountInfo<'info>,
 
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = vault,
+        token::token_program = token_program,
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
 }
 
 /// Create a new prediction market with initial liquidity
 ///
-/// The market creator provides initial liquidity for both sides.
-/// This ensures there's always profit potential for bettors.
-/// Initial liquidity is set at 0.5 SOL per side (1 SOL total).
+/// The market creator chooses the initial liquidity for every outcome:
+/// - Equal seeds start at even odds (an empty list seeds every outcome with
+///   Config::default_liquidity)
+/// - Asymmetric seeds set the starting odds
+/// - Zero seeds make a pure parimutuel market
+///
+/// Every encrypted pool total starts out as an encryption of its seed amount.
+/// The seeds are stored on the market so clients can tell them apart from bets.
+///
+/// An LMSR market takes no seeds: the creator funds the market maker with
+/// lmsr::max_loss(b, outcomes), which is also the most the creator can lose.
+///
+/// A SOL vault is also funded with the rent-exempt minimum on top of the seeds,
+/// so it stays rent-exempt until finalize_market sweeps it.
+///
+/// Pass collateral_mint (plus the token accounts) to denominate the market in an
+/// SPL / Token-2022 token such as USDC instead of SOL.
+///
+/// Parameters:
+/// - outcomes: 2..=16 outcome labels; a binary market uses ["NO", "YES"]
+/// - kind: Categorical, Scalar with bounds (outcomes must then be ["SHORT", "LONG"]),
+///   or Lmsr with liquidity b > 0
+/// - initial_liquidity: seed amount per outcome, in outcome order
+/// - resolution_time: earliest time the market can be resolved
+/// - betting_closes_at: bets are rejected from this time on (must not be after resolution_time)
+/// - creator_fee_bps: creator fee on winnings (at most Config::max_creator_fee_bps);
+///   the protocol fee is taken from Config
+/// - resolution: who decides the outcome
+///   - Authority: the market authority
+///   - Oracle: a Pyth price feed, permissionlessly. Binary or scalar markets only;
+///     feed_id must be set and evaluation_time must not be before betting_closes_at
+///   - Optimistic: bonded proposals with a dispute window (see propose_resolution)
+///   - Committee: M-of-N vote of a ResolverSet (see vote_resolution)
+///
+/// Rejected while the program is paused, or if the collateral mint is not allowed
+/// or is a Token-2022 mint with a transfer fee.
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<CreateMarket>,
     market_id: u64,
     question: String,
+    outcomes: Vec<String>,
+    kind: MarketKind,
+    initial_liquidity: Vec<u64>,
     resolution_time: i64,
+    betting_closes_at: i64,
+    creator_fee_bps: u16,
+    resolution: ResolutionSource,
 ) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require!(!config.paused, DakeError::ProgramPaused);
     require!(question.len() <= 256, DakeError::QuestionTooLong);
+    require!(
+        creator_fee_bps <= config.max_creator_fee_bps,
+        DakeError::InvalidFeeBps
+    );
+    require!(
+        betting_closes_at <= resolution_time,
+        DakeError::InvalidBettingCutoff
+    );
+    require!(
+        (MIN_OUTCOMES..=MAX_OUTCOMES).contains(&outcomes.len()),
+        DakeError::InvalidOutcomeCount
+    );
+    require!(
+        outcomes.iter().all(|label| label.len() <= MAX_OUTCOME_LABEL_LEN),
+        DakeError::OutcomeLabelTooLong
+    );
+    if let MarketKind::Scalar { lower_bound, upper_bound } = kind {
+        require!(outcomes.len() == 2, DakeError::InvalidOutcomeCount);
+        require!(lower_bound < upper_bound, DakeError::InvalidScalarBounds);
+    }
+    let subsidy = match kind {
+        MarketKind::Lmsr { liquidity } => {
+            require!(
+                initial_liquidity.iter().all(|seed| *seed == 0),
+                DakeError::InvalidInitialLiquidity
+            );
+            lmsr::max_loss(liquidity, outcomes.len()).ok_or(DakeError::InvalidInitialLiquidity)?
+        }
+        _ => 0,
+    };
+
+    let initial_liquidity = match kind {
+        MarketKind::Lmsr { .. } if initial_liquidity.is_empty() => vec![0; outcomes.len()],
+        _ if initial_liquidity.is_empty() => vec![config.default_liquidity; outcomes.len()],
+        _ => initial_liquidity,
+    };
+    if let ResolutionSource::Oracle(oracle) = &resolution {
+        require!(outcomes.len() == 2, DakeError::InvalidOracleConfig);
+        require!(oracle.feed_id != [0; 32], DakeError::InvalidOracleConfig);
+        require!(
+            oracle.evaluation_time >= betting_closes_at,
+            DakeError::InvalidOracleConfig
+        );
+        require!(
+            oracle.comparator != OracleComparator::Between
+                || oracle.threshold <= oracle.upper_threshold,
+            DakeError::InvalidOracleConfig
+        );
+    }
+
+    if let ResolutionSource::Committee { vote_window_secs, .. } = resolution {
+        require!(vote_window_secs > 0, DakeError::InvalidResolverSet);
+    }
+
+    require!(
+        initial_liquidity.len() == outcomes.len(),
+        DakeError::InvalidInitialLiquidity
+    );
+
+    let outcome_count = outcomes.len();
+    let total_liquidity = initial_liquidity
+        .iter()
+        .try_fold(subsidy, |acc, seed| acc.checked_add(*seed))
+        .ok_or(DakeError::InvalidInitialLiquidity)?;
 
-    let initial_liquidity = DEFAULT_INITIAL_LIQUIDITY;
-    let total_liquidity = initial_liquidity.checked_mul(2).unwrap();
-
-    // Transfer initial liquidity from creator to vault (funds both YES and NO pools)
-    anchor_lang::solana_program::program::invoke(
-        &anchor_lang::solana_program::system_instruction::transfer(
-            &ctx.accounts.authority.key(),
-            &ctx.accounts.vault.key(),
-            total_liquidity,
-        ),
-        &[
-            ctx.accounts.authority.to_account_info(),
-            ctx.accounts.vault.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    ctx.accounts.market.collateral_mint = ctx
+        .accounts
+        .collateral_mint
+        .as_ref()
+        .map(|mint| mint.key())
+        .unwrap_or_default();
+    require!(
+        ctx.accounts.config.is_mint_allowed(&ctx.accounts.market.collateral_mint),
+        DakeError::CollateralMintNotAllowed
+    );
+
+    // Transfer initial liquidity from creator to vault (funds every outcome pool)
+    let token = collateral::token_collateral(
+        &ctx.accounts.market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.authority_token_account,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.token_program,
     )?;
+    // A SOL vault also gets its rent reserve, which is not part of any pool
+    let vault_reserve = match token {
+        Some(_) => 0,
+        None => collateral::vault_reserve()?,
+    };
+    let deposit = total_liquidity
+        .checked_add(vault_reserve)
+        .ok_or(DakeError::MathOverflow)?;
+    if deposit > 0 {
+        collateral::transfer_to_vault(
+            &ctx.accounts.authority,
+            &ctx.accounts.vault,
+            &ctx.accounts.system_program,
+            token,
+            deposit,
+        )?;
+    }
+
+    // Seed the encrypted pool totals with the (public) initial liquidity;
+    // LMSR markets keep public share totals only
+    let inco = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.authority.to_account_info();
+    let mut encrypted_totals = Vec::with_capacity(outcome_count);
+    let encrypted_seeds = match kind {
+        MarketKind::Lmsr { .. } => &[][..],
+        _ => &initial_liquidity[..],
+    };
+    for seed in encrypted_seeds {
+        let cpi_ctx = CpiContext::new(
+            inco.clone(),
+            Operation {
+                signer: signer.clone(),
+            },
+        );
+        let seed_encrypted: Euint128 = cpi::as_euint128(cpi_ctx, *seed as u128)?;
+        encrypted_totals.push(seed_encrypted.0);
+    }
+
+    let market_key = ctx.accounts.market.key();
+    for (fee_vault, kind, bump) in [
+        (&mut ctx.accounts.protocol_fee_vault, FeeKind::Protocol, ctx.bumps.protocol_fee_vault),
+        (&mut ctx.accounts.creator_fee_vault, FeeKind::Creator, ctx.bumps.creator_fee_vault),
+    ] {
+        fee_vault.market = market_key;
+        fee_vault.kind = kind;
+        fee_vault.accrued = 0;
+        fee_vault.withdrawn = 0;
+        fee_vault.bump = bump;
+    }
 
     let market = &mut ctx.accounts.market;
     market.authority = ctx.accounts.authority.key();
     market.market_id = market_id;
     market.question = question.clone();
     market.resolution_time = resolution_time;
+    market.betting_closes_at = betting_closes_at;
     market.status = MarketStatus::Open;
-    // Initialize both pools with liquidity - ensures profitable odds from start
-    market.total_yes_amount = initial_liquidity;
-    market.total_no_amount = initial_liquidity;
+    market.kind = kind;
+    market.resolved_value = 0;
+    market.outcomes = outcomes;
+    market.winning_outcome = 0;
+    // Initialize every pool with its seed liquidity
+    market.initial_liquidity = initial_liquidity;
+    market.encrypted_totals = encrypted_totals;
+    // Plaintext totals stay zero until reveal_pools (LMSR: shares outstanding)
+    market.outcome_totals = vec![0; outcome_count];
+    market.pools_revealed = market.is_lmsr();
+    market.total_deposited = total_liquidity;
+    market.total_paid_out = 0;
+    market.exit_fees_collected = 0;
+    market.protocol_fee_bps = ctx.accounts.config.protocol_fee_bps;
+    market.creator_fee_bps = creator_fee_bps;
+    market.resolution = resolution;
     market.participant_count = 0;
+    market.open_positions = 0;
+    market.created_at = Clock::get()?.unix_timestamp;
+    market.settled_at = 0;
     market.bump = ctx.bumps.market;
 
-    msg!("Dake Market #{} created with initial liquidity!", market_id);
+    emit!(MarketCreated {
+        market: market.key(),
+        market_id,
+        authority: market.authority,
+        question: market.question.clone(),
+        outcomes: market.outcomes.clone(),
+        kind: market.kind,
+        collateral_mint: market.collateral_mint,
+        initial_liquidity: market.initial_liquidity.clone(),
+        encrypted_totals: market.encrypted_totals.clone(),
+        resolution_time,
+        betting_closes_at,
+        protocol_fee_bps: market.protocol_fee_bps,
+        creator_fee_bps: market.creator_fee_bps,
+        resolution: market.resolution,
+    });
+
+    msg!("Dake Market #{} created!", market_id);
     msg!("   Question: {}", question);
     msg!("   Resolution time: {}", resolution_time);
-    msg!("   Initial liquidity: {} lamports per side", initial_liquidity);
-    msg!("   Starting odds: 2.00x (50/50)");
+    msg!("   Betting closes at: {}", betting_closes_at);
+    msg!("   Outcomes: {}", outcome_count);
+    if market.uses_token_collateral() {
+        msg!("   Collateral mint: {}", market.collateral_mint);
+    }
+    msg!(
+        "   Fees: {} bps protocol, {} bps creator",
+        market.protocol_fee_bps,
+        market.creator_fee_bps
+    );
+    if market.is_lmsr() {
+        msg!("   LMSR subsidy (max creator loss): {}", subsidy);
+    } else {
+        msg!("   Initial liquidity: {:?} (total {})", market.initial_liquidity, total_liquidity);
+    }
 
     Ok(())
 }
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::{ResolverSet, MAX_RESOLVERS};
+use crate::error::DakeError;
+
+#[derive(Accounts)]
+#[instruction(set_id: u64)]
+pub struct CreateResolverSet<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ResolverSet::SIZE,
+        seeds = [b"resolver_set", authority.key().as_ref(), set_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub resolver_set: Account<'info, ResolverSet>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create an M-of-N resolver committee that markets can resolve through
+///
+/// Parameters:
+/// - set_id: identifier, unique per authority
+/// - members: 1..=MAX_RESOLVERS unique committee members
+/// - threshold: matching votes needed to resolve - a strict majority of
+///   members, so two conflicting outcomes can never both reach it
+pub fn handler(
+    ctx: Context<CreateResolverSet>,
+    set_id: u64,
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(
+        !members.is_empty() && members.len() <= MAX_RESOLVERS,
+        DakeError::InvalidResolverSet
+    );
+    require!(
+        (threshold as usize) > members.len() / 2 && (threshold as usize) <= members.len(),
+        DakeError::InvalidResolverSet
+    );
+    for (i, member) in members.iter().enumerate() {
+        require!(!members[..i].contains(member), DakeError::InvalidResolverSet);
+    }
+
+    let resolver_set = &mut ctx.accounts.resolver_set;
+    resolver_set.authority = ctx.accounts.authority.key();
+    resolver_set.set_id = set_id;
+    resolver_set.members = members;
+    resolver_set.threshold = threshold;
+    resolver_set.bump = ctx.bumps.resolver_set;
+
+    msg!("Resolver set #{} created!", set_id);
+    msg!("   Members: {}", resolver_set.members.len());
+    msg!("   Threshold: {}", threshold);
+
+    Ok(())
+}
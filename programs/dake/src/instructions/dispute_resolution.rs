@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, ResolutionProposal, ARBITRATION_WINDOW_SECS};
+use crate::error::DakeError;
+use crate::events::ResolutionDisputed;
+
+#[derive(Accounts)]
+pub struct DisputeResolution<'info> {
+    #[account(mut)]
+    pub disputer: Signer<'info>,
+
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"resolution", market.key().as_ref(), market.created_at.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = market,
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Dispute a pending resolution by matching the proposer's bond
+///
+/// Only one dispute is accepted; the Config admin then rules via arbitrate_resolution.
+/// If it has not ruled within ARBITRATION_WINDOW_SECS, anyone can void the market
+/// with expire_arbitration.
+pub fn handler(ctx: Context<DisputeResolution>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let proposal = &ctx.accounts.proposal;
+
+    require!(market.is_pending_resolution(), DakeError::MarketNotResolved);
+    require!(proposal.disputer.is_none(), DakeError::AlreadyDisputed);
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < proposal.dispute_deadline, DakeError::DisputeWindowClosed);
+
+    let bond = proposal.bond;
+    anchor_lang::solana_program::program::invoke(
+        &anchor_lang::solana_program::system_instruction::transfer(
+            &ctx.accounts.disputer.key(),
+            &proposal.key(),
+            bond,
+        ),
+        &[
+            ctx.accounts.disputer.to_account_info(),
+            ctx.accounts.proposal.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    let arbitration_deadline = now
+        .checked_add(ARBITRATION_WINDOW_SECS)
+        .ok_or(DakeError::MathOverflow)?;
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.disputer = Some(ctx.accounts.disputer.key());
+    proposal.arbitration_deadline = arbitration_deadline;
+
+    emit!(ResolutionDisputed {
+        market: market.key(),
+        market_id: market.market_id,
+        disputer: ctx.accounts.disputer.key(),
+        bond,
+        arbitration_deadline,
+    });
+
+    msg!("Resolution DISPUTED for Dake Market #{}!", market.market_id);
+    msg!("   Disputer: {}", ctx.accounts.disputer.key());
+
+    Ok(())
+}
@@ -0,0 +1,183 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use inco_lightning::{
+    program::IncoLightning,
+    types::Euint128,
+    ID as INCO_LIGHTNING_ID,
+};
+use crate::state::{Config, FeeVault, Market, Position};
+use crate::collateral;
+use crate::fees;
+use crate::payout;
+use crate::pools::{self, PoolUpdate};
+use crate::error::DakeError;
+use crate::events::PositionExited;
+
+#[derive(Accounts)]
+pub struct ExitPosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        has_one = market @ DakeError::PositionMarketMismatch,
+        constraint = market.is_current(&position) @ DakeError::PositionMarketMismatch,
+        has_one = owner @ DakeError::NotOwner,
+        close = owner,
+    )]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: Vault PDA holding the funds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", market.key().as_ref()],
+        bump = creator_fee_vault.bump,
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        mut,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+/// Quote for exiting a position early: (refund, fee)
+///
+/// The pools are encrypted while betting is open, so the quote cannot depend on
+/// them without revealing sides - an exit returns the stake minus Config::exit_fee_bps.
+pub fn exit_quote(amount: u64, exit_fee_bps: u16) -> (u64, u64) {
+    let fee = payout::fee(amount, exit_fee_bps);
+    (amount - fee, fee)
+}
+
+/// Exit (sell back) a position while the market is still open
+///
+/// - The position amount is removed from its encrypted pool (side stays private)
+/// - The owner receives the quoted refund; the exit fee goes to the creator fee vault
+/// - The Position account is closed and its rent returned to the owner
+///
+/// LMSR positions cannot be exited.
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, ExitPosition<'info>>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let position = &ctx.accounts.position;
+
+    require!(
+        market.is_accepting_bets(Clock::get()?.unix_timestamp),
+        DakeError::MarketNotOpen
+    );
+    require!(!market.is_lmsr(), DakeError::WrongMarketKind);
+
+    let amount = position.amount;
+    let (refund, fee) = exit_quote(amount, ctx.accounts.config.exit_fee_bps);
+
+    let token = collateral::token_collateral(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.owner_token_account,
+        &ctx.accounts.owner.key(),
+        &ctx.accounts.token_program,
+    )?;
+
+    let vault_balance = collateral::vault_balance(&ctx.accounts.vault, token.as_ref())?;
+    require!(
+        amount <= market.outstanding_liability() && amount <= vault_balance,
+        DakeError::InsufficientVaultFunds
+    );
+
+    // Transfer refund from vault to owner
+    let market_key = market.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"vault",
+        market_key.as_ref(),
+        &[ctx.bumps.vault],
+    ];
+
+    if refund > 0 {
+        collateral::transfer_from_vault(
+            &ctx.accounts.vault,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program,
+            token,
+            vault_seeds,
+            refund,
+        )?;
+    }
+
+    // Take the full amount back out of the position's encrypted pool
+    let side_handle = Euint128(position.encrypted_side_handle);
+    fees::accrue(
+        &mut ctx.accounts.creator_fee_vault,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        market.uses_token_collateral(),
+        vault_seeds,
+        fee,
+    )?;
+
+    let market = &mut ctx.accounts.market;
+    pools::update_encrypted_pools(
+        &ctx.accounts.inco_lightning_program.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+        &mut market.encrypted_totals,
+        side_handle,
+        amount,
+        PoolUpdate::Remove,
+    )?;
+
+    market.total_paid_out = market
+        .total_paid_out
+        .checked_add(amount)
+        .ok_or(DakeError::MathOverflow)?;
+    market.open_positions = market.open_positions.saturating_sub(1);
+    market.exit_fees_collected = market
+        .exit_fees_collected
+        .checked_add(fee)
+        .ok_or(DakeError::MathOverflow)?;
+
+    emit!(PositionExited {
+        market: market.key(),
+        market_id: market.market_id,
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.owner.key(),
+        amount,
+        refund,
+        fee,
+    });
+
+    msg!("Position exited from Dake Market #{}!", market.market_id);
+    msg!("   Amount: {}", amount);
+    msg!("   Refund: {}", refund);
+    msg!("   Exit fee: {}", fee);
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Market, ResolutionProposal};
+use crate::collateral;
+use crate::error::DakeError;
+use super::cancel_market::void_market;
+
+#[derive(Accounts)]
+pub struct ExpireArbitration<'info> {
+    /// Anyone can expire a stalled arbitration
+    pub caller: Signer<'info>,
+
+    #[account(mut, has_one = authority @ DakeError::Unauthorized)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Market authority - receives the seed liquidity (checked via has_one)
+    #[account(mut)]
+    pub authority: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"resolution", market.key().as_ref(), market.created_at.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = market,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+
+    /// CHECK: Proposer (checked against proposal.proposer)
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    /// CHECK: Disputer (checked against proposal.disputer)
+    #[account(
+        mut,
+        constraint = proposal.disputer == Some(disputer.key()) @ DakeError::NotDisputed,
+    )]
+    pub disputer: AccountInfo<'info>,
+
+    /// CHECK: Vault PDA holding the funds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        mut,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Authority's token account - receives the seed liquidity (token markets only)
+    #[account(mut, token::authority = authority)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Cancel an optimistic market whose dispute was not arbitrated in time
+///
+/// Past the proposal's arbitration_deadline the market is voided: the seed
+/// liquidity goes back to the authority, every position can be refunded, and
+/// the proposer and disputer each get their own bond back.
+pub fn handler(ctx: Context<ExpireArbitration>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let proposal = &ctx.accounts.proposal;
+
+    require!(market.is_pending_resolution(), DakeError::MarketNotResolved);
+    require!(
+        Clock::get()?.unix_timestamp >= proposal.arbitration_deadline,
+        DakeError::ArbitrationStillOpen
+    );
+
+    // The disputer's bond goes back to them; closing the proposal returns the proposer's
+    let bond = proposal.bond;
+    let proposal_info = ctx.accounts.proposal.to_account_info();
+    **proposal_info.try_borrow_mut_lamports()? = proposal_info
+        .lamports()
+        .checked_sub(bond)
+        .ok_or(DakeError::MathOverflow)?;
+    let disputer = &ctx.accounts.disputer;
+    **disputer.try_borrow_mut_lamports()? = disputer
+        .lamports()
+        .checked_add(bond)
+        .ok_or(DakeError::MathOverflow)?;
+
+    let token = collateral::token_collateral(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.authority_token_account,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.token_program,
+    )?;
+
+    msg!("Arbitration expired without a ruling");
+    void_market(
+        &mut ctx.accounts.market,
+        &ctx.accounts.vault,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        token,
+        ctx.bumps.vault,
+    )
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Market, ResolutionSource};
+use crate::collateral;
+use crate::error::DakeError;
+use super::cancel_market::void_market;
+
+#[derive(Accounts)]
+pub struct ExpireResolutionVote<'info> {
+    /// Anyone can expire a stalled vote
+    pub caller: Signer<'info>,
+
+    #[account(mut, has_one = authority @ DakeError::Unauthorized)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Market authority - receives the seed liquidity (checked via has_one)
+    #[account(mut)]
+    pub authority: AccountInfo<'info>,
+
+    /// CHECK: Vault PDA holding the funds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        mut,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Authority's token account - receives the seed liquidity (token markets only)
+    #[account(mut, token::authority = authority)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Cancel a committee market whose vote did not reach the threshold in time
+///
+/// Conflicting (or missing) votes past the deadline void the market: the seed
+/// liquidity goes back to the authority and every position can be refunded.
+pub fn handler(ctx: Context<ExpireResolutionVote>) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    let ResolutionSource::Committee { vote_window_secs, .. } = market.resolution else {
+        return Err(DakeError::WrongResolutionSource.into());
+    };
+    require!(!market.is_resolved(), DakeError::MarketAlreadyResolved);
+    require!(!market.is_cancelled(), DakeError::MarketCancelled);
+    let vote_deadline = market
+        .resolution_time
+        .checked_add(vote_window_secs as i64)
+        .ok_or(DakeError::MathOverflow)?;
+    require!(
+        Clock::get()?.unix_timestamp >= vote_deadline,
+        DakeError::VoteStillOpen
+    );
+
+    let token = collateral::token_collateral(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.authority_token_account,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.token_program,
+    )?;
+
+    msg!("Resolution vote expired without a quorum");
+    void_market(
+        &mut ctx.accounts.market,
+        &ctx.accounts.vault,
+        &ctx.accounts.authority,
+        &ctx.accounts.system_program,
+        token,
+        ctx.bumps.vault,
+    )
+}
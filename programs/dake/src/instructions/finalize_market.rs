@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Config, FeeKind, FeeVault, Market};
+use crate::collateral;
+use crate::fees;
+use crate::error::DakeError;
+use crate::events::{FeesWithdrawn, MarketFinalized};
+
+/// How long after settlement positions can claim before the market can be finalized (30 days)
+pub const CLAIM_PERIOD_SECS: i64 = 30 * 24 * 60 * 60;
+
+#[derive(Accounts)]
+pub struct FinalizeMarket<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ DakeError::Unauthorized,
+        close = authority,
+    )]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Vault PDA holding the funds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee_vault", market.key().as_ref()],
+        bump = protocol_fee_vault.bump,
+        close = authority,
+    )]
+    pub protocol_fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", market.key().as_ref()],
+        bump = creator_fee_vault.bump,
+        close = authority,
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Config admin - receives protocol fees that were not withdrawn
+    #[account(mut, address = config.admin @ DakeError::Unauthorized)]
+    pub admin: AccountInfo<'info>,
+
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        mut,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Admin's token account - receives unwithdrawn protocol fees (token markets only)
+    #[account(mut)]
+    pub admin_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Close a settled market, sweep the vault to the creator and reclaim all rent
+///
+/// Allowed once the market is cancelled, or resolved with its pools revealed, and
+/// either every position has been closed or CLAIM_PERIOD_SECS have passed since
+/// settlement. The creator fee vault must have been withdrawn; protocol fees the
+/// admin has not withdrawn are paid out to config.admin here (to admin_token_account
+/// for token markets). Whatever the vault still holds (the creator's share of the
+/// seed liquidity, rounding dust and unclaimed winnings) goes to the authority, and
+/// the market, fee vault and vault token accounts are closed.
+pub fn handler(ctx: Context<FinalizeMarket>) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    require!(
+        market.is_resolved() || market.is_cancelled(),
+        DakeError::MarketNotSettled
+    );
+    // Nobody could claim from pools that were never revealed
+    require!(
+        market.pools_revealed || market.is_cancelled(),
+        DakeError::PoolsNotRevealed
+    );
+    require!(
+        market.open_positions == 0
+            || Clock::get()?.unix_timestamp >= market.settled_at + CLAIM_PERIOD_SECS,
+        DakeError::ClaimPeriodNotOver
+    );
+    require!(
+        ctx.accounts.creator_fee_vault.available() == 0,
+        DakeError::FeesNotWithdrawn
+    );
+
+    let market_key = market.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"vault",
+        market_key.as_ref(),
+        &[ctx.bumps.vault],
+    ];
+
+    // The admin's share must not be swept to the creator
+    let protocol_fees = ctx.accounts.protocol_fee_vault.available();
+    if protocol_fees > 0 {
+        let admin_token = collateral::token_collateral(
+            market,
+            &ctx.accounts.collateral_mint,
+            &ctx.accounts.vault_token_account,
+            &ctx.accounts.admin_token_account,
+            &ctx.accounts.admin.key(),
+            &ctx.accounts.token_program,
+        )?;
+        fees::withdraw(
+            &mut ctx.accounts.protocol_fee_vault,
+            &ctx.accounts.vault,
+            &ctx.accounts.admin,
+            &ctx.accounts.system_program,
+            admin_token,
+            vault_seeds,
+        )?;
+
+        emit!(FeesWithdrawn {
+            market: market_key,
+            market_id: market.market_id,
+            kind: FeeKind::Protocol,
+            recipient: ctx.accounts.admin.key(),
+            amount: protocol_fees,
+        });
+
+        // Token fees left through the vault token account; sweep what remains
+        if let Some(vault_token_account) = ctx.accounts.vault_token_account.as_mut() {
+            vault_token_account.reload()?;
+        }
+    }
+
+    let token = collateral::token_collateral(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.authority_token_account,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.token_program,
+    )?;
+
+    // Everything, including a SOL vault's rent reserve
+    let swept = match &token {
+        Some(token) => token.vault_token_account.amount,
+        None => ctx.accounts.vault.lamports(),
+    };
+    let vault_token_account = token.as_ref().map(|token| token.vault_token_account);
+    let token_program = token.as_ref().map(|token| token.token_program);
+    if swept > 0 {
+        collateral::transfer_from_vault(
+            &ctx.accounts.vault,
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.system_program,
+            token,
+            vault_seeds,
+            swept,
+        )?;
+    }
+    if let (Some(vault_token_account), Some(token_program)) = (vault_token_account, token_program) {
+        collateral::close_vault_token_account(
+            &ctx.accounts.vault,
+            vault_token_account,
+            &ctx.accounts.authority.to_account_info(),
+            token_program,
+            vault_seeds,
+        )?;
+    }
+
+    emit!(MarketFinalized {
+        market: market_key,
+        market_id: market.market_id,
+        swept,
+        protocol_fees,
+        unclosed_positions: market.open_positions,
+    });
+
+    msg!("Dake Market #{} FINALIZED!", market.market_id);
+    msg!("   Protocol fees to admin: {}", protocol_fees);
+    msg!("   Swept to creator: {}", swept);
+    msg!("   Unclosed positions: {}", market.open_positions);
+
+    Ok(())
+}
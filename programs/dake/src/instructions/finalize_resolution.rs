@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, ResolutionProposal};
+use crate::error::DakeError;
+use crate::events::MarketResolved;
+
+#[derive(Accounts)]
+pub struct FinalizeResolution<'info> {
+    /// Anyone can finalize an undisputed proposal
+    pub finalizer: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"resolution", market.key().as_ref(), market.created_at.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+        has_one = market,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+
+    /// CHECK: Receives the bond back (checked against proposal.proposer)
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+}
+
+/// Finalize an undisputed proposal once the dispute window has ended
+///
+/// The proposed outcome becomes the resolution and the proposer gets the bond back.
+pub fn handler(ctx: Context<FinalizeResolution>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+
+    require!(
+        ctx.accounts.market.is_pending_resolution(),
+        DakeError::MarketNotResolved
+    );
+    require!(proposal.disputer.is_none(), DakeError::ResolutionDisputed);
+    require!(
+        Clock::get()?.unix_timestamp >= proposal.dispute_deadline,
+        DakeError::DisputeWindowOpen
+    );
+
+    let market = &mut ctx.accounts.market;
+    market.apply_resolution(proposal.outcome, proposal.value)?;
+
+    emit!(MarketResolved {
+        market: market.key(),
+        market_id: market.market_id,
+        winning_outcome: market.winning_outcome,
+        resolved_value: market.resolved_value,
+        outcome_totals: market.outcome_totals.clone(),
+    });
+
+    msg!("Dake Market #{} RESOLVED (undisputed)!", market.market_id);
+    msg!("   Question: {}", market.question);
+    msg!("   Outcome: {} wins!", market.outcomes[market.winning_outcome as usize]);
+    msg!("   Bond returned: {} lamports", proposal.bond);
+
+    Ok(())
+}
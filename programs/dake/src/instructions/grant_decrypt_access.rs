@@ -6,6 +6,7 @@ use inco_lightning::{
 };
 use crate::state::Position;
 use crate::error::DakeError;
+use crate::events::DecryptAccessGranted;
 
 #[derive(Accounts)]
 pub struct GrantDecryptAccess<'info> {
@@ -45,6 +46,13 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, GrantDecryptAccess<'info>>
 
     cpi::allow(cpi_ctx, position.is_winner_handle, true, position.owner)?;
 
+    emit!(DecryptAccessGranted {
+        market: position.market,
+        position: position.key(),
+        owner: position.owner,
+        handle: position.is_winner_handle,
+    });
+
     msg!("Decrypt access granted for is_winner_handle: {}", position.is_winner_handle);
 
     Ok(())
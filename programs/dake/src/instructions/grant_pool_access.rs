@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use inco_lightning::{
+    program::IncoLightning,
+    ID as INCO_LIGHTNING_ID,
+};
+use crate::state::Market;
+use crate::pools;
+use crate::error::DakeError;
+use crate::events::PoolAccessGranted;
+
+#[derive(Accounts)]
+pub struct GrantPoolAccess<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    pub market: Account<'info, Market>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+/// Grant the caller decrypt access to the pool totals of a closed market
+///
+/// The totals are about to be published by reveal_pools anyway, so anyone may
+/// decrypt them once betting is over. This is what lets someone other than the
+/// closer reveal the pools.
+///
+/// Remaining accounts, one pair per outcome in outcome order:
+/// - [2i] allowance PDA for encrypted_totals[i], [2i + 1] caller
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, GrantPoolAccess<'info>>) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    require!(!market.is_open(), DakeError::MarketStillOpen);
+    require!(!market.pools_revealed, DakeError::PoolsAlreadyRevealed);
+    require!(
+        ctx.remaining_accounts.len() >= 2 * market.outcome_count(),
+        DakeError::MissingAllowanceAccounts
+    );
+    for accounts in ctx.remaining_accounts.chunks(2) {
+        require!(
+            accounts.get(1).map(|grantee| grantee.key()) == Some(ctx.accounts.caller.key()),
+            DakeError::GranteeNotCaller
+        );
+    }
+
+    pools::allow_totals(
+        &ctx.accounts.inco_lightning_program.to_account_info(),
+        &ctx.accounts.caller.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.remaining_accounts,
+        &market.encrypted_totals,
+        ctx.accounts.caller.key(),
+    )?;
+
+    emit!(PoolAccessGranted {
+        market: market.key(),
+        market_id: market.market_id,
+        grantee: ctx.accounts.caller.key(),
+    });
+
+    msg!("Pool decrypt access granted for Dake Market #{}", market.market_id);
+    msg!("   Grantee: {}", ctx.accounts.caller.key());
+
+    Ok(())
+}
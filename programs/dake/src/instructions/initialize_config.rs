@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::program::Dake;
+use crate::state::{Config, ConfigParams};
+use crate::error::DakeError;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Config::SIZE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Dake>,
+
+    /// Only the program's upgrade authority can create the config
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ DakeError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the program-wide config (once, by the upgrade authority)
+///
+/// The creator becomes the admin; the program starts unpaused.
+pub fn handler(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.pending_admin = None;
+    config.paused = false;
+    config.bump = ctx.bumps.config;
+    config.apply(params)?;
+
+    msg!("Dake config initialized!");
+    msg!("   Admin: {}", config.admin);
+    msg!("   Protocol fee: {} bps", config.protocol_fee_bps);
+    msg!("   Bet limits: {} - {}", config.min_bet, config.max_bet);
+
+    Ok(())
+}
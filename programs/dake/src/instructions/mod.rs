@@ -1,15 +1,69 @@
+#![allow(ambiguous_glob_reexports)]
+
+pub mod initialize_config;
 pub mod create_market;
 pub mod place_bet;
+pub mod buy_shares;
 pub mod close_market;
+pub mod reveal_pools;
+pub mod grant_pool_access;
 pub mod resolve_market;
+pub mod resolve_scalar_market;
+pub mod resolve_from_oracle;
+pub mod propose_resolution;
+pub mod dispute_resolution;
+pub mod finalize_resolution;
+pub mod arbitrate_resolution;
+pub mod create_resolver_set;
+pub mod vote_resolution;
+pub mod expire_arbitration;
+pub mod expire_resolution_vote;
+pub mod close_position;
+pub mod finalize_market;
 pub mod check_winner;
 pub mod claim_winnings;
+pub mod redeem_shares;
 pub mod grant_decrypt_access;
+pub mod cancel_market;
+pub mod refund_position;
+pub mod exit_position;
+pub mod withdraw_protocol_fees;
+pub mod withdraw_creator_fees;
+pub mod update_config;
+pub mod set_paused;
+pub mod propose_admin;
+pub mod accept_admin;
 
+pub use initialize_config::*;
 pub use create_market::*;
 pub use place_bet::*;
+pub use buy_shares::*;
 pub use close_market::*;
+pub use reveal_pools::*;
+pub use grant_pool_access::*;
 pub use resolve_market::*;
+pub use resolve_scalar_market::*;
+pub use resolve_from_oracle::*;
+pub use propose_resolution::*;
+pub use dispute_resolution::*;
+pub use finalize_resolution::*;
+pub use arbitrate_resolution::*;
+pub use create_resolver_set::*;
+pub use vote_resolution::*;
+pub use expire_arbitration::*;
+pub use expire_resolution_vote::*;
+pub use close_position::*;
+pub use finalize_market::*;
 pub use check_winner::*;
 pub use claim_winnings::*;
+pub use redeem_shares::*;
 pub use grant_decrypt_access::*;
+pub use cancel_market::*;
+pub use refund_position::*;
+pub use exit_position::*;
+pub use withdraw_protocol_fees::*;
+pub use withdraw_creator_fees::*;
+pub use update_config::*;
+pub use set_paused::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
@@ -1,14 +1,19 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use inco_lightning::{
     cpi::{self, accounts::{Allow, Operation}},
     program::IncoLightning,
     types::Euint128,
     ID as INCO_LIGHTNING_ID,
 };
-use crate::state::{Market, Position};
+use crate::state::{Config, Market, Position};
+use crate::collateral;
+use crate::pools::{self, PoolUpdate};
 use crate::error::DakeError;
+use crate::events::BetPlaced;
 
 #[derive(Accounts)]
+#[instruction(encrypted_side: Vec<u8>, amount: u64, position_nonce: u64)]
 pub struct PlaceBet<'info> {
     #[account(mut)]
     pub bettor: Signer<'info>,
@@ -16,11 +21,19 @@ pub struct PlaceBet<'info> {
     #[account(mut)]
     pub market: Account<'info, Market>,
 
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
     #[account(
         init,
         payer = bettor,
         space = Position::SIZE,
-        seeds = [b"position", market.key().as_ref(), bettor.key().as_ref()],
+        seeds = [
+            b"position",
+            market.key().as_ref(),
+            bettor.key().as_ref(),
+            position_nonce.to_le_bytes().as_ref(),
+        ],
         bump
     )]
     pub position: Account<'info, Position>,
@@ -33,6 +46,22 @@ pub struct PlaceBet<'info> {
     )]
     pub vault: AccountInfo<'info>,
 
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        mut,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub bettor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
 
     #[account(address = INCO_LIGHTNING_ID)]
@@ -42,95 +71,132 @@ pub struct PlaceBet<'info> {
 /// Place a bet on a prediction market
 ///
 /// Parameters:
-/// - encrypted_side: Encrypted value of the side (1 = YES, 0 = NO)
-/// - amount: Bet amount in lamports (public - needed for payout calculation)
-/// - side_for_pool: The actual side (0 or 1) to update pool totals
-///                  This must match the encrypted value - we trust the client here
-///                  (In production, you'd use a commit-reveal scheme)
+/// - encrypted_side: Encrypted outcome index (binary: 1 = YES, 0 = NO)
+/// - amount: Bet amount in lamports or collateral base units (public - needed for payout calculation)
+/// - position_nonce: Distinguishes positions of the same bettor in the same market.
+///   Each bet is its own tranche with its own encrypted side, so a wallet can add to
+///   its exposure (or hedge another outcome) by betting again with a new nonce.
+/// - min_payout: Slippage guard - the smallest payout (stake included) the bet accepts
+///   at the pools it lands on, i.e. amount * total_pool / own_pool after the bet.
+///   0, or anything up to amount, always fills. The pools are encrypted, so the guard
+///   is checked on the ciphertexts: a bet that fails it is still taken but joins no
+///   pool, and check_winner / claim_winnings refund its stake once the market resolves.
+///   A min_payout above everything the market holds fails with PayoutBelowMinimum.
+///
+/// The pool totals are updated from the ciphertext itself, for every outcome i:
+/// - total[i] += select(side == i, amount, 0)
+///
+/// Bets are rejected once betting_closes_at has passed, even if close_market
+/// has not been called yet, and while the program is paused. The amount must be
+/// within the Config bet limits.
+///
+/// An out-of-range outcome adds to no pool and can never win.
+/// Markets with many outcomes need a raised compute unit limit.
+/// LMSR markets take buy_shares instead.
 pub fn handler<'info>(
     ctx: Context<'_, '_, '_, 'info, PlaceBet<'info>>,
     encrypted_side: Vec<u8>,
     amount: u64,
-    side_for_pool: u8, // 0 = NO, 1 = YES
+    position_nonce: u64,
+    min_payout: u64,
 ) -> Result<()> {
-    let market = &mut ctx.accounts.market;
-    require!(market.is_open(), DakeError::MarketNotOpen);
+    require!(ctx.accounts.market.is_open(), DakeError::MarketNotOpen);
+    require!(!ctx.accounts.market.is_lmsr(), DakeError::WrongMarketKind);
+    require!(
+        ctx.accounts.market.is_accepting_bets(Clock::get()?.unix_timestamp),
+        DakeError::BettingClosed
+    );
     require!(amount > 0, DakeError::InvalidBetAmount);
-    require!(side_for_pool <= 1, DakeError::InvalidSide);
+    require!(!ctx.accounts.config.paused, DakeError::ProgramPaused);
+    require!(
+        ctx.accounts.config.is_bet_in_range(amount),
+        DakeError::BetOutOfRange
+    );
 
     // Transfer bet amount to vault
-    anchor_lang::solana_program::program::invoke(
-        &anchor_lang::solana_program::system_instruction::transfer(
-            &ctx.accounts.bettor.key(),
-            &ctx.accounts.vault.key(),
-            amount,
-        ),
-        &[
-            ctx.accounts.bettor.to_account_info(),
-            ctx.accounts.vault.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
+    let token = collateral::token_collateral(
+        &ctx.accounts.market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.bettor_token_account,
+        &ctx.accounts.bettor.key(),
+        &ctx.accounts.token_program,
+    )?;
+    collateral::transfer_to_vault(
+        &ctx.accounts.bettor,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        token,
+        amount,
     )?;
 
-    // Get current pool values BEFORE adding this bet
-    let yes_pool_before = market.total_yes_amount;
-    let no_pool_before = market.total_no_amount;
-    let total_pool_before = yes_pool_before.saturating_add(no_pool_before);
-
-    // Update pool totals based on side
-    if side_for_pool == 1 {
-        market.total_yes_amount = market.total_yes_amount.saturating_add(amount);
-    } else {
-        market.total_no_amount = market.total_no_amount.saturating_add(amount);
-    }
-    market.participant_count += 1;
-
-    // Calculate LOCKED payout at bet time (odds locked forever!)
-    // Formula: locked_payout = (amount * (total_pool + amount)) / (side_pool + amount)
-    // This uses AFTER-bet pools for the calculation
-    let side_pool_after = if side_for_pool == 1 {
-        market.total_yes_amount
-    } else {
-        market.total_no_amount
-    };
-    let total_pool_after = market.total_yes_amount.saturating_add(market.total_no_amount);
-
-    let locked_payout = if side_pool_after > 0 {
-        (amount as u128)
-            .checked_mul(total_pool_after as u128)
-            .and_then(|v| v.checked_div(side_pool_after as u128))
-            .map(|v| v as u64)
-            .unwrap_or(amount)
-    } else {
-        // Edge case: return original amount
-        amount
-    };
+    let market = &mut ctx.accounts.market;
+    market.total_deposited = market
+        .total_deposited
+        .checked_add(amount)
+        .ok_or(DakeError::MathOverflow)?;
+
+    // The pools add up to at most what the vault still owes, so no payout exceeds it
+    require!(
+        min_payout <= market.outstanding_liability(),
+        DakeError::PayoutBelowMinimum
+    );
 
-    // Create encrypted side handle using Inco Lightning
     let inco = ctx.accounts.inco_lightning_program.to_account_info();
+    let signer = ctx.accounts.bettor.to_account_info();
     let cpi_ctx = CpiContext::new(
         inco.clone(),
         Operation {
-            signer: ctx.accounts.bettor.to_account_info(),
+            signer: signer.clone(),
         },
     );
-    let side_handle: Euint128 = cpi::new_euint128(cpi_ctx, encrypted_side, 0)?;
 
-    // Store position with LOCKED payout
+    // Create encrypted side handle using Inco Lightning
+    let mut side_handle: Euint128 = cpi::new_euint128(cpi_ctx, encrypted_side, 0)?;
+
+    // A payout is never below the stake, so smaller guards always fill
+    if min_payout > amount {
+        side_handle = pools::guard_min_payout(
+            &inco,
+            &signer,
+            &market.encrypted_totals,
+            side_handle,
+            amount,
+            min_payout,
+        )?;
+    }
+
+    // Route the amount into the matching encrypted pool without revealing the outcome
+    pools::update_encrypted_pools(
+        &inco,
+        &signer,
+        &mut market.encrypted_totals,
+        side_handle,
+        amount,
+        PoolUpdate::Add,
+    )?;
+
+    market.participant_count += 1;
+    market.open_positions += 1;
+
+    // Store position
     let position = &mut ctx.accounts.position;
     position.market = market.key();
     position.owner = ctx.accounts.bettor.key();
+    position.nonce = position_nonce;
+    position.opened_at = Clock::get()?.unix_timestamp;
     position.amount = amount;
-    position.locked_payout = locked_payout; // LOCKED - never changes!
     position.encrypted_side_handle = side_handle.0;
     position.is_winner_handle = 0; // Will be set during check_winner
     position.claimed = false;
+    position.outcome = 0;
+    position.shares = 0;
     position.bump = ctx.bumps.position;
 
     // Allow bettor to decrypt their own side
     if ctx.remaining_accounts.len() >= 2 {
         let cpi_ctx = CpiContext::new(
-            inco,
+            inco.clone(),
             Allow {
                 allowance_account: ctx.remaining_accounts[0].clone(),
                 signer: ctx.accounts.bettor.to_account_info(),
@@ -141,12 +207,25 @@ pub fn handler<'info>(
         cpi::allow(cpi_ctx, side_handle.0, true, ctx.accounts.bettor.key())?;
     }
 
-    let multiplier = if amount > 0 { (locked_payout as f64 / amount as f64 * 100.0) as u64 } else { 100 };
+    emit!(BetPlaced {
+        market: market.key(),
+        market_id: market.market_id,
+        position: position.key(),
+        owner: position.owner,
+        nonce: position_nonce,
+        amount,
+        min_payout,
+        side_handle: side_handle.0,
+        encrypted_totals: market.encrypted_totals.clone(),
+        participant_count: market.participant_count,
+    });
+
     msg!("Bet placed on Dake Market #{}!", market.market_id);
-    msg!("   Amount: {} lamports", amount);
-    msg!("   Locked payout: {} lamports ({}% return if win)", locked_payout, multiplier);
+    msg!("   Amount: {}", amount);
+    msg!("   Position nonce: {}", position_nonce);
+    msg!("   Min payout: {}", min_payout);
     msg!("   Side handle: {} (encrypted - nobody knows your position!)", side_handle.0);
-    msg!("   Pool totals - YES: {}, NO: {}", market.total_yes_amount, market.total_no_amount);
+    msg!("   Encrypted pool handles: {:?}", market.encrypted_totals);
 
     Ok(())
 }
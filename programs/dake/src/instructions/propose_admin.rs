@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::error::DakeError;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DakeError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Propose a new admin (step 1 of 2) - the new admin must call accept_admin
+///
+/// Proposing again replaces the pending admin.
+pub fn handler(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.config.pending_admin = Some(new_admin);
+
+    msg!("Dake admin change proposed!");
+    msg!("   Pending admin: {}", new_admin);
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    Config, Market, MarketStatus, ResolutionProposal, ResolutionSource,
+};
+use crate::error::DakeError;
+use crate::events::ResolutionProposed;
+
+#[derive(Accounts)]
+pub struct ProposeResolution<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = ResolutionProposal::SIZE,
+        seeds = [b"resolution", market.key().as_ref(), market.created_at.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub proposal: Account<'info, ResolutionProposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Propose the outcome of an optimistic market, posting Config::resolution_bond
+///
+/// Parameters:
+/// - outcome: proposed winning outcome (categorical markets)
+/// - value: proposed value (scalar markets)
+///
+/// Anyone can propose once resolution_time has passed. The market then stays in
+/// PendingResolution until finalize_resolution (undisputed, after the window) or
+/// arbitrate_resolution (disputed).
+pub fn handler(ctx: Context<ProposeResolution>, outcome: u8, value: i64) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    require!(
+        market.resolution == ResolutionSource::Optimistic,
+        DakeError::WrongResolutionSource
+    );
+    require!(!market.is_resolved(), DakeError::MarketAlreadyResolved);
+    require!(!market.is_cancelled(), DakeError::MarketCancelled);
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= market.resolution_time,
+        DakeError::ResolutionTimeNotReached
+    );
+    if !market.is_scalar() {
+        require!(
+            (outcome as usize) < market.outcome_count(),
+            DakeError::InvalidOutcome
+        );
+    }
+
+    // Bond is held by the proposal account
+    let bond = ctx.accounts.config.resolution_bond;
+    anchor_lang::solana_program::program::invoke(
+        &anchor_lang::solana_program::system_instruction::transfer(
+            &ctx.accounts.proposer.key(),
+            &ctx.accounts.proposal.key(),
+            bond,
+        ),
+        &[
+            ctx.accounts.proposer.to_account_info(),
+            ctx.accounts.proposal.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.market = market.key();
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.outcome = outcome;
+    proposal.value = value;
+    proposal.bond = bond;
+    proposal.dispute_deadline = now + ctx.accounts.config.dispute_window_secs as i64;
+    proposal.disputer = None;
+    proposal.arbitration_deadline = 0;
+    proposal.bump = ctx.bumps.proposal;
+
+    let market = &mut ctx.accounts.market;
+    market.status = MarketStatus::PendingResolution;
+
+    emit!(ResolutionProposed {
+        market: market.key(),
+        market_id: market.market_id,
+        proposer: proposal.proposer,
+        outcome,
+        value,
+        bond,
+        dispute_deadline: proposal.dispute_deadline,
+    });
+
+    msg!("Resolution proposed for Dake Market #{}!", market.market_id);
+    msg!("   Outcome: {} / value: {}", outcome, value);
+    msg!("   Bond: {} lamports", bond);
+    msg!("   Dispute deadline: {}", proposal.dispute_deadline);
+
+    Ok(())
+}
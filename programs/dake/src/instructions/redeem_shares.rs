@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{FeeVault, Market, Position};
+use crate::collateral;
+use crate::fees;
+use crate::payout;
+use crate::error::DakeError;
+use crate::events::WinningsClaimed;
+
+#[derive(Accounts)]
+pub struct RedeemShares<'info> {
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market @ DakeError::PositionMarketMismatch,
+        constraint = market.is_current(&position) @ DakeError::PositionMarketMismatch,
+    )]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: Vault PDA holding the funds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee_vault", market.key().as_ref()],
+        bump = protocol_fee_vault.bump,
+    )]
+    pub protocol_fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", market.key().as_ref()],
+        bump = creator_fee_vault.bump,
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        mut,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub winner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Redeem the winning shares of an LMSR position
+///
+/// Each share of the winning outcome pays one base unit. Fees: market.protocol_fee_bps
+/// and market.creator_fee_bps of the profit (shares minus the cost paid) go to the
+/// fee vaults, as in claim_winnings. Shares of a losing outcome pay nothing.
+pub fn handler(ctx: Context<RedeemShares>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let position = &mut ctx.accounts.position;
+
+    require!(market.is_lmsr(), DakeError::WrongMarketKind);
+    require!(position.owner == ctx.accounts.winner.key(), DakeError::NotOwner);
+    require!(!position.claimed, DakeError::AlreadyClaimed);
+    require!(market.is_resolved(), DakeError::MarketNotResolved);
+    require!(
+        position.outcome == market.winning_outcome && position.shares > 0,
+        DakeError::NotWinner
+    );
+
+    let cost = position.amount;
+    let payout = position.shares;
+
+    let token = collateral::token_collateral(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.winner_token_account,
+        &ctx.accounts.winner.key(),
+        &ctx.accounts.token_program,
+    )?;
+
+    // Never pay out more than the market still owes, or than the vault holds
+    let vault_balance = collateral::vault_balance(&ctx.accounts.vault, token.as_ref())?;
+    require!(
+        payout <= market.outstanding_liability() && payout <= vault_balance,
+        DakeError::InsufficientVaultFunds
+    );
+
+    // Mark as claimed
+    position.claimed = true;
+    let market = &mut ctx.accounts.market;
+    market.total_paid_out = market
+        .total_paid_out
+        .checked_add(payout)
+        .ok_or(DakeError::MathOverflow)?;
+
+    let (protocol_fee, creator_fee) = market.fees_on(payout, cost);
+    let net_payout = payout - protocol_fee - creator_fee;
+    let uses_token_collateral = market.uses_token_collateral();
+
+    // Transfer payout (minus fees) from vault to winner
+    let market_key = market.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"vault",
+        market_key.as_ref(),
+        &[ctx.bumps.vault],
+    ];
+
+    collateral::transfer_from_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.winner.to_account_info(),
+        &ctx.accounts.system_program,
+        token,
+        vault_seeds,
+        net_payout,
+    )?;
+
+    fees::accrue(
+        &mut ctx.accounts.protocol_fee_vault,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        uses_token_collateral,
+        vault_seeds,
+        protocol_fee,
+    )?;
+    fees::accrue(
+        &mut ctx.accounts.creator_fee_vault,
+        &ctx.accounts.vault,
+        &ctx.accounts.system_program,
+        uses_token_collateral,
+        vault_seeds,
+        creator_fee,
+    )?;
+
+    let return_pct = payout::return_pct(net_payout, cost);
+
+    emit!(WinningsClaimed {
+        market: market.key(),
+        market_id: market.market_id,
+        position: ctx.accounts.position.key(),
+        owner: ctx.accounts.position.owner,
+        amount: cost,
+        payout: net_payout,
+        protocol_fee,
+        creator_fee,
+    });
+
+    msg!("Shares redeemed from Dake Market #{}!", market.market_id);
+    msg!("   Shares: {} (cost {})", payout, cost);
+    msg!("   Payout: {}", net_payout);
+    msg!("   Fees: {} protocol, {} creator", protocol_fee, creator_fee);
+    msg!("   Return: {}%", return_pct);
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Market, Position};
+use crate::collateral;
+use crate::error::DakeError;
+use crate::events::PositionRefunded;
+
+#[derive(Accounts)]
+pub struct RefundPosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        has_one = market @ DakeError::PositionMarketMismatch,
+        constraint = market.is_current(&position) @ DakeError::PositionMarketMismatch,
+        constraint = position.owner == owner.key() @ DakeError::NotOwner,
+    )]
+    pub position: Account<'info, Position>,
+
+    /// CHECK: Vault PDA holding the funds
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        mut,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub owner_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Refund a position of a cancelled market
+///
+/// Returns exactly Position::amount to the owner - the encrypted side is never needed.
+pub fn handler(ctx: Context<RefundPosition>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let position = &mut ctx.accounts.position;
+
+    require!(market.is_cancelled(), DakeError::MarketNotCancelled);
+    require!(!position.claimed, DakeError::AlreadyClaimed);
+
+    let refund = position.amount;
+
+    let token = collateral::token_collateral(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.owner_token_account,
+        &ctx.accounts.owner.key(),
+        &ctx.accounts.token_program,
+    )?;
+
+    let vault_balance = collateral::vault_balance(&ctx.accounts.vault, token.as_ref())?;
+    require!(
+        refund <= market.outstanding_liability() && refund <= vault_balance,
+        DakeError::InsufficientVaultFunds
+    );
+
+    // Mark as refunded
+    position.claimed = true;
+    let market = &mut ctx.accounts.market;
+    market.total_paid_out = market
+        .total_paid_out
+        .checked_add(refund)
+        .ok_or(DakeError::MathOverflow)?;
+
+    // Transfer refund from vault to owner
+    let market_key = market.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"vault",
+        market_key.as_ref(),
+        &[ctx.bumps.vault],
+    ];
+
+    collateral::transfer_from_vault(
+        &ctx.accounts.vault,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.system_program,
+        token,
+        vault_seeds,
+        refund,
+    )?;
+
+    emit!(PositionRefunded {
+        market: market.key(),
+        market_id: market.market_id,
+        position: position.key(),
+        owner: position.owner,
+        amount: refund,
+    });
+
+    msg!("Position refunded from cancelled Dake Market #{}!", market.market_id);
+    msg!("   Owner: {}", position.owner);
+    msg!("   Refund: {}", refund);
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    Market, MarketKind, ResolutionSource, OUTCOME_LONG, OUTCOME_NO, OUTCOME_YES,
+};
+use crate::oracle::{self, PYTH_RECEIVER_ID};
+use crate::error::DakeError;
+use crate::events::MarketResolved;
+
+#[derive(Accounts)]
+pub struct ResolveFromOracle<'info> {
+    /// Anyone can trigger oracle resolution
+    pub resolver: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Pyth PriceUpdateV2 account - must be the market's feed, owned by the Pyth receiver
+    #[account(owner = PYTH_RECEIVER_ID @ DakeError::InvalidOracleAccount)]
+    pub price_feed: AccountInfo<'info>,
+}
+
+/// Resolve a market from its oracle price feed
+///
+/// Permissionless - the outcome comes from the feed, not from the caller:
+/// - Binary markets: YES if the price satisfies the comparator, NO otherwise
+/// - Scalar markets: resolved to the price (clamped to the bounds)
+///
+/// The price must be fully verified, carry the configured feed_id, be published within max_staleness_secs of
+/// evaluation_time, and have a confidence interval within max_confidence_bps.
+pub fn handler(ctx: Context<ResolveFromOracle>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    let ResolutionSource::Oracle(oracle_config) = market.resolution else {
+        return Err(DakeError::NoOracle.into());
+    };
+    require_keys_eq!(
+        ctx.accounts.price_feed.key(),
+        oracle_config.feed,
+        DakeError::InvalidOracleAccount
+    );
+    require!(!market.is_resolved(), DakeError::MarketAlreadyResolved);
+    require!(!market.is_cancelled(), DakeError::MarketCancelled);
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= market.resolution_time && now >= oracle_config.evaluation_time,
+        DakeError::ResolutionTimeNotReached
+    );
+
+    let price = oracle::parse_price_update(&ctx.accounts.price_feed.try_borrow_data()?)?;
+    oracle::validate_price(&oracle_config, &price)?;
+
+    let outcome = match market.kind {
+        MarketKind::Categorical | MarketKind::Lmsr { .. }
+            if oracle::evaluate(&oracle_config, price.price) => OUTCOME_YES,
+        MarketKind::Categorical | MarketKind::Lmsr { .. } => OUTCOME_NO,
+        MarketKind::Scalar { .. } => OUTCOME_LONG,
+    };
+    market.apply_resolution(outcome, price.price)?;
+
+    emit!(MarketResolved {
+        market: market.key(),
+        market_id: market.market_id,
+        winning_outcome: market.winning_outcome,
+        resolved_value: market.resolved_value,
+        outcome_totals: market.outcome_totals.clone(),
+    });
+
+    msg!("Dake Market #{} RESOLVED from oracle!", market.market_id);
+    msg!("   Question: {}", market.question);
+    msg!("   Price: {} (expo {}, conf {})", price.price, price.exponent, price.conf);
+    msg!("   Published at: {}", price.publish_time);
+    msg!("   Outcome: {} wins!", market.outcomes[market.winning_outcome as usize]);
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
-use crate::state::{Market, MarketStatus};
+use crate::state::{Market, ResolutionSource};
 use crate::error::DakeError;
+use crate::events::MarketResolved;
 
 #[derive(Accounts)]
 pub struct ResolveMarket<'info> {
@@ -14,8 +15,8 @@ pub struct ResolveMarket<'info> {
 /// Resolve a market with the outcome
 ///
 /// Parameters:
-/// - outcome: true = YES wins, false = NO wins
-pub fn handler(ctx: Context<ResolveMarket>, outcome: bool) -> Result<()> {
+/// - outcome: index of the winning outcome (binary: 1 = YES wins, 0 = NO wins)
+pub fn handler(ctx: Context<ResolveMarket>, outcome: u8) -> Result<()> {
     let market = &mut ctx.accounts.market;
 
     require!(
@@ -26,24 +27,46 @@ pub fn handler(ctx: Context<ResolveMarket>, outcome: bool) -> Result<()> {
         !market.is_resolved(),
         DakeError::MarketAlreadyResolved
     );
+    require!(!market.is_cancelled(), DakeError::MarketCancelled);
+    require!(
+        market.resolution == ResolutionSource::Authority,
+        DakeError::WrongResolutionSource
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= market.resolution_time,
+        DakeError::ResolutionTimeNotReached
+    );
+
+    require!(!market.is_scalar(), DakeError::WrongMarketKind);
+    require!(
+        (outcome as usize) < market.outcome_count(),
+        DakeError::InvalidOutcome
+    );
 
     // Set the resolution status
-    if outcome {
-        market.status = MarketStatus::ResolvedYes;
-    } else {
-        market.status = MarketStatus::ResolvedNo;
-    }
+    market.apply_resolution(outcome, 0)?;
 
-    let winning_side = if outcome { "YES" } else { "NO" };
-    let winning_pool = if outcome { market.total_yes_amount } else { market.total_no_amount };
-    let losing_pool = if outcome { market.total_no_amount } else { market.total_yes_amount };
+    let winning_side = &market.outcomes[outcome as usize];
+
+    emit!(MarketResolved {
+        market: market.key(),
+        market_id: market.market_id,
+        winning_outcome: market.winning_outcome,
+        resolved_value: market.resolved_value,
+        outcome_totals: market.outcome_totals.clone(),
+    });
 
     msg!("Dake Market #{} RESOLVED!", market.market_id);
     msg!("   Question: {}", market.question);
     msg!("   Outcome: {} wins!", winning_side);
-    msg!("   Winning pool: {} lamports", winning_pool);
-    msg!("   Losing pool (goes to winners): {} lamports", losing_pool);
-    msg!("   Total payout pool: {} lamports", market.total_pool());
+    if market.is_lmsr() {
+        msg!("   Winning shares: {}", market.winning_pool());
+    } else if market.pools_revealed {
+        let winning_pool = market.winning_pool();
+        msg!("   Winning pool: {} lamports", winning_pool);
+        msg!("   Losing pool (goes to winners): {} lamports", market.total_pool() - winning_pool);
+        msg!("   Total payout pool: {} lamports", market.total_pool());
+    }
 
     Ok(())
 }
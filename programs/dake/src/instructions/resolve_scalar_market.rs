@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::{Market, MarketKind, ResolutionSource, OUTCOME_LONG};
+use crate::error::DakeError;
+use crate::events::MarketResolved;
+
+#[derive(Accounts)]
+pub struct ResolveScalarMarket<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+}
+
+/// Resolve a scalar market with the observed value
+///
+/// Parameters:
+/// - value: the observed value; clamped to [lower_bound, upper_bound]
+///
+/// LONG positions share total_pool * (value - lower) / (upper - lower),
+/// SHORT positions share the remainder.
+pub fn handler(ctx: Context<ResolveScalarMarket>, value: i64) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    require!(
+        market.authority == ctx.accounts.authority.key(),
+        DakeError::Unauthorized
+    );
+    require!(
+        !market.is_resolved(),
+        DakeError::MarketAlreadyResolved
+    );
+    require!(!market.is_cancelled(), DakeError::MarketCancelled);
+    require!(
+        market.resolution == ResolutionSource::Authority,
+        DakeError::WrongResolutionSource
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= market.resolution_time,
+        DakeError::ResolutionTimeNotReached
+    );
+
+    let MarketKind::Scalar { lower_bound, upper_bound } = market.kind else {
+        return Err(DakeError::WrongMarketKind.into());
+    };
+
+    // Set the resolution status - check_winner compares positions against LONG
+    market.apply_resolution(OUTCOME_LONG, value)?;
+
+    emit!(MarketResolved {
+        market: market.key(),
+        market_id: market.market_id,
+        winning_outcome: market.winning_outcome,
+        resolved_value: market.resolved_value,
+        outcome_totals: market.outcome_totals.clone(),
+    });
+
+    msg!("Dake Market #{} RESOLVED!", market.market_id);
+    msg!("   Question: {}", market.question);
+    msg!("   Value: {} (range {} to {})", market.resolved_value, lower_bound, upper_bound);
+    if market.pools_revealed {
+        let long_share = market.scalar_long_share();
+        msg!("   LONG share: {} lamports", long_share);
+        msg!("   SHORT share: {} lamports", market.total_pool() - long_share);
+    }
+
+    Ok(())
+}
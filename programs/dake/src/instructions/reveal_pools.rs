@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use inco_lightning::{
+    cpi::{self, accounts::VerifySignature},
+    program::IncoLightning,
+    ID as INCO_LIGHTNING_ID,
+};
+use crate::state::Market;
+use crate::decryption;
+use crate::error::DakeError;
+use crate::events::PoolsRevealed;
+
+#[derive(Accounts)]
+pub struct RevealPools<'info> {
+    /// Anyone holding the attested decryptions
+    #[account(mut)]
+    pub revealer: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    /// CHECK: Instructions sysvar for Ed25519 signature verification
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: AccountInfo<'info>,
+
+    #[account(address = INCO_LIGHTNING_ID)]
+    pub inco_lightning_program: Program<'info, IncoLightning>,
+}
+
+/// Reveal the plaintext pool totals after betting has closed
+///
+/// Permissionless: whoever closed the market (or got access through
+/// grant_pool_access) decrypts every encrypted_totals handle off-chain and
+/// submits the handles + plaintexts, which are verified against the attested
+/// Ed25519 signatures in the same transaction.
+///
+/// Parameters (both in outcome order, encoded as described in crate::decryption):
+/// - handles: encrypted_totals handles
+/// - plaintexts: decrypted pool totals
+pub fn handler(
+    ctx: Context<RevealPools>,
+    handles: Vec<Vec<u8>>,
+    plaintexts: Vec<Vec<u8>>,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    require!(!market.is_open(), DakeError::MarketStillOpen);
+    require!(!market.pools_revealed, DakeError::PoolsAlreadyRevealed);
+
+    let outcome_count = market.outcome_count();
+    require!(
+        handles.len() == outcome_count && plaintexts.len() == outcome_count,
+        DakeError::InvalidRevealData
+    );
+    for (handle, expected) in handles.iter().zip(market.encrypted_totals.iter()) {
+        require!(decryption::parse_handle(handle)? == *expected, DakeError::HandleMismatch);
+    }
+
+    // Verify the decryption signatures on-chain
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.inco_lightning_program.to_account_info(),
+        VerifySignature {
+            instructions: ctx.accounts.instructions.to_account_info(),
+            signer: ctx.accounts.revealer.to_account_info(),
+        },
+    );
+
+    cpi::is_validsignature(
+        cpi_ctx,
+        outcome_count as u8,
+        Some(handles),
+        Some(plaintexts.clone()),
+    )?;
+
+    let totals = plaintexts
+        .iter()
+        .map(|plaintext| decryption::parse_plaintext_u64(plaintext))
+        .collect::<Result<Vec<u64>>>()?;
+
+    // The pools can never hold more than was actually deposited into the vault
+    let revealed_total = totals
+        .iter()
+        .try_fold(0u64, |acc, total| acc.checked_add(*total))
+        .ok_or(DakeError::MathOverflow)?;
+    require!(
+        revealed_total <= market.total_deposited,
+        DakeError::InsufficientVaultFunds
+    );
+
+    market.outcome_totals = totals;
+    market.pools_revealed = true;
+
+    emit!(PoolsRevealed {
+        market: market.key(),
+        market_id: market.market_id,
+        outcome_totals: market.outcome_totals.clone(),
+    });
+
+    msg!("Pools revealed for Dake Market #{}!", market.market_id);
+    msg!("   Pool totals: {:?}", market.outcome_totals);
+
+    Ok(())
+}
+
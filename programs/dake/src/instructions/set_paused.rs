@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::error::DakeError;
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DakeError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Pause or unpause the program (admin only)
+///
+/// While paused, create_market and place_bet are rejected. Resolution, claims
+/// and refunds keep working so funds are never locked.
+pub fn handler(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+    ctx.accounts.config.paused = paused;
+
+    msg!("Dake {}", if paused { "PAUSED" } else { "unpaused" });
+
+    Ok(())
+}
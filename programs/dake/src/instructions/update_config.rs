@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, ConfigParams};
+use crate::error::DakeError;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DakeError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+/// Replace the config settings (admin only)
+///
+/// Fee changes only apply to markets created afterwards.
+pub fn handler(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.apply(params)?;
+
+    msg!("Dake config updated!");
+    msg!("   Protocol fee: {} bps", config.protocol_fee_bps);
+    msg!("   Bet limits: {} - {}", config.min_bet, config.max_bet);
+    msg!("   Allowed mints: {:?}", config.allowed_mints);
+
+    Ok(())
+}
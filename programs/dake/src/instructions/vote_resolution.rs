@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    Market, MarketKind, ResolutionSource, ResolutionVotes, ResolverSet, ResolverVote,
+};
+use crate::error::DakeError;
+use crate::events::{MarketResolved, ResolutionVoted};
+
+#[derive(Accounts)]
+pub struct VoteResolution<'info> {
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+
+    #[account(mut)]
+    pub market: Account<'info, Market>,
+
+    pub resolver_set: Account<'info, ResolverSet>,
+
+    #[account(
+        init_if_needed,
+        payer = resolver,
+        space = ResolutionVotes::SIZE,
+        seeds = [b"votes", market.key().as_ref(), market.created_at.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub votes: Account<'info, ResolutionVotes>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Vote on the outcome of a committee market (resolver set members only)
+///
+/// Parameters:
+/// - outcome: winning outcome index (categorical markets)
+/// - value: observed value (scalar markets, clamped to the bounds)
+///
+/// Voting opens at resolution_time and closes vote_window_secs later. The market
+/// resolves as soon as `threshold` votes match; if the window ends first,
+/// expire_resolution_vote cancels it.
+pub fn handler(ctx: Context<VoteResolution>, outcome: u8, value: i64) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let resolver_set = &ctx.accounts.resolver_set;
+    let resolver = ctx.accounts.resolver.key();
+
+    let ResolutionSource::Committee { resolver_set: set_key, vote_window_secs } = market.resolution
+    else {
+        return Err(DakeError::WrongResolutionSource.into());
+    };
+    require_keys_eq!(resolver_set.key(), set_key, DakeError::InvalidResolverSet);
+    require!(resolver_set.is_member(&resolver), DakeError::NotResolver);
+    require!(!market.is_resolved(), DakeError::MarketAlreadyResolved);
+    require!(!market.is_cancelled(), DakeError::MarketCancelled);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= market.resolution_time,
+        DakeError::ResolutionTimeNotReached
+    );
+    let vote_deadline = market
+        .resolution_time
+        .checked_add(vote_window_secs as i64)
+        .ok_or(DakeError::MathOverflow)?;
+    require!(now < vote_deadline, DakeError::VoteDeadlinePassed);
+
+    // Normalize so matching votes compare equal
+    let (outcome, value) = match market.kind {
+        MarketKind::Categorical | MarketKind::Lmsr { .. } => {
+            require!(
+                (outcome as usize) < market.outcome_count(),
+                DakeError::InvalidOutcome
+            );
+            (outcome, 0)
+        }
+        MarketKind::Scalar { lower_bound, upper_bound } => {
+            (0, value.clamp(lower_bound, upper_bound))
+        }
+    };
+
+    let votes = &mut ctx.accounts.votes;
+    votes.market = market.key();
+    votes.bump = ctx.bumps.votes;
+    require!(!votes.has_voted(&resolver), DakeError::AlreadyVoted);
+    votes.votes.push(ResolverVote {
+        member: resolver,
+        outcome,
+        value,
+    });
+
+    let matching_votes = votes.matching(outcome, value) as u8;
+    let threshold = resolver_set.threshold;
+
+    let market = &mut ctx.accounts.market;
+    emit!(ResolutionVoted {
+        market: market.key(),
+        market_id: market.market_id,
+        member: resolver,
+        outcome,
+        value,
+        matching_votes,
+        threshold,
+    });
+
+    msg!("Resolution vote on Dake Market #{}!", market.market_id);
+    msg!("   Outcome: {} / value: {}", outcome, value);
+    msg!("   Matching votes: {} of {}", matching_votes, threshold);
+
+    if matching_votes >= threshold {
+        market.apply_resolution(outcome, value)?;
+
+        emit!(MarketResolved {
+            market: market.key(),
+            market_id: market.market_id,
+            winning_outcome: market.winning_outcome,
+            resolved_value: market.resolved_value,
+            outcome_totals: market.outcome_totals.clone(),
+        });
+
+        msg!("Dake Market #{} RESOLVED by committee!", market.market_id);
+        msg!("   Outcome: {} wins!", market.outcomes[market.winning_outcome as usize]);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{FeeKind, FeeVault, Market};
+use crate::collateral;
+use crate::fees;
+use crate::error::DakeError;
+use crate::events::FeesWithdrawn;
+
+#[derive(Accounts)]
+pub struct WithdrawCreatorFees<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(has_one = authority @ DakeError::Unauthorized)]
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_fee_vault", market.key().as_ref()],
+        bump = creator_fee_vault.bump,
+        has_one = market @ DakeError::FeeVaultMismatch,
+        constraint = creator_fee_vault.kind == FeeKind::Creator @ DakeError::FeeVaultMismatch,
+    )]
+    pub creator_fee_vault: Account<'info, FeeVault>,
+
+    /// CHECK: Vault PDA holding the funds (signs token fee transfers)
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        mut,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub authority_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Withdraw the creator fees accrued on a market to the market authority
+pub fn handler(ctx: Context<WithdrawCreatorFees>) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    let token = collateral::token_collateral(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.authority_token_account,
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.token_program,
+    )?;
+
+    let market_key = market.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"vault",
+        market_key.as_ref(),
+        &[ctx.bumps.vault],
+    ];
+
+    let amount = fees::withdraw(
+        &mut ctx.accounts.creator_fee_vault,
+        &ctx.accounts.vault,
+        &ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.system_program,
+        token,
+        vault_seeds,
+    )?;
+
+    emit!(FeesWithdrawn {
+        market: market.key(),
+        market_id: market.market_id,
+        kind: FeeKind::Creator,
+        recipient: ctx.accounts.authority.key(),
+        amount,
+    });
+
+    msg!("Creator fees withdrawn from Dake Market #{}!", market.market_id);
+    msg!("   Amount: {}", amount);
+
+    Ok(())
+}
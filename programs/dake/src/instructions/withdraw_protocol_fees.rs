@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use crate::state::{Config, FeeKind, FeeVault, Market};
+use crate::collateral;
+use crate::fees;
+use crate::error::DakeError;
+use crate::events::FeesWithdrawn;
+
+#[derive(Accounts)]
+pub struct WithdrawProtocolFees<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = admin @ DakeError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub market: Account<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [b"protocol_fee_vault", market.key().as_ref()],
+        bump = protocol_fee_vault.bump,
+        has_one = market @ DakeError::FeeVaultMismatch,
+        constraint = protocol_fee_vault.kind == FeeKind::Protocol @ DakeError::FeeVaultMismatch,
+    )]
+    pub protocol_fee_vault: Account<'info, FeeVault>,
+
+    /// CHECK: Vault PDA holding the funds (signs token fee transfers)
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: AccountInfo<'info>,
+
+    /// Collateral mint (omit for native SOL markets)
+    pub collateral_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the vault PDA (token markets only)
+    #[account(
+        mut,
+        seeds = [b"vault_token", market.key().as_ref()],
+        bump
+    )]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub admin_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Withdraw the protocol fees accrued on a market to the config admin
+pub fn handler(ctx: Context<WithdrawProtocolFees>) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    let token = collateral::token_collateral(
+        market,
+        &ctx.accounts.collateral_mint,
+        &ctx.accounts.vault_token_account,
+        &ctx.accounts.admin_token_account,
+        &ctx.accounts.admin.key(),
+        &ctx.accounts.token_program,
+    )?;
+
+    let market_key = market.key();
+    let vault_seeds: &[&[u8]] = &[
+        b"vault",
+        market_key.as_ref(),
+        &[ctx.bumps.vault],
+    ];
+
+    let amount = fees::withdraw(
+        &mut ctx.accounts.protocol_fee_vault,
+        &ctx.accounts.vault,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program,
+        token,
+        vault_seeds,
+    )?;
+
+    emit!(FeesWithdrawn {
+        market: market.key(),
+        market_id: market.market_id,
+        kind: FeeKind::Protocol,
+        recipient: ctx.accounts.admin.key(),
+        amount,
+    });
+
+    msg!("Protocol fees withdrawn from Dake Market #{}!", market.market_id);
+    msg!("   Amount: {}", amount);
+
+    Ok(())
+}
@@ -1,12 +1,23 @@
 #![allow(unexpected_cfgs)]
+#![allow(deprecated)]
+#![allow(clippy::too_many_arguments)]
 
 use anchor_lang::prelude::*;
 
+pub mod collateral;
+pub mod decryption;
 pub mod error;
+pub mod events;
+pub mod fees;
 pub mod instructions;
+pub mod lmsr;
+pub mod oracle;
+pub mod payout;
+pub mod pools;
 pub mod state;
 
 use instructions::*;
+use state::{ConfigParams, MarketKind, ResolutionSource};
 
 declare_id!("5apEYrFFuxT7yExEFz56kfmuYvc1YxcActFCMWnYpQea");
 
@@ -14,40 +25,171 @@ declare_id!("5apEYrFFuxT7yExEFz56kfmuYvc1YxcActFCMWnYpQea");
 pub mod dake {
     use super::*;
 
+    /// Create the program-wide config (upgrade authority only)
+    /// - params: fee rates, bet limits, default liquidity and allowed collateral mints
+    pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+        instructions::initialize_config::handler(ctx, params)
+    }
+
     /// Create a new prediction market
+    /// - outcomes: 2..=16 outcome labels (binary: ["NO", "YES"])
+    /// - kind: Categorical, Scalar with lower/upper bounds (outcomes: ["SHORT", "LONG"]),
+    ///   or Lmsr with liquidity b (the creator funds b * ln(outcomes))
+    /// - initial_liquidity: creator's seed amount per outcome (may be zero or asymmetric)
+    /// - betting_closes_at: bets are rejected from this time on
+    /// - creator_fee_bps: creator fee on winnings, in basis points
+    /// - resolution: Authority, Oracle (price feed), Optimistic (bonded proposals) or Committee (M-of-N)
     pub fn create_market(
         ctx: Context<CreateMarket>,
         market_id: u64,
         question: String,
+        outcomes: Vec<String>,
+        kind: MarketKind,
+        initial_liquidity: Vec<u64>,
         resolution_time: i64,
+        betting_closes_at: i64,
+        creator_fee_bps: u16,
+        resolution: ResolutionSource,
     ) -> Result<()> {
-        instructions::create_market::handler(ctx, market_id, question, resolution_time)
+        instructions::create_market::handler(
+            ctx,
+            market_id,
+            question,
+            outcomes,
+            kind,
+            initial_liquidity,
+            resolution_time,
+            betting_closes_at,
+            creator_fee_bps,
+            resolution,
+        )
     }
 
     /// Place a bet on a prediction market
-    /// - encrypted_side: Encrypted value (1 = YES, 0 = NO)
+    /// - encrypted_side: Encrypted outcome index (binary: 1 = YES, 0 = NO)
     /// - amount: Bet amount in lamports
-    /// - side_for_pool: The side to update pool totals (must match encrypted value)
+    /// - position_nonce: position seed, so one wallet can place several bets per market
+    /// - min_payout: smallest payout (stake included) the bet accepts, else it is refunded
     pub fn place_bet<'info>(
         ctx: Context<'_, '_, '_, 'info, PlaceBet<'info>>,
         encrypted_side: Vec<u8>,
         amount: u64,
-        side_for_pool: u8,
+        position_nonce: u64,
+        min_payout: u64,
+    ) -> Result<()> {
+        instructions::place_bet::handler(ctx, encrypted_side, amount, position_nonce, min_payout)
+    }
+
+    /// Buy shares of an outcome in an LMSR market
+    /// - outcome: outcome index (public)
+    /// - shares: shares to buy, each paying one base unit if the outcome wins
+    /// - max_cost: fail if the trade costs more than this
+    /// - position_nonce: position seed, so one wallet can hold several positions per market
+    pub fn buy_shares(
+        ctx: Context<BuyShares>,
+        outcome: u8,
+        shares: u64,
+        max_cost: u64,
+        position_nonce: u64,
     ) -> Result<()> {
-        instructions::place_bet::handler(ctx, encrypted_side, amount, side_for_pool)
+        instructions::buy_shares::handler(ctx, outcome, shares, max_cost, position_nonce)
     }
 
-    /// Close a market for betting
-    pub fn close_market(ctx: Context<CloseMarket>) -> Result<()> {
+    /// Close a market for betting (anyone, once betting has closed)
+    pub fn close_market<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseMarket<'info>>,
+    ) -> Result<()> {
         instructions::close_market::handler(ctx)
     }
 
+    /// Reveal the decrypted pool totals (anyone, after close_market)
+    pub fn reveal_pools(
+        ctx: Context<RevealPools>,
+        handles: Vec<Vec<u8>>,
+        plaintexts: Vec<Vec<u8>>,
+    ) -> Result<()> {
+        instructions::reveal_pools::handler(ctx, handles, plaintexts)
+    }
+
+    /// Grant the caller decrypt access to a closed market's pool totals
+    pub fn grant_pool_access<'info>(
+        ctx: Context<'_, '_, '_, 'info, GrantPoolAccess<'info>>,
+    ) -> Result<()> {
+        instructions::grant_pool_access::handler(ctx)
+    }
+
     /// Resolve a market with the outcome
-    /// - outcome: true = YES wins, false = NO wins
-    pub fn resolve_market(ctx: Context<ResolveMarket>, outcome: bool) -> Result<()> {
+    /// - outcome: winning outcome index (binary: 1 = YES wins, 0 = NO wins)
+    pub fn resolve_market(ctx: Context<ResolveMarket>, outcome: u8) -> Result<()> {
         instructions::resolve_market::handler(ctx, outcome)
     }
 
+    /// Resolve a scalar market with the observed value
+    /// - value: clamped to the market's bounds; payouts are interpolated linearly
+    pub fn resolve_scalar_market(ctx: Context<ResolveScalarMarket>, value: i64) -> Result<()> {
+        instructions::resolve_scalar_market::handler(ctx, value)
+    }
+
+    /// Resolve an oracle market from its price feed (permissionless)
+    pub fn resolve_from_oracle(ctx: Context<ResolveFromOracle>) -> Result<()> {
+        instructions::resolve_from_oracle::handler(ctx)
+    }
+
+    /// Propose the outcome of an optimistic market, posting the resolution bond
+    /// - outcome: winning outcome index (categorical); value: observed value (scalar)
+    pub fn propose_resolution(
+        ctx: Context<ProposeResolution>,
+        outcome: u8,
+        value: i64,
+    ) -> Result<()> {
+        instructions::propose_resolution::handler(ctx, outcome, value)
+    }
+
+    /// Dispute a pending resolution by matching the bond
+    pub fn dispute_resolution(ctx: Context<DisputeResolution>) -> Result<()> {
+        instructions::dispute_resolution::handler(ctx)
+    }
+
+    /// Finalize an undisputed resolution after the dispute window (permissionless)
+    pub fn finalize_resolution(ctx: Context<FinalizeResolution>) -> Result<()> {
+        instructions::finalize_resolution::handler(ctx)
+    }
+
+    /// Rule on a disputed resolution (config admin) - the correct side gets both bonds
+    pub fn arbitrate_resolution(
+        ctx: Context<ArbitrateResolution>,
+        outcome: u8,
+        value: i64,
+    ) -> Result<()> {
+        instructions::arbitrate_resolution::handler(ctx, outcome, value)
+    }
+
+    /// Cancel an optimistic market whose dispute was not arbitrated in time (permissionless)
+    pub fn expire_arbitration(ctx: Context<ExpireArbitration>) -> Result<()> {
+        instructions::expire_arbitration::handler(ctx)
+    }
+
+    /// Create an M-of-N resolver committee
+    /// - members: committee members; threshold: matching votes needed to resolve
+    pub fn create_resolver_set(
+        ctx: Context<CreateResolverSet>,
+        set_id: u64,
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::create_resolver_set::handler(ctx, set_id, members, threshold)
+    }
+
+    /// Vote on a committee market's outcome - resolves once the threshold is reached
+    pub fn vote_resolution(ctx: Context<VoteResolution>, outcome: u8, value: i64) -> Result<()> {
+        instructions::vote_resolution::handler(ctx, outcome, value)
+    }
+
+    /// Cancel a committee market whose vote missed the deadline (permissionless)
+    pub fn expire_resolution_vote(ctx: Context<ExpireResolutionVote>) -> Result<()> {
+        instructions::expire_resolution_vote::handler(ctx)
+    }
+
     /// Check if a position is a winner (encrypted comparison)
     pub fn check_winner<'info>(
         ctx: Context<'_, '_, '_, 'info, CheckWinner<'info>>,
@@ -64,10 +206,72 @@ pub mod dake {
         instructions::claim_winnings::handler(ctx, handle, plaintext)
     }
 
+    /// Redeem the winning shares of an LMSR position
+    pub fn redeem_shares(ctx: Context<RedeemShares>) -> Result<()> {
+        instructions::redeem_shares::handler(ctx)
+    }
+
     /// Grant decrypt access for is_winner_handle (call after check_winner)
     pub fn grant_decrypt_access<'info>(
         ctx: Context<'_, '_, '_, 'info, GrantDecryptAccess<'info>>,
     ) -> Result<()> {
         instructions::grant_decrypt_access::handler(ctx)
     }
+
+    /// Cancel (void) a market - returns the creator's seed liquidity or LMSR subsidy
+    pub fn cancel_market(ctx: Context<CancelMarket>) -> Result<()> {
+        instructions::cancel_market::handler(ctx)
+    }
+
+    /// Refund a position's bet amount from a cancelled market
+    pub fn refund_position(ctx: Context<RefundPosition>) -> Result<()> {
+        instructions::refund_position::handler(ctx)
+    }
+
+    /// Exit a position while the market is open - refunds the stake minus the exit fee
+    pub fn exit_position<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExitPosition<'info>>,
+    ) -> Result<()> {
+        instructions::exit_position::handler(ctx)
+    }
+
+    /// Withdraw a market's accrued protocol fees (config admin only)
+    pub fn withdraw_protocol_fees(ctx: Context<WithdrawProtocolFees>) -> Result<()> {
+        instructions::withdraw_protocol_fees::handler(ctx)
+    }
+
+    /// Withdraw a market's accrued creator fees (market authority only)
+    pub fn withdraw_creator_fees(ctx: Context<WithdrawCreatorFees>) -> Result<()> {
+        instructions::withdraw_creator_fees::handler(ctx)
+    }
+
+    /// Replace the config settings (admin only)
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        instructions::update_config::handler(ctx, params)
+    }
+
+    /// Pause or unpause market creation and betting (admin only)
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        instructions::set_paused::handler(ctx, paused)
+    }
+
+    /// Propose a new admin (admin only)
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::propose_admin::handler(ctx, new_admin)
+    }
+
+    /// Accept the admin role (pending admin only)
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    /// Close a claimed / refunded (or resolved) position and reclaim its rent
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        instructions::close_position::handler(ctx)
+    }
+
+    /// Close a settled market, sweeping the vault to the creator and reclaiming rent
+    pub fn finalize_market(ctx: Context<FinalizeMarket>) -> Result<()> {
+        instructions::finalize_market::handler(ctx)
+    }
 }
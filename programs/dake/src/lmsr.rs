@@ -0,0 +1,213 @@
+//! LMSR (logarithmic market scoring rule) pricing
+//!
+//! Pure fixed-point functions behind buy_shares. With liquidity `b` and q[i]
+//! shares of outcome i outstanding, the market maker's cost function is
+//!   C(q) = b * ln(sum_i exp(q[i] / b))
+//! and buying `shares` of outcome k costs C(q + shares * e_k) - C(q).
+//!
+//! Invariants, checked in isolation below:
+//! - C(q) >= max_i q[i], so a market maker funded with C(0) = b * ln(n) and paid
+//!   every trade's cost can always pay one base unit per winning share: the
+//!   creator's loss is bounded by max_loss
+//! - Costs are monotonic in the number of shares
+//!
+//! Intermediate values are fixed-point with 18 decimals (WAD); every amount a
+//! trader pays is rounded up.
+
+/// Fixed-point one
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+/// ln(2) in WAD
+const LN_2: u128 = 693_147_180_559_945_309;
+/// exp(-x) rounds to zero past x = 42
+const EXP_CUTOFF: u128 = 42 * WAD;
+
+/// exp(-x) for x >= 0, both in WAD
+///
+/// x = k * ln(2) + r with 0 <= r < ln(2), so exp(-x) = exp(-r) / 2^k, and
+/// exp(-r) is summed from its Taylor series.
+fn exp_neg(x: u128) -> u128 {
+    if x >= EXP_CUTOFF {
+        return 0;
+    }
+    let k = x / LN_2;
+    let r = x - k * LN_2;
+
+    let (mut positive, mut negative) = (WAD, 0u128);
+    let mut term = WAD;
+    let mut n = 1u128;
+    while term > 0 {
+        term = term * r / (n * WAD);
+        if n % 2 == 1 {
+            negative += term;
+        } else {
+            positive += term;
+        }
+        n += 1;
+    }
+    (positive - negative) >> k
+}
+
+/// ln(x) for x >= 1, both in WAD
+///
+/// x = 2^k * y with 1 <= y < 2, so ln(x) = k * ln(2) + ln(y), and
+/// ln(y) = 2 * atanh((y - 1) / (y + 1)) is summed from its series.
+fn ln(x: u128) -> u128 {
+    debug_assert!(x >= WAD);
+    let k = (x / WAD).ilog2();
+    let y = x >> k;
+
+    let z = (y - WAD) * WAD / (y + WAD);
+    let z_squared = z * z / WAD;
+    let (mut sum, mut term) = (z, z);
+    let mut n = 1u128;
+    while term > 0 {
+        term = term * z_squared / WAD;
+        n += 2;
+        sum += term / n;
+    }
+    2 * sum + k as u128 * LN_2
+}
+
+/// exp((q[i] - max q) / b) per outcome, in WAD; the largest is exactly WAD
+fn weights(quantities: &[u64], liquidity: u64) -> Option<(u64, Vec<u128>)> {
+    if liquidity == 0 {
+        return None;
+    }
+    let max = quantities.iter().copied().max()?;
+    let weights = quantities
+        .iter()
+        .map(|quantity| exp_neg((max - quantity) as u128 * WAD / liquidity as u128))
+        .collect();
+    Some((max, weights))
+}
+
+/// C(q) * WAD, computed as max q + b * ln(sum_i exp((q[i] - max q) / b))
+/// so no exponent is ever positive
+fn cost_wad(quantities: &[u64], liquidity: u64) -> Option<u128> {
+    let (max, weights) = weights(quantities, liquidity)?;
+    let sum = weights.iter().try_fold(0u128, |acc, weight| acc.checked_add(*weight))?;
+    (max as u128)
+        .checked_mul(WAD)?
+        .checked_add((liquidity as u128).checked_mul(ln(sum))?)
+}
+
+/// Cost function C(q), rounded up to a base unit
+///
+/// None if there are no outcomes or the liquidity is zero.
+pub fn cost(quantities: &[u64], liquidity: u64) -> Option<u64> {
+    u64::try_from(cost_wad(quantities, liquidity)?.div_ceil(WAD)).ok()
+}
+
+/// Subsidy a creator funds at create_market, and the most it can lose:
+/// C(0) = b * ln(outcome_count), rounded up
+pub fn max_loss(liquidity: u64, outcome_count: usize) -> Option<u64> {
+    cost(&vec![0; outcome_count], liquidity)
+}
+
+/// Price of buying `shares` of `outcome` at `quantities`, rounded up
+///
+/// None on an out-of-range outcome, zero liquidity or overflow.
+pub fn buy_cost(quantities: &[u64], liquidity: u64, outcome: usize, shares: u64) -> Option<u64> {
+    let mut after = quantities.to_vec();
+    let bought = after.get_mut(outcome)?;
+    *bought = bought.checked_add(shares)?;
+
+    let before = cost_wad(quantities, liquidity)?;
+    let after = cost_wad(&after, liquidity)?;
+    u64::try_from(after.saturating_sub(before).div_ceil(WAD)).ok()
+}
+
+/// Instantaneous price of every outcome in basis points: exp(q[i] / b) / sum_j exp(q[j] / b)
+///
+/// Rounded down, so the prices add up to at most 10_000.
+pub fn prices_bps(quantities: &[u64], liquidity: u64) -> Option<Vec<u64>> {
+    let (_, weights) = weights(quantities, liquidity)?;
+    let sum = weights.iter().sum::<u128>();
+    Some(
+        weights
+            .iter()
+            .map(|weight| (weight * crate::state::BPS_DENOMINATOR as u128 / sum) as u64)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Up to 1e12 base units - a thousand SOL - per liquidity or trade
+    const MAX_AMOUNT: u64 = 1_000_000_000_000;
+
+    fn close_to(actual: u128, expected: u128) -> bool {
+        actual.abs_diff(expected) <= 16
+    }
+
+    #[test]
+    fn computes_exp_and_ln() {
+        assert_eq!(exp_neg(0), WAD);
+        assert!(close_to(exp_neg(WAD), 367_879_441_171_442_321));
+        assert!(close_to(exp_neg(10 * WAD), 45_399_929_762_484));
+        assert_eq!(exp_neg(EXP_CUTOFF), 0);
+        assert_eq!(ln(WAD), 0);
+        assert!(close_to(ln(2 * WAD), LN_2));
+        assert!(close_to(ln(3 * WAD), 1_098_612_288_668_109_691));
+        assert!(close_to(ln(16 * WAD), 2_772_588_722_239_781_237));
+    }
+
+    #[test]
+    fn prices_a_fresh_market() {
+        // b * ln(2) for 1 SOL of liquidity
+        assert_eq!(max_loss(1_000_000_000, 2), Some(693_147_181));
+        assert_eq!(max_loss(0, 2), None);
+        assert_eq!(prices_bps(&[0, 0], 1_000_000_000), Some(vec![5_000, 5_000]));
+        assert_eq!(prices_bps(&[0, 0, 0, 0], 1_000_000_000), Some(vec![2_500; 4]));
+
+        // Buying b * ln(3) YES shares moves YES from 1/2 to 3/4
+        let cost = buy_cost(&[0, 0], 1_000_000_000, 1, 1_098_612_289).unwrap();
+        assert_eq!(cost, 693_147_181);
+        assert_eq!(prices_bps(&[0, 1_098_612_289], 1_000_000_000), Some(vec![2_499, 7_500]));
+        assert_eq!(buy_cost(&[0, 0], 1_000_000_000, 2, 1), None);
+        assert_eq!(buy_cost(&[0, u64::MAX], 1_000_000_000, 1, 1), None);
+    }
+
+    proptest! {
+        #[test]
+        fn the_market_maker_can_always_pay_the_winners(
+            liquidity in 1..MAX_AMOUNT,
+            trades in prop::collection::vec((0..4usize, 1..MAX_AMOUNT), 1..24),
+        ) {
+            let mut quantities = vec![0u64; 4];
+            let mut collected = max_loss(liquidity, 4).unwrap() as u128;
+            for (outcome, shares) in trades {
+                collected += buy_cost(&quantities, liquidity, outcome, shares).unwrap() as u128;
+                quantities[outcome] += shares;
+            }
+            let max_liability = quantities.iter().copied().max().unwrap() as u128;
+            prop_assert!(collected >= max_liability);
+        }
+
+        #[test]
+        fn cost_is_monotonic_in_the_shares(
+            liquidity in 1..MAX_AMOUNT,
+            quantities in prop::collection::vec(0..MAX_AMOUNT, 2..16),
+            smaller in 0..MAX_AMOUNT,
+            extra in 0..MAX_AMOUNT,
+        ) {
+            let cheaper = buy_cost(&quantities, liquidity, 0, smaller).unwrap();
+            let dearer = buy_cost(&quantities, liquidity, 0, smaller + extra).unwrap();
+            prop_assert!(cheaper <= dearer);
+            // A share never costs more than the base unit it pays out
+            prop_assert!(dearer <= smaller + extra + 1);
+        }
+
+        #[test]
+        fn prices_never_exceed_one(
+            liquidity in 1..MAX_AMOUNT,
+            quantities in prop::collection::vec(0..MAX_AMOUNT, 2..16),
+        ) {
+            let prices = prices_bps(&quantities, liquidity).unwrap();
+            prop_assert!(prices.iter().sum::<u64>() <= 10_000);
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+use crate::state::{OracleComparator, OracleConfig, BPS_DENOMINATOR};
+use crate::error::DakeError;
+
+/// Pyth pull-oracle receiver program (owner of PriceUpdateV2 accounts)
+pub const PYTH_RECEIVER_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Anchor discriminator of PriceUpdateV2: sha256("account:PriceUpdateV2")[..8]
+pub const PRICE_UPDATE_V2_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+
+/// Price read from a PriceUpdateV2 account
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+/// Parse a Pyth PriceUpdateV2 account
+///
+/// Layout: discriminator (8) | write_authority (32) | verification_level (1, +1 for Partial)
+/// | feed_id (32) | price (i64) | conf (u64) | exponent (i32) | publish_time (i64) | ...
+///
+/// Only fully verified updates are accepted.
+pub fn parse_price_update(data: &[u8]) -> Result<OraclePrice> {
+    require!(
+        data.len() >= 8 && data[..8] == PRICE_UPDATE_V2_DISCRIMINATOR,
+        DakeError::InvalidOracleAccount
+    );
+
+    // VerificationLevel: 0 = Partial { num_signatures: u8 }, 1 = Full
+    let level_offset = 8 + 32;
+    require!(
+        data.get(level_offset) == Some(&1),
+        DakeError::InvalidOracleAccount
+    );
+
+    let mut offset = level_offset + 1;
+    let mut take = |len: usize| -> Result<&[u8]> {
+        let bytes = data
+            .get(offset..offset + len)
+            .ok_or(DakeError::InvalidOracleAccount)?;
+        offset += len;
+        Ok(bytes)
+    };
+
+    let feed_id: [u8; 32] = take(32)?.try_into().unwrap();
+    let price = i64::from_le_bytes(take(8)?.try_into().unwrap());
+    let conf = u64::from_le_bytes(take(8)?.try_into().unwrap());
+    let exponent = i32::from_le_bytes(take(4)?.try_into().unwrap());
+    let publish_time = i64::from_le_bytes(take(8)?.try_into().unwrap());
+
+    Ok(OraclePrice {
+        feed_id,
+        price,
+        conf,
+        exponent,
+        publish_time,
+    })
+}
+
+/// Check a price against the market's oracle limits
+///
+/// - The update must be for the configured feed_id
+/// - The exponent must match the one the thresholds are expressed in
+/// - publish_time must be within max_staleness_secs of evaluation_time
+/// - conf must be at most max_confidence_bps of |price|
+pub fn validate_price(oracle: &OracleConfig, price: &OraclePrice) -> Result<()> {
+    require!(price.feed_id == oracle.feed_id, DakeError::InvalidOracleAccount);
+    require!(
+        price.exponent == oracle.exponent,
+        DakeError::OracleExponentMismatch
+    );
+    require!(
+        (price.publish_time - oracle.evaluation_time).unsigned_abs()
+            <= oracle.max_staleness_secs as u64,
+        DakeError::StaleOraclePrice
+    );
+    require!(
+        (price.conf as u128) * (BPS_DENOMINATOR as u128)
+            <= (price.price.unsigned_abs() as u128) * (oracle.max_confidence_bps as u128),
+        DakeError::OracleConfidenceTooWide
+    );
+    Ok(())
+}
+
+/// Whether the price satisfies the market's comparator (i.e. YES wins)
+pub fn evaluate(oracle: &OracleConfig, price: i64) -> bool {
+    match oracle.comparator {
+        OracleComparator::GreaterThan => price > oracle.threshold,
+        OracleComparator::LessThan => price < oracle.threshold,
+        OracleComparator::Between => {
+            oracle.threshold <= price && price <= oracle.upper_threshold
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EVALUATION_TIME: i64 = 1_700_000_000;
+
+    fn oracle(comparator: OracleComparator) -> OracleConfig {
+        OracleConfig {
+            feed: Pubkey::new_unique(),
+            feed_id: [7u8; 32],
+            comparator,
+            threshold: 150_00000000,
+            upper_threshold: 200_00000000,
+            exponent: -8,
+            evaluation_time: EVALUATION_TIME,
+            max_staleness_secs: 60,
+            max_confidence_bps: 100,
+        }
+    }
+
+    /// Mock PriceUpdateV2 account data
+    fn price_update(level: &[u8], price: i64, conf: u64, exponent: i32, publish_time: i64) -> Vec<u8> {
+        let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(level);
+        data.extend_from_slice(&[7u8; 32]);
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&conf.to_le_bytes());
+        data.extend_from_slice(&exponent.to_le_bytes());
+        data.extend_from_slice(&publish_time.to_le_bytes());
+        data.extend_from_slice(&(publish_time - 1).to_le_bytes());
+        data.extend_from_slice(&price.to_le_bytes());
+        data.extend_from_slice(&conf.to_le_bytes());
+        data.extend_from_slice(&42u64.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn parses_fully_verified_update() {
+        let data = price_update(&[1], 175_00000000, 5_000000, -8, EVALUATION_TIME);
+        let price = parse_price_update(&data).unwrap();
+        assert_eq!(price.feed_id, [7u8; 32]);
+        assert_eq!(price.price, 175_00000000);
+        assert_eq!(price.conf, 5_000000);
+        assert_eq!(price.exponent, -8);
+        assert_eq!(price.publish_time, EVALUATION_TIME);
+    }
+
+    #[test]
+    fn rejects_partial_verification_and_bad_data() {
+        let partial = price_update(&[0, 3], 175_00000000, 0, -8, EVALUATION_TIME);
+        assert!(parse_price_update(&partial).is_err());
+
+        let mut wrong_discriminator = price_update(&[1], 175_00000000, 0, -8, EVALUATION_TIME);
+        wrong_discriminator[0] ^= 0xff;
+        assert!(parse_price_update(&wrong_discriminator).is_err());
+
+        let truncated = &price_update(&[1], 175_00000000, 0, -8, EVALUATION_TIME)[..60];
+        assert!(parse_price_update(truncated).is_err());
+    }
+
+    #[test]
+    fn enforces_feed_staleness_confidence_and_exponent() {
+        let oracle = oracle(OracleComparator::GreaterThan);
+        let price = |conf: u64, exponent: i32, publish_time: i64| OraclePrice {
+            feed_id: [7u8; 32],
+            price: 175_00000000,
+            conf,
+            exponent,
+            publish_time,
+        };
+
+        assert!(validate_price(&oracle, &price(1_00000000, -8, EVALUATION_TIME + 60)).is_ok());
+        assert!(validate_price(&oracle, &price(1_00000000, -8, EVALUATION_TIME - 61)).is_err());
+        assert!(validate_price(&oracle, &price(1_00000000, -8, EVALUATION_TIME + 61)).is_err());
+        // 1% of 175 = 1.75
+        assert!(validate_price(&oracle, &price(1_75000000, -8, EVALUATION_TIME)).is_ok());
+        assert!(validate_price(&oracle, &price(1_75000001, -8, EVALUATION_TIME)).is_err());
+        assert!(validate_price(&oracle, &price(0, -6, EVALUATION_TIME)).is_err());
+
+        let other_feed = OraclePrice {
+            feed_id: [8u8; 32],
+            ..price(1_00000000, -8, EVALUATION_TIME)
+        };
+        assert!(validate_price(&oracle, &other_feed).is_err());
+    }
+
+    #[test]
+    fn evaluates_comparators() {
+        let gt = oracle(OracleComparator::GreaterThan);
+        assert!(evaluate(&gt, 150_00000001));
+        assert!(!evaluate(&gt, 150_00000000));
+
+        let lt = oracle(OracleComparator::LessThan);
+        assert!(evaluate(&lt, 149_99999999));
+        assert!(!evaluate(&lt, 150_00000000));
+
+        let between = oracle(OracleComparator::Between);
+        assert!(evaluate(&between, 150_00000000));
+        assert!(evaluate(&between, 200_00000000));
+        assert!(!evaluate(&between, 200_00000001));
+        assert!(!evaluate(&between, 149_99999999));
+    }
+}
@@ -0,0 +1,174 @@
+//! Parimutuel payout math
+//!
+//! Pure integer functions behind claim_winnings, kept free of accounts so the
+//! invariants can be checked in isolation:
+//! - A position's payout is floor(amount * share / pool), so the payouts of a
+//!   side never add up to more than that side's share of the total pool
+//! - A categorical winner never gets back less than it bet
+//! - Payouts are monotonic in the bet amount
+
+use crate::state::BPS_DENOMINATOR;
+
+/// Share of `share` owed to `amount` out of `pool`: floor(amount * share / pool)
+///
+/// None if the pool is empty or the result does not fit in a u64 (only possible
+/// when amount > pool, i.e. the position is not part of the pool).
+pub fn proportional(amount: u64, share: u64, pool: u64) -> Option<u64> {
+    if pool == 0 {
+        return None;
+    }
+    let payout = (amount as u128) * (share as u128) / (pool as u128);
+    u64::try_from(payout).ok()
+}
+
+/// Gross payout of a winning categorical position: its share of the total pool
+pub fn categorical(amount: u64, total_pool: u64, winning_pool: u64) -> Option<u64> {
+    proportional(amount, total_pool, winning_pool)
+}
+
+/// Portion of `total_pool` owed to LONG in a scalar market resolved to `value`
+///
+/// long_share = total_pool * (value - lower_bound) / (upper_bound - lower_bound),
+/// with `value` clamped to the bounds; SHORT is owed the rest.
+pub fn scalar_long_share(total_pool: u64, lower_bound: i64, upper_bound: i64, value: i64) -> u64 {
+    if upper_bound <= lower_bound {
+        return 0;
+    }
+    let range = (upper_bound as i128) - (lower_bound as i128);
+    let position = (value.clamp(lower_bound, upper_bound) as i128) - (lower_bound as i128);
+    ((total_pool as i128) * position / range) as u64
+}
+
+/// `bps` basis points of `amount`, rounded down
+pub fn fee(amount: u64, bps: u16) -> u64 {
+    ((amount as u128) * (bps as u128) / (BPS_DENOMINATOR as u128)) as u64
+}
+
+/// Return on a bet in percent (100 = break-even), rounded down
+pub fn return_pct(net_payout: u64, amount: u64) -> u64 {
+    if amount == 0 {
+        return 100;
+    }
+    ((net_payout as u128) * 100 / (amount as u128)).min(u64::MAX as u128) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Up to 1e17 base units per bet - a hundred million SOL, far beyond any real
+    /// pool, while the sums below still fit in a u64
+    const MAX_BET: u64 = 100_000_000_000_000_000;
+
+    #[test]
+    fn pays_the_proportional_share() {
+        // 100 of a 1000 winning pool takes 1/10 of the 2000 total
+        assert_eq!(categorical(100, 2_000, 1_000), Some(200));
+        assert_eq!(categorical(1, 3, 2), Some(1));
+        assert_eq!(categorical(100, 2_000, 0), None);
+        assert_eq!(proportional(u64::MAX, u64::MAX, 1), None);
+        assert_eq!(proportional(u64::MAX, u64::MAX, u64::MAX), Some(u64::MAX));
+    }
+
+    #[test]
+    fn splits_scalar_pools_by_value() {
+        assert_eq!(scalar_long_share(400, 0, 100, 75), 300);
+        assert_eq!(scalar_long_share(400, 0, 100, 150), 400);
+        assert_eq!(scalar_long_share(400, 0, 100, -5), 0);
+        assert_eq!(scalar_long_share(400, i64::MIN, i64::MAX, i64::MAX), 400);
+        assert_eq!(scalar_long_share(400, 10, 10, 10), 0);
+    }
+
+    #[test]
+    fn computes_fees_and_returns() {
+        assert_eq!(fee(1_000_000, 100), 10_000);
+        assert_eq!(fee(u64::MAX, 10_000), u64::MAX);
+        assert_eq!(return_pct(250, 100), 250);
+        assert_eq!(return_pct(99, 100), 99);
+        assert_eq!(return_pct(u64::MAX, 1), u64::MAX);
+        assert_eq!(return_pct(5, 0), 100);
+    }
+
+    proptest! {
+        #[test]
+        fn winners_never_take_more_than_the_pool(
+            winners in prop::collection::vec(1..MAX_BET, 1..32),
+            winning_seed in 0..MAX_BET,
+            losing_pool in 0..MAX_BET,
+        ) {
+            let winning_pool = winners.iter().sum::<u64>() + winning_seed;
+            let total_pool = winning_pool + losing_pool;
+
+            let paid = winners
+                .iter()
+                .map(|amount| categorical(*amount, total_pool, winning_pool).unwrap() as u128)
+                .sum::<u128>();
+            prop_assert!(paid <= total_pool as u128);
+        }
+
+        #[test]
+        fn winners_get_at_least_their_bet(
+            amount in 1..MAX_BET,
+            others in 0..MAX_BET,
+            losing_pool in 0..MAX_BET,
+        ) {
+            let winning_pool = amount + others;
+            let payout = categorical(amount, winning_pool + losing_pool, winning_pool).unwrap();
+            prop_assert!(payout >= amount);
+        }
+
+        #[test]
+        fn pool_members_never_overflow(share in any::<u64>(), pool in 1..=u64::MAX, amount in any::<u64>()) {
+            let amount = amount % pool + 1;
+            let payout = proportional(amount, share, pool);
+            prop_assert!(payout.is_some_and(|payout| payout <= share));
+        }
+
+        #[test]
+        fn payout_is_monotonic_in_the_bet(
+            smaller in 0..MAX_BET,
+            extra in 0..MAX_BET,
+            others in 0..MAX_BET,
+            losing_pool in 0..MAX_BET,
+        ) {
+            let larger = smaller + extra;
+            let winning_pool = larger + others;
+            let total_pool = winning_pool + losing_pool;
+            prop_assert!(
+                categorical(smaller, total_pool, winning_pool) <= categorical(larger, total_pool, winning_pool)
+            );
+        }
+
+        #[test]
+        fn scalar_sides_never_take_more_than_the_pool(
+            long_bets in prop::collection::vec(1..MAX_BET, 1..16),
+            short_bets in prop::collection::vec(1..MAX_BET, 1..16),
+            lower_bound in any::<i64>(),
+            upper_bound in any::<i64>(),
+            value in any::<i64>(),
+        ) {
+            let long_pool = long_bets.iter().sum::<u64>();
+            let short_pool = short_bets.iter().sum::<u64>();
+            let total_pool = long_pool + short_pool;
+            let long_share = scalar_long_share(total_pool, lower_bound, upper_bound, value);
+            prop_assert!(long_share <= total_pool);
+
+            let paid = |bets: &[u64], share: u64, pool: u64| {
+                bets.iter()
+                    .map(|amount| proportional(*amount, share, pool).unwrap() as u128)
+                    .sum::<u128>()
+            };
+            let long_paid = paid(&long_bets, long_share, long_pool);
+            let short_paid = paid(&short_bets, total_pool - long_share, short_pool);
+            prop_assert!(long_paid <= long_share as u128);
+            prop_assert!(short_paid <= (total_pool - long_share) as u128);
+        }
+
+        #[test]
+        fn fees_never_exceed_the_payout(payout in any::<u64>(), protocol_bps in 0..=1_000u16, creator_bps in 0..=1_000u16) {
+            let fees = fee(payout, protocol_bps) as u128 + fee(payout, creator_bps) as u128;
+            prop_assert!(fees <= payout as u128);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use anchor_lang::prelude::*;
+use inco_lightning::{
+    cpi::{self, accounts::{Allow, Operation}},
+    types::{Ebool, Euint128},
+};
+use crate::state::UNFILLED_SIDE;
+
+/// Direction of an encrypted pool update
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PoolUpdate {
+    Add,
+    Remove,
+}
+
+/// Move `amount` into (or out of) the encrypted pool of the position's outcome
+/// without revealing which outcome it is. For every outcome i:
+/// - totals[i] += select(side == i, amount, 0)   (or -= for PoolUpdate::Remove)
+///
+/// An out-of-range side matches no outcome and leaves every pool unchanged.
+pub fn update_encrypted_pools<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    totals: &mut [u128],
+    side: Euint128,
+    amount: u64,
+    update: PoolUpdate,
+) -> Result<()> {
+    let operation = || {
+        CpiContext::new(
+            inco.clone(),
+            Operation {
+                signer: signer.clone(),
+            },
+        )
+    };
+
+    let amount_encrypted = cpi::as_euint128(operation(), amount as u128)?;
+    let zero = cpi::as_euint128(operation(), 0)?;
+
+    for (index, total) in totals.iter_mut().enumerate() {
+        let outcome_value = if index == 0 {
+            zero
+        } else {
+            cpi::as_euint128(operation(), index as u128)?
+        };
+        let is_outcome: Ebool = cpi::e_eq(operation(), side, outcome_value, 0)?;
+        let delta = cpi::e_select(operation(), is_outcome, amount_encrypted, zero, 0)?;
+        *total = match update {
+            PoolUpdate::Add => cpi::e_add(operation(), Euint128(*total), delta, 0)?.0,
+            PoolUpdate::Remove => cpi::e_sub(operation(), Euint128(*total), delta, 0)?.0,
+        };
+    }
+
+    Ok(())
+}
+
+/// Apply a bet's min_payout guard to its encrypted side, before the bet is added
+///
+/// The bet is filled if its payout at the pools it lands on reaches min_payout:
+/// - amount * total_after >= min_payout * own_pool_after
+///
+/// where own_pool_after is the pool of the bet's outcome and total_after the sum of
+/// every pool once the bet is added. Both stay encrypted. The returned side is the
+/// bet's own if it is filled and UNFILLED_SIDE otherwise, which adds to no pool.
+pub fn guard_min_payout<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    totals: &[u128],
+    side: Euint128,
+    amount: u64,
+    min_payout: u64,
+) -> Result<Euint128> {
+    let operation = || {
+        CpiContext::new(
+            inco.clone(),
+            Operation {
+                signer: signer.clone(),
+            },
+        )
+    };
+
+    let amount_encrypted = cpi::as_euint128(operation(), amount as u128)?;
+    let zero = cpi::as_euint128(operation(), 0)?;
+
+    let (mut own_pool, mut total) = (zero, zero);
+    for (index, pool) in totals.iter().copied().enumerate() {
+        let outcome_value = if index == 0 {
+            zero
+        } else {
+            cpi::as_euint128(operation(), index as u128)?
+        };
+        let is_outcome: Ebool = cpi::e_eq(operation(), side, outcome_value, 0)?;
+        let own = cpi::e_select(operation(), is_outcome, Euint128(pool), zero, 0)?;
+        own_pool = cpi::e_add(operation(), own_pool, own, 0)?;
+        total = cpi::e_add(operation(), total, Euint128(pool), 0)?;
+    }
+
+    let own_pool_after = cpi::e_add(operation(), own_pool, amount_encrypted, 0)?;
+    let total_after = cpi::e_add(operation(), total, amount_encrypted, 0)?;
+    let min_payout_encrypted = cpi::as_euint128(operation(), min_payout as u128)?;
+    let required = cpi::e_mul(operation(), own_pool_after, min_payout_encrypted, 0)?;
+    let offered = cpi::e_mul(operation(), total_after, amount_encrypted, 0)?;
+    let filled: Ebool = cpi::e_le(operation(), required, offered, 0)?;
+
+    let unfilled = cpi::as_euint128(operation(), UNFILLED_SIDE)?;
+    cpi::e_select(operation(), filled, side, unfilled, 0)
+}
+
+/// Let `allowed` decrypt every pool total
+///
+/// `accounts` holds one pair per outcome, in outcome order:
+/// - [2i] allowance PDA for totals[i], [2i + 1] allowed
+pub fn allow_totals<'info>(
+    inco: &AccountInfo<'info>,
+    signer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    accounts: &[AccountInfo<'info>],
+    totals: &[u128],
+    allowed: Pubkey,
+) -> Result<()> {
+    for (accounts, handle) in accounts.chunks(2).zip(totals.iter().copied()) {
+        let cpi_ctx = CpiContext::new(
+            inco.clone(),
+            Allow {
+                allowance_account: accounts[0].clone(),
+                signer: signer.clone(),
+                allowed_address: accounts[1].clone(),
+                system_program: system_program.clone(),
+            },
+        );
+        cpi::allow(cpi_ctx, handle, true, allowed)?;
+    }
+    Ok(())
+}
@@ -1,26 +1,144 @@
 use anchor_lang::prelude::*;
+use crate::error::DakeError;
+use crate::payout;
+
+/// Minimum number of outcomes (a binary YES/NO market)
+pub const MIN_OUTCOMES: usize = 2;
+/// Maximum number of outcomes in a categorical market
+pub const MAX_OUTCOMES: usize = 16;
+/// Maximum length of an outcome label
+pub const MAX_OUTCOME_LABEL_LEN: usize = 32;
+
+/// Outcome index of NO in a binary market
+pub const OUTCOME_NO: u8 = 0;
+/// Outcome index of YES in a binary market
+pub const OUTCOME_YES: u8 = 1;
+
+/// Outcome index of SHORT in a scalar market
+pub const OUTCOME_SHORT: u8 = 0;
+/// Outcome index of LONG in a scalar market
+pub const OUTCOME_LONG: u8 = 1;
+
+/// Decrypted check_winner result in a scalar market: position is LONG
+pub const SCALAR_SETTLE_LONG: u128 = 2;
+/// Decrypted check_winner result in a scalar market: position is SHORT
+pub const SCALAR_SETTLE_SHORT: u128 = 1;
+/// Decrypted check_winner result for a bet that failed its min_payout guard:
+/// the stake is refunded
+pub const SETTLE_REFUND: u128 = 3;
+
+/// Encrypted side of a bet that failed its min_payout guard (matches no outcome)
+pub const UNFILLED_SIDE: u128 = u8::MAX as u128;
+
+/// Basis-point denominator for fee rates
+pub const BPS_DENOMINATOR: u64 = 10_000;
+/// Maximum protocol or creator fee (10%)
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+/// How long the Config admin has to rule on a dispute before it can be voided (7 days)
+pub const ARBITRATION_WINDOW_SECS: i64 = 7 * 24 * 60 * 60;
 
 /// Market status enum
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum MarketStatus {
-    Open,        // Accepting bets
-    Closed,      // No more bets, awaiting resolution
-    ResolvedYes, // Resolved - YES won
-    ResolvedNo,  // Resolved - NO won
+    #[default]
+    Open,      // Accepting bets
+    Closed,    // No more bets, awaiting resolution
+    Resolved,  // Resolved - see Market::winning_outcome
+    Cancelled, // Voided - every position is refunded its amount
+    PendingResolution, // Optimistic resolution proposed, dispute window running
 }
 
-impl Default for MarketStatus {
-    fn default() -> Self {
-        MarketStatus::Open
-    }
+/// Market type
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MarketKind {
+    /// One of N labelled outcomes wins the whole pool
+    #[default]
+    Categorical,
+    /// Settles on a numeric value: LONG receives the share of the pool given by
+    /// where the value falls between the bounds, SHORT receives the rest
+    Scalar { lower_bound: i64, upper_bound: i64 },
+    /// One of N labelled outcomes wins, priced by an LMSR market maker with
+    /// liquidity `b` (see crate::lmsr). Sides are public: positions buy shares
+    /// of an outcome with buy_shares, and each winning share pays one base unit
+    Lmsr { liquidity: u64 },
+}
+
+/// How an oracle price decides a binary market (YES wins if true)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OracleComparator {
+    /// price > threshold
+    GreaterThan,
+    /// price < threshold
+    LessThan,
+    /// threshold <= price <= upper_threshold
+    Between,
+}
+
+/// Oracle that resolves a market through resolve_from_oracle
+///
+/// Prices and thresholds are in the feed's raw units (price * 10^exponent).
+/// Binary markets resolve YES/NO via the comparator; scalar markets resolve
+/// to the price itself.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct OracleConfig {
+    /// Pyth PriceUpdateV2 account to read
+    pub feed: Pubkey,
+    /// Pyth price feed id the update must carry (the asset being priced)
+    pub feed_id: [u8; 32],
+    pub comparator: OracleComparator,
+    pub threshold: i64,
+    /// Upper bound for OracleComparator::Between (ignored otherwise)
+    pub upper_threshold: i64,
+    /// Exponent the thresholds are expressed in (must match the feed)
+    pub exponent: i32,
+    /// Time the question refers to (Unix timestamp)
+    pub evaluation_time: i64,
+    /// Largest accepted distance between publish_time and evaluation_time
+    pub max_staleness_secs: u32,
+    /// Largest accepted confidence interval, in basis points of the price
+    pub max_confidence_bps: u16,
+}
+
+impl OracleConfig {
+    // 32 (feed) + 32 (feed_id) + 1 (comparator) + 8 (threshold) + 8 (upper_threshold)
+    // + 4 (exponent) + 8 (evaluation_time) + 4 (max_staleness_secs) + 2 (max_confidence_bps)
+    pub const SIZE: usize = 32 + 32 + 1 + 8 + 8 + 4 + 8 + 4 + 2;
+}
+
+/// Who decides a market's outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResolutionSource {
+    /// The market authority (resolve_market / resolve_scalar_market)
+    #[default]
+    Authority,
+    /// A price feed, permissionlessly (resolve_from_oracle)
+    Oracle(OracleConfig),
+    /// Anyone proposes with a bond; disputes go to the Config admin as arbitrator
+    Optimistic,
+    /// M-of-N vote of a ResolverSet; voting closes vote_window_secs after resolution_time
+    Committee {
+        resolver_set: Pubkey,
+        vote_window_secs: u32,
+    },
 }
 
 /// Prediction Market account
 ///
+/// A market has 2..=16 outcomes. A scalar market always has exactly two:
+/// SHORT (0) and LONG (1). A binary market is the two-outcome case
+/// with labels ["NO", "YES"], so outcome 1 = YES and 0 = NO.
+///
 /// Privacy model:
-/// - Individual bet SIDES (YES/NO) are encrypted - nobody knows your position
+/// - Individual bet OUTCOMES are encrypted - nobody knows your position
 /// - Bet AMOUNTS are public - needed for proportional payout calculation
-/// - On resolution, winners prove their side via decryption to claim
+/// - Pool totals per outcome are encrypted while betting is open, and are only
+///   revealed (decrypt + verify) after the market is closed
+/// - On resolution, winners prove their outcome via decryption to claim
+///
+/// An LMSR market trades public shares instead: outcome_totals holds the shares
+/// outstanding per outcome, encrypted_totals is empty, and pools_revealed is set
+/// from creation.
 #[account]
 pub struct Market {
     /// Authority who can resolve the market
@@ -31,63 +149,465 @@ pub struct Market {
     pub question: String,
     /// When the market can be resolved (Unix timestamp)
     pub resolution_time: i64,
+    /// When betting closes automatically (Unix timestamp, at or before resolution_time)
+    pub betting_closes_at: i64,
     /// Current market status
     pub status: MarketStatus,
-    /// Total amount bet on YES side (public, for payout calculation)
-    pub total_yes_amount: u64,
-    /// Total amount bet on NO side (public, for payout calculation)
-    pub total_no_amount: u64,
-    /// Number of participants
+    /// Market type (categorical, scalar or LMSR)
+    pub kind: MarketKind,
+    /// Collateral mint (SPL / Token-2022); Pubkey::default() for native SOL
+    pub collateral_mint: Pubkey,
+    /// Resolved value of a scalar market, clamped to its bounds
+    pub resolved_value: i64,
+    /// Outcome labels, indexed by outcome (e.g. ["NO", "YES"])
+    pub outcomes: Vec<String>,
+    /// Winning outcome index (only meaningful once resolved)
+    pub winning_outcome: u8,
+    /// Seed liquidity provided by the creator per outcome (included in the pool totals)
+    pub initial_liquidity: Vec<u64>,
+    /// Encrypted total bet per outcome (updated homomorphically from each bet's encrypted outcome)
+    pub encrypted_totals: Vec<u128>,
+    /// Total amount bet per outcome - zero until revealed via reveal_pools
+    pub outcome_totals: Vec<u64>,
+    /// Whether the pool totals have been decrypted and verified
+    pub pools_revealed: bool,
+    /// Collateral deposited into the vault (seed liquidity + every bet)
+    pub total_deposited: u64,
+    /// Collateral paid out of the vault (claims, refunds and fees)
+    pub total_paid_out: u64,
+    /// Exit fees taken from positions exited early (paid to the creator fee vault)
+    pub exit_fees_collected: u64,
+    /// Protocol fee on winnings, in basis points (snapshot of Config at creation)
+    pub protocol_fee_bps: u16,
+    /// Creator fee on winnings, in basis points
+    pub creator_fee_bps: u16,
+    /// Who decides the outcome
+    pub resolution: ResolutionSource,
+    /// Number of positions (bets) placed
     pub participant_count: u32,
+    /// Positions not yet closed (see close_position)
+    pub open_positions: u32,
+    /// When the market was created (Unix timestamp)
+    pub created_at: i64,
+    /// When the market was resolved or cancelled (Unix timestamp, 0 before)
+    pub settled_at: i64,
     /// PDA bump seed
     pub bump: u8,
 }
 
 impl Market {
     // 8 (discriminator) + 32 (authority) + 8 (market_id) + 4 + 256 (question) + 8 (resolution_time)
-    // + 1 (status) + 8 (total_yes) + 8 (total_no) + 4 (participant_count) + 1 (bump) + padding
-    pub const SIZE: usize = 8 + 32 + 8 + (4 + 256) + 8 + 1 + 8 + 8 + 4 + 1 + 64;
+    // + 8 (betting_closes_at) + 1 (status) + 17 (kind) + 32 (collateral_mint) + 8 (resolved_value) + 1 (winning_outcome) + 1 (pools_revealed)
+    // + 8 (total_deposited) + 8 (total_paid_out) + 8 (exit_fees_collected) + 2 (protocol_fee_bps)
+    // + 2 (creator_fee_bps) + 1 + OracleConfig::SIZE (resolution) + 4 (participant_count)
+    // + 4 (open_positions) + 8 (created_at) + 8 (settled_at) + 1 (bump) + padding,
+    // plus the per-outcome vectors (see Market::space)
+    pub const BASE_SIZE: usize = 8 + 32 + 8 + (4 + 256) + 8 + 8 + 1 + 17 + 32 + 8 + 1 + 1 + 8 + 8 + 8 + 2 + 2 + (1 + OracleConfig::SIZE) + 4 + 4 + 8 + 8 + 1 + 64;
+
+    // Per outcome: 4 + 32 (label) + 8 (initial liquidity) + 16 (encrypted total) + 8 (revealed total)
+    pub const OUTCOME_SIZE: usize = (4 + MAX_OUTCOME_LABEL_LEN) + 8 + 16 + 8;
+
+    /// Account size for a market with `outcome_count` outcomes
+    pub fn space(outcome_count: usize) -> usize {
+        // 4 vector length prefixes
+        Self::BASE_SIZE + 4 * 4 + outcome_count * Self::OUTCOME_SIZE
+    }
 
     pub fn is_open(&self) -> bool {
         self.status == MarketStatus::Open
     }
 
+    /// Open and before the betting cutoff
+    pub fn is_accepting_bets(&self, now: i64) -> bool {
+        self.is_open() && now < self.betting_closes_at
+    }
+
     pub fn is_resolved(&self) -> bool {
-        matches!(self.status, MarketStatus::ResolvedYes | MarketStatus::ResolvedNo)
+        self.status == MarketStatus::Resolved
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.status == MarketStatus::Cancelled
+    }
+
+    pub fn is_pending_resolution(&self) -> bool {
+        self.status == MarketStatus::PendingResolution
+    }
+
+    pub fn uses_token_collateral(&self) -> bool {
+        self.collateral_mint != Pubkey::default()
+    }
+
+    pub fn is_scalar(&self) -> bool {
+        matches!(self.kind, MarketKind::Scalar { .. })
+    }
+
+    pub fn is_lmsr(&self) -> bool {
+        matches!(self.kind, MarketKind::Lmsr { .. })
+    }
+
+    pub fn outcome_count(&self) -> usize {
+        self.outcomes.len()
+    }
+
+    pub fn total_initial_liquidity(&self) -> u64 {
+        self.initial_liquidity
+            .iter()
+            .fold(0u64, |acc, seed| acc.saturating_add(*seed))
     }
 
     pub fn total_pool(&self) -> u64 {
-        self.total_yes_amount.saturating_add(self.total_no_amount)
+        self.outcome_totals
+            .iter()
+            .fold(0u64, |acc, total| acc.saturating_add(*total))
+    }
+
+    /// Collateral the vault still owes: everything deposited minus everything paid out.
+    ///
+    /// Payouts are shares of the revealed pools, and the pools can never add up to more
+    /// than was deposited, so this is the upper bound on what claimants can still take.
+    pub fn outstanding_liability(&self) -> u64 {
+        self.total_deposited.saturating_sub(self.total_paid_out)
+    }
+
+    /// Portion of the total pool owed to LONG positions in a resolved scalar market
+    ///
+    /// long_share = total_pool * (resolved_value - lower_bound) / (upper_bound - lower_bound)
+    pub fn scalar_long_share(&self) -> u64 {
+        let MarketKind::Scalar { lower_bound, upper_bound } = self.kind else {
+            return 0;
+        };
+        payout::scalar_long_share(self.total_pool(), lower_bound, upper_bound, self.resolved_value)
+    }
+
+    /// Protocol and creator fees owed on a payout of a `stake`: (protocol_fee, creator_fee)
+    ///
+    /// Fees are charged on the profit only, so getting back no more than the
+    /// stake costs nothing.
+    pub fn fees_on(&self, payout: u64, stake: u64) -> (u64, u64) {
+        let profit = payout.saturating_sub(stake);
+        (
+            payout::fee(profit, self.protocol_fee_bps),
+            payout::fee(profit, self.creator_fee_bps),
+        )
+    }
+
+    /// Mark the market resolved with `outcome` (categorical) or `value` (scalar, clamped)
+    pub fn apply_resolution(&mut self, outcome: u8, value: i64) -> Result<()> {
+        match self.kind {
+            MarketKind::Categorical | MarketKind::Lmsr { .. } => {
+                require!(
+                    (outcome as usize) < self.outcome_count(),
+                    DakeError::InvalidOutcome
+                );
+                self.winning_outcome = outcome;
+            }
+            MarketKind::Scalar { lower_bound, upper_bound } => {
+                self.winning_outcome = OUTCOME_LONG;
+                self.resolved_value = value.clamp(lower_bound, upper_bound);
+            }
+        }
+        self.status = MarketStatus::Resolved;
+        self.settled_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// Whether `position` was opened on this market, not on an earlier market
+    /// with the same id that has since been finalized
+    pub fn is_current(&self, position: &Position) -> bool {
+        position.opened_at >= self.created_at
+    }
+
+    /// Revealed pool of the winning outcome (0 if not resolved)
+    pub fn winning_pool(&self) -> u64 {
+        if !self.is_resolved() {
+            return 0;
+        }
+        self.outcome_totals
+            .get(self.winning_outcome as usize)
+            .copied()
+            .unwrap_or(0)
     }
 }
 
 /// User's position in a market
 ///
-/// Privacy: The SIDE (YES=1, NO=0) is stored as an encrypted handle.
-/// Nobody can see which side you bet on until you choose to reveal (or claim winnings).
+/// Privacy: The OUTCOME index (binary: YES=1, NO=0) is stored as an encrypted handle.
+/// Nobody can see which outcome you bet on until you choose to reveal (or claim winnings).
+/// LMSR positions are the exception: their outcome and shares are public.
 #[account]
 pub struct Position {
     /// The market this position belongs to
     pub market: Pubkey,
     /// Owner of this position
     pub owner: Pubkey,
-    /// Bet amount in lamports (public - needed for payout calculation)
+    /// Nonce seed - one wallet can hold many positions (tranches) per market
+    pub nonce: u64,
+    /// When the position was opened (Unix timestamp)
+    pub opened_at: i64,
+    /// Bet amount in lamports or collateral base units (public - needed for payout calculation)
     pub amount: u64,
-    /// Locked payout amount - calculated at bet time, NEVER changes
-    /// This ensures your profit is guaranteed regardless of future bets
-    pub locked_payout: u64,
-    /// Encrypted side handle: 1 = YES, 0 = NO (private!)
+    /// Encrypted outcome index handle (binary: 1 = YES, 0 = NO; scalar: 1 = LONG, 0 = SHORT) (private!)
     pub encrypted_side_handle: u128,
-    /// Encrypted result of (user_side == winning_side) comparison
+    /// Encrypted result of (user_outcome == winning_outcome) comparison.
+    /// For scalar markets: SCALAR_SETTLE_LONG, SCALAR_SETTLE_SHORT, or 0 for an invalid side
     pub is_winner_handle: u128,
-    /// Whether winnings have been claimed
+    /// Whether winnings (or a refund of a cancelled market) have been claimed
     pub claimed: bool,
+    /// Outcome the shares were bought on (LMSR markets only - public)
+    pub outcome: u8,
+    /// Shares held in an LMSR market, each paying one base unit if `outcome` wins
+    /// (0 in parimutuel markets, where `amount` is the stake)
+    pub shares: u64,
     /// PDA bump seed
     pub bump: u8,
 }
 
 impl Position {
-    // 8 (discriminator) + 32 (market) + 32 (owner) + 8 (amount) + 8 (locked_payout) + 16 (encrypted_side)
-    // + 16 (is_winner) + 1 (claimed) + 1 (bump) + padding
-    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 16 + 16 + 1 + 1 + 32;
+    // 8 (discriminator) + 32 (market) + 32 (owner) + 8 (nonce) + 8 (opened_at) + 8 (amount)
+    // + 16 (encrypted_side) + 16 (is_winner) + 1 (claimed) + 1 (outcome) + 8 (shares) + 1 (bump) + padding
+    pub const SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 16 + 16 + 1 + 1 + 8 + 1 + 32;
+}
+
+/// Maximum number of members in a ResolverSet
+pub const MAX_RESOLVERS: usize = 10;
+
+/// Maximum number of allowed collateral mints in Config
+pub const MAX_ALLOWED_MINTS: usize = 8;
+
+/// Settings the admin passes to initialize_config / update_config
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    /// Protocol fee on winnings for new markets, in basis points
+    pub protocol_fee_bps: u16,
+    /// Highest creator fee a new market may set, in basis points
+    pub max_creator_fee_bps: u16,
+    /// Fee on early exits, in basis points of the position amount
+    pub exit_fee_bps: u16,
+    /// Smallest accepted bet (base units)
+    pub min_bet: u64,
+    /// Largest accepted bet (base units, 0 = no limit)
+    pub max_bet: u64,
+    /// Seed liquidity per outcome when create_market is given none
+    pub default_liquidity: u64,
+    /// SPL / Token-2022 mints markets may use as collateral (SOL is always allowed)
+    pub allowed_mints: Vec<Pubkey>,
+    /// Bond (lamports) to propose or dispute an optimistic resolution
+    pub resolution_bond: u64,
+    /// How long an optimistic proposal can be disputed, in seconds
+    pub dispute_window_secs: u32,
+}
+
+/// Program-wide settings (singleton PDA, seeds = [b"config"])
+#[account]
+pub struct Config {
+    /// Admin who can change the config and withdraw protocol fees
+    pub admin: Pubkey,
+    /// Proposed new admin - becomes admin once they call accept_admin
+    pub pending_admin: Option<Pubkey>,
+    /// Protocol fee on winnings for newly created markets, in basis points
+    pub protocol_fee_bps: u16,
+    /// Highest creator fee a new market may set, in basis points
+    pub max_creator_fee_bps: u16,
+    /// Fee on early exits, in basis points of the position amount
+    pub exit_fee_bps: u16,
+    /// Smallest accepted bet (base units)
+    pub min_bet: u64,
+    /// Largest accepted bet (base units, 0 = no limit)
+    pub max_bet: u64,
+    /// Seed liquidity per outcome when create_market is given none
+    pub default_liquidity: u64,
+    /// SPL / Token-2022 mints markets may use as collateral (SOL is always allowed)
+    pub allowed_mints: Vec<Pubkey>,
+    /// Bond (lamports) to propose or dispute an optimistic resolution
+    pub resolution_bond: u64,
+    /// How long an optimistic proposal can be disputed, in seconds
+    pub dispute_window_secs: u32,
+    /// Global pause switch - blocks create_market and place_bet
+    pub paused: bool,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl Config {
+    // 8 (discriminator) + 32 (admin) + 33 (pending_admin) + 2 (protocol_fee_bps) + 2 (max_creator_fee_bps)
+    // + 2 (exit_fee_bps) + 8 (min_bet) + 8 (max_bet) + 8 (default_liquidity)
+    // + 4 + 32 * MAX_ALLOWED_MINTS (allowed_mints) + 8 (resolution_bond) + 4 (dispute_window_secs)
+    // + 1 (paused) + 1 (bump) + 30 (padding)
+    pub const SIZE: usize =
+        8 + 32 + 33 + 2 + 2 + 2 + 8 + 8 + 8 + (4 + 32 * MAX_ALLOWED_MINTS) + 8 + 4 + 1 + 1 + 30;
+
+    /// Validate and store new settings
+    pub fn apply(&mut self, params: ConfigParams) -> Result<()> {
+        require!(params.protocol_fee_bps <= MAX_FEE_BPS, DakeError::InvalidFeeBps);
+        require!(params.max_creator_fee_bps <= MAX_FEE_BPS, DakeError::InvalidFeeBps);
+        require!(params.exit_fee_bps <= MAX_FEE_BPS, DakeError::InvalidFeeBps);
+        require!(
+            params.max_bet == 0 || params.min_bet <= params.max_bet,
+            DakeError::InvalidBetLimits
+        );
+        require!(
+            params.allowed_mints.len() <= MAX_ALLOWED_MINTS,
+            DakeError::TooManyAllowedMints
+        );
+        require!(
+            params.resolution_bond > 0 && params.dispute_window_secs > 0,
+            DakeError::InvalidDisputeSettings
+        );
+
+        self.protocol_fee_bps = params.protocol_fee_bps;
+        self.max_creator_fee_bps = params.max_creator_fee_bps;
+        self.exit_fee_bps = params.exit_fee_bps;
+        self.min_bet = params.min_bet;
+        self.max_bet = params.max_bet;
+        self.default_liquidity = params.default_liquidity;
+        self.allowed_mints = params.allowed_mints;
+        self.resolution_bond = params.resolution_bond;
+        self.dispute_window_secs = params.dispute_window_secs;
+        Ok(())
+    }
+
+    /// SOL (Pubkey::default()) or one of the allowed mints
+    pub fn is_mint_allowed(&self, mint: &Pubkey) -> bool {
+        *mint == Pubkey::default() || self.allowed_mints.contains(mint)
+    }
+
+    /// Whether a bet of `amount` is within the configured limits
+    pub fn is_bet_in_range(&self, amount: u64) -> bool {
+        amount >= self.min_bet && (self.max_bet == 0 || amount <= self.max_bet)
+    }
+}
+
+/// Who a fee vault accrues fees for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum FeeKind {
+    /// Withdrawable by the Config admin
+    Protocol,
+    /// Withdrawable by the market authority
+    Creator,
+}
+
+/// Per-market fee vault (seeds = [b"protocol_fee_vault" | b"creator_fee_vault", market])
+///
+/// SOL markets: the fees are held as lamports on this account.
+/// Token markets: the fees stay in the market's vault token account and are
+/// earmarked here, so they can never be paid out to claimants.
+#[account]
+pub struct FeeVault {
+    /// The market the fees were taken from
+    pub market: Pubkey,
+    /// Protocol or creator fees
+    pub kind: FeeKind,
+    /// Fees accrued so far
+    pub accrued: u64,
+    /// Fees already withdrawn
+    pub withdrawn: u64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl FeeVault {
+    // 8 (discriminator) + 32 (market) + 1 (kind) + 8 (accrued) + 8 (withdrawn) + 1 (bump)
+    pub const SIZE: usize = 8 + 32 + 1 + 8 + 8 + 1;
+
+    /// Fees accrued but not yet withdrawn
+    pub fn available(&self) -> u64 {
+        self.accrued.saturating_sub(self.withdrawn)
+    }
+}
+
+/// Optimistic resolution proposal (seeds = [b"resolution", market, market.created_at])
+///
+/// Holds both bonds as lamports until the proposal is finalized or arbitrated.
+#[account]
+pub struct ResolutionProposal {
+    /// The market being resolved
+    pub market: Pubkey,
+    /// Who proposed (and bonded) the outcome
+    pub proposer: Pubkey,
+    /// Proposed winning outcome (categorical markets)
+    pub outcome: u8,
+    /// Proposed value (scalar markets)
+    pub value: i64,
+    /// Bond posted by each side, in lamports
+    pub bond: u64,
+    /// Disputes are accepted until this time (Unix timestamp)
+    pub dispute_deadline: i64,
+    /// Who disputed (and matched the bond), if anyone
+    pub disputer: Option<Pubkey>,
+    /// A disputed proposal is arbitrated until this time (Unix timestamp, 0 = not disputed)
+    pub arbitration_deadline: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ResolutionProposal {
+    // 8 (discriminator) + 32 (market) + 32 (proposer) + 1 (outcome) + 8 (value) + 8 (bond)
+    // + 8 (dispute_deadline) + 33 (disputer) + 8 (arbitration_deadline) + 1 (bump)
+    pub const SIZE: usize = 8 + 32 + 32 + 1 + 8 + 8 + 8 + 33 + 8 + 1;
+}
+
+/// Resolver committee (seeds = [b"resolver_set", authority, set_id])
+///
+/// Markets with ResolutionSource::Committee resolve once `threshold` members
+/// have voted for the same outcome.
+#[account]
+pub struct ResolverSet {
+    /// Who created the set
+    pub authority: Pubkey,
+    /// Identifier, unique per authority
+    pub set_id: u64,
+    /// Committee members
+    pub members: Vec<Pubkey>,
+    /// Matching votes needed to resolve
+    pub threshold: u8,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ResolverSet {
+    // 8 (discriminator) + 32 (authority) + 8 (set_id) + 4 + 32 * MAX_RESOLVERS (members)
+    // + 1 (threshold) + 1 (bump)
+    pub const SIZE: usize = 8 + 32 + 8 + (4 + 32 * MAX_RESOLVERS) + 1 + 1;
+
+    pub fn is_member(&self, key: &Pubkey) -> bool {
+        self.members.contains(key)
+    }
+}
+
+/// A committee member's vote
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ResolverVote {
+    pub member: Pubkey,
+    /// Voted winning outcome (categorical markets)
+    pub outcome: u8,
+    /// Voted value, clamped to the bounds (scalar markets)
+    pub value: i64,
+}
+
+/// Committee votes on a market (seeds = [b"votes", market, market.created_at])
+#[account]
+pub struct ResolutionVotes {
+    /// The market being voted on
+    pub market: Pubkey,
+    pub votes: Vec<ResolverVote>,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl ResolutionVotes {
+    // 8 (discriminator) + 32 (market) + 4 + MAX_RESOLVERS * (32 + 1 + 8) (votes) + 1 (bump)
+    pub const SIZE: usize = 8 + 32 + (4 + MAX_RESOLVERS * (32 + 1 + 8)) + 1;
+
+    pub fn has_voted(&self, member: &Pubkey) -> bool {
+        self.votes.iter().any(|vote| vote.member == *member)
+    }
+
+    /// Number of votes matching `outcome` / `value`
+    pub fn matching(&self, outcome: u8, value: i64) -> usize {
+        self.votes
+            .iter()
+            .filter(|vote| vote.outcome == outcome && vote.value == value)
+            .count()
+    }
 }
@@ -0,0 +1,106 @@
+//! Deterministic stand-in for the Inco Lightning program
+//!
+//! Handles carry their plaintext: handle = HANDLE_TAG | value. That keeps the
+//! mock stateless while every homomorphic op still yields the right value, and
+//! lets is_validsignature check a decryption against its handle instead of
+//! an Ed25519 attestation.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::program::set_return_data;
+
+const HANDLE_TAG: u128 = 1 << 127;
+
+/// Error returned when a claimed plaintext does not match its handle
+pub const INVALID_DECRYPTION: u32 = 0xdec0;
+
+/// Handle the mock produces for `value`
+pub fn encrypt(value: u128) -> u128 {
+    HANDLE_TAG | value
+}
+
+/// Plaintext of a mock handle
+pub fn decrypt(handle: u128) -> u128 {
+    handle & !HANDLE_TAG
+}
+
+fn sighash(name: &str) -> [u8; 8] {
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash(format!("global:{name}").as_bytes()).to_bytes()[..8]);
+    discriminator
+}
+
+fn u128_at(data: &[u8], offset: usize) -> std::result::Result<u128, ProgramError> {
+    data.get(offset..offset + 16)
+        .and_then(|bytes| bytes.try_into().ok())
+        .map(u128::from_le_bytes)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+fn return_handle(handle: u128) -> ProgramResult {
+    set_return_data(&handle.to_le_bytes());
+    Ok(())
+}
+
+pub fn process(_program_id: &Pubkey, _accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (discriminator, args) = data.split_at(8);
+    let op = |name: &str| discriminator == sighash(name);
+
+    if op("new_euint128") {
+        // Mock ciphertext: the plaintext itself, little-endian
+        let (ciphertext, _input_type) =
+            <(Vec<u8>, u8)>::try_from_slice(args).map_err(|_| ProgramError::InvalidInstructionData)?;
+        let mut bytes = [0u8; 16];
+        let len = ciphertext.len().min(16);
+        bytes[..len].copy_from_slice(&ciphertext[..len]);
+        return_handle(encrypt(u128::from_le_bytes(bytes)))
+    } else if op("as_euint128") {
+        return_handle(encrypt(u128_at(args, 0)?))
+    } else if op("e_add") {
+        let sum = decrypt(u128_at(args, 0)?).wrapping_add(decrypt(u128_at(args, 16)?));
+        return_handle(encrypt(sum & !HANDLE_TAG))
+    } else if op("e_sub") {
+        let difference = decrypt(u128_at(args, 0)?).wrapping_sub(decrypt(u128_at(args, 16)?));
+        return_handle(encrypt(difference & !HANDLE_TAG))
+    } else if op("e_mul") {
+        let product = decrypt(u128_at(args, 0)?).wrapping_mul(decrypt(u128_at(args, 16)?));
+        return_handle(encrypt(product & !HANDLE_TAG))
+    } else if op("e_eq") {
+        let equal = decrypt(u128_at(args, 0)?) == decrypt(u128_at(args, 16)?);
+        return_handle(encrypt(equal as u128))
+    } else if op("e_le") {
+        let less_or_equal = decrypt(u128_at(args, 0)?) <= decrypt(u128_at(args, 16)?);
+        return_handle(encrypt(less_or_equal as u128))
+    } else if op("e_select") {
+        let condition = decrypt(u128_at(args, 0)?) != 0;
+        return_handle(if condition {
+            u128_at(args, 16)?
+        } else {
+            u128_at(args, 32)?
+        })
+    } else if op("allow") {
+        Ok(())
+    } else if op("is_validsignature") {
+        let (_count, handles, plaintexts) =
+            <(u8, Option<Vec<Vec<u8>>>, Option<Vec<Vec<u8>>>)>::try_from_slice(args)
+                .map_err(|_| ProgramError::InvalidInstructionData)?;
+        let handles = handles.unwrap_or_default();
+        let plaintexts = plaintexts.unwrap_or_default();
+        if handles.len() != plaintexts.len() {
+            return Err(ProgramError::Custom(INVALID_DECRYPTION));
+        }
+        for (handle, plaintext) in handles.iter().zip(&plaintexts) {
+            let handle = u128_at(handle, 0)?;
+            let plaintext = u128_at(plaintext, 0)?;
+            if decrypt(handle) != plaintext {
+                return Err(ProgramError::Custom(INVALID_DECRYPTION));
+            }
+        }
+        // No signature results (Vec<SignatureVerificationResult> of length 0)
+        set_return_data(&0u32.to_le_bytes());
+        Ok(())
+    } else {
+        Err(ProgramError::InvalidInstructionData)
+    }
+}
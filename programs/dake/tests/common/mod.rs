@@ -0,0 +1,413 @@
+//! Offline test harness: dake and a mock Inco Lightning program run natively
+//! inside solana-program-test, so no validator or deployed Inco is needed.
+
+#![allow(dead_code)]
+#![allow(deprecated)]
+
+pub mod mock_inco;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{Instruction, InstructionError};
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::associated_token::{get_associated_token_address_with_program_id, spl_associated_token_account};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{transfer_fee, ExtensionType},
+};
+use anchor_spl::token_interface::TokenAccount;
+use dake::state::{Config, ConfigParams, Market, MarketKind, Position, ResolutionSource};
+use dake_client::instructions::*;
+use dake_client::pda;
+use inco_lightning::ID as INCO_LIGHTNING_ID;
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account as SolanaAccount;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub const SOL: u64 = 1_000_000_000;
+
+pub const PROTOCOL_FEE_BPS: u16 = 100;
+pub const MAX_CREATOR_FEE_BPS: u16 = 500;
+pub const EXIT_FEE_BPS: u16 = 200;
+pub const MIN_BET: u64 = SOL / 100;
+pub const DEFAULT_LIQUIDITY: u64 = SOL;
+pub const RESOLUTION_BOND: u64 = SOL / 10;
+pub const DISPUTE_WINDOW_SECS: u32 = 3_600;
+
+/// Seconds from market creation until betting closes / the market can resolve
+pub const MARKET_DURATION_SECS: i64 = 86_400;
+
+/// Outcome indices of a binary market
+pub const NO: u8 = 0;
+pub const YES: u8 = 1;
+
+/// anchor's entrypoint wants `&'info [AccountInfo<'info>]`; program-test hands
+/// out shorter-lived slices, so leak a copy (test processes are short-lived)
+fn dake_entry<'info>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo<'info>],
+    data: &[u8],
+) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+    let accounts: &'info [AccountInfo<'info>] = Box::leak(accounts.to_vec().into_boxed_slice());
+    dake::entry(program_id, accounts, data)
+}
+
+/// Mock ciphertext of an outcome index (see mock_inco)
+pub fn encrypted_side(outcome: u8) -> Vec<u8> {
+    (outcome as u128).to_le_bytes().to_vec()
+}
+
+/// 16-byte little-endian encoding of a handle or plaintext
+pub fn encode(value: u128) -> Vec<u8> {
+    value.to_le_bytes().to_vec()
+}
+
+/// Token collateral of a market: the mint and the token program that owns it
+#[derive(Clone, Copy)]
+pub struct Collateral {
+    pub mint: Pubkey,
+    pub token_program: Pubkey,
+}
+
+impl Collateral {
+    /// Collateral accounts of an instruction signed by the owner of `user_token_account`
+    pub fn accounts(&self, user_token_account: &Pubkey) -> TokenAccounts {
+        TokenAccounts {
+            mint: self.mint,
+            user_token_account: *user_token_account,
+            token_program: self.token_program,
+        }
+    }
+}
+
+pub struct TestEnv {
+    pub ctx: ProgramTestContext,
+    pub admin: Keypair,
+    /// Makes otherwise identical transactions distinct within one blockhash
+    nonce: u32,
+}
+
+impl TestEnv {
+    pub async fn new() -> Self {
+        let mut program_test = ProgramTest::new("dake", dake::ID, processor!(dake_entry));
+        program_test.add_program(
+            "inco_lightning",
+            INCO_LIGHTNING_ID,
+            processor!(mock_inco::process),
+        );
+        program_test.prefer_bpf(false);
+
+        // initialize_config is gated on the upgradeable-loader ProgramData account,
+        // which a natively-run program doesn't have - start from a stored Config
+        let admin = Keypair::new();
+        let (config, bump) = Pubkey::find_program_address(&[b"config"], &dake::ID);
+        let state = Config {
+            admin: admin.pubkey(),
+            pending_admin: None,
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
+            max_creator_fee_bps: MAX_CREATOR_FEE_BPS,
+            exit_fee_bps: EXIT_FEE_BPS,
+            min_bet: MIN_BET,
+            max_bet: 0,
+            default_liquidity: DEFAULT_LIQUIDITY,
+            allowed_mints: vec![],
+            resolution_bond: RESOLUTION_BOND,
+            dispute_window_secs: DISPUTE_WINDOW_SECS,
+            paused: false,
+            bump,
+        };
+        let mut data = Vec::with_capacity(Config::SIZE);
+        state.try_serialize(&mut data).unwrap();
+        data.resize(Config::SIZE, 0);
+        program_test.add_account(
+            config,
+            SolanaAccount {
+                lamports: Rent::default().minimum_balance(Config::SIZE),
+                data,
+                owner: dake::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        let ctx = program_test.start_with_context().await;
+        let mut env = Self { ctx, admin, nonce: 0 };
+        let admin = env.admin.pubkey();
+        env.fund(&admin, 10 * SOL).await;
+        env
+    }
+
+    pub fn payer(&self) -> Keypair {
+        self.ctx.payer.insecure_clone()
+    }
+
+    pub async fn fund(&mut self, to: &Pubkey, lamports: u64) {
+        let payer = self.payer();
+        self.send(
+            &[system_instruction::transfer(&payer.pubkey(), to, lamports)],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// A new wallet holding `lamports`
+    pub async fn user(&mut self, lamports: u64) -> Keypair {
+        let user = Keypair::new();
+        self.fund(&user.pubkey(), lamports).await;
+        user
+    }
+
+    /// Send `instructions`; the context payer pays the fees, `signers` sign as well
+    pub async fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> std::result::Result<(), BanksClientError> {
+        self.nonce += 1;
+        let payer = self.payer();
+        let mut all = vec![ComputeBudgetInstruction::set_compute_unit_limit(
+            1_400_000 - self.nonce,
+        )];
+        all.extend_from_slice(instructions);
+
+        let mut keypairs = vec![&payer];
+        keypairs.extend_from_slice(signers);
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await?;
+        let tx = Transaction::new_signed_with_payer(&all, Some(&payer.pubkey()), &keypairs, blockhash);
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn account<T: AccountDeserialize>(&mut self, key: &Pubkey) -> T {
+        let account = self
+            .ctx
+            .banks_client
+            .get_account(*key)
+            .await
+            .unwrap()
+            .unwrap_or_else(|| panic!("account {key} not found"));
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    pub async fn exists(&mut self, key: &Pubkey) -> bool {
+        self.ctx.banks_client.get_account(*key).await.unwrap().is_some()
+    }
+
+    pub async fn lamports(&mut self, key: &Pubkey) -> u64 {
+        self.ctx.banks_client.get_balance(*key).await.unwrap()
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        self.ctx
+            .banks_client
+            .get_sysvar::<Clock>()
+            .await
+            .unwrap()
+            .unix_timestamp
+    }
+
+    /// Create a 6-decimal mint under `token_program` (payer is the mint authority),
+    /// with a Token-2022 transfer fee of `transfer_fee_bps` if given
+    pub async fn create_mint(&mut self, token_program: Pubkey, transfer_fee_bps: Option<u16>) -> Collateral {
+        let payer = self.payer();
+        let mint = Keypair::new();
+        let extensions: Vec<ExtensionType> = transfer_fee_bps
+            .iter()
+            .map(|_| ExtensionType::TransferFeeConfig)
+            .collect();
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&extensions).unwrap();
+
+        let mut instructions = vec![system_instruction::create_account(
+            &payer.pubkey(),
+            &mint.pubkey(),
+            Rent::default().minimum_balance(space),
+            space as u64,
+            &token_program,
+        )];
+        if let Some(bps) = transfer_fee_bps {
+            instructions.push(
+                transfer_fee::instruction::initialize_transfer_fee_config(
+                    &token_program,
+                    &mint.pubkey(),
+                    Some(&payer.pubkey()),
+                    Some(&payer.pubkey()),
+                    bps,
+                    u64::MAX,
+                )
+                .unwrap(),
+            );
+        }
+        instructions.push(
+            spl_token_2022::instruction::initialize_mint2(&token_program, &mint.pubkey(), &payer.pubkey(), None, 6)
+                .unwrap(),
+        );
+        self.send(&instructions, &[&mint]).await.unwrap();
+
+        Collateral {
+            mint: mint.pubkey(),
+            token_program,
+        }
+    }
+
+    /// `owner`'s associated token account for `collateral`, minted `amount` tokens
+    pub async fn token_account(&mut self, collateral: &Collateral, owner: &Pubkey, amount: u64) -> Pubkey {
+        let payer = self.payer();
+        let account = get_associated_token_address_with_program_id(owner, &collateral.mint, &collateral.token_program);
+        self.send(
+            &[
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(),
+                    owner,
+                    &collateral.mint,
+                    &collateral.token_program,
+                ),
+                spl_token_2022::instruction::mint_to(
+                    &collateral.token_program,
+                    &collateral.mint,
+                    &account,
+                    &payer.pubkey(),
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+            ],
+            &[],
+        )
+        .await
+        .unwrap();
+        account
+    }
+
+    pub async fn token_balance(&mut self, account: &Pubkey) -> u64 {
+        self.account::<TokenAccount>(account).await.amount
+    }
+
+    /// Add `mints` to the config's allowed collateral mints
+    pub async fn allow_mints(&mut self, allowed_mints: Vec<Pubkey>) {
+        let admin = self.admin.insecure_clone();
+        let params = ConfigParams {
+            protocol_fee_bps: PROTOCOL_FEE_BPS,
+            max_creator_fee_bps: MAX_CREATOR_FEE_BPS,
+            exit_fee_bps: EXIT_FEE_BPS,
+            min_bet: MIN_BET,
+            max_bet: 0,
+            default_liquidity: DEFAULT_LIQUIDITY,
+            allowed_mints,
+            resolution_bond: RESOLUTION_BOND,
+            dispute_window_secs: DISPUTE_WINDOW_SECS,
+        };
+        self.send(&[update_config(&admin.pubkey(), params)], &[&admin])
+            .await
+            .unwrap();
+    }
+
+    /// Store `account` at `key`, bypassing the runtime (e.g. a price feed)
+    pub fn set_account(&mut self, key: &Pubkey, account: SolanaAccount) {
+        self.ctx.set_account(key, &account.into());
+    }
+
+    /// Move the clock forward by `secs`
+    pub async fn warp(&mut self, secs: i64) {
+        let mut clock = self.ctx.banks_client.get_sysvar::<Clock>().await.unwrap();
+        clock.unix_timestamp += secs;
+        self.ctx.set_sysvar(&clock);
+    }
+}
+
+/// Create a market with `params` as `authority`
+pub async fn open_market(env: &mut TestEnv, authority: &Keypair, market_id: u64, params: MarketParams) -> Pubkey {
+    let now = env.now().await;
+    env.send(&[create_market(&authority.pubkey(), market_args(market_id, now, params), None)], &[authority])
+        .await
+        .unwrap();
+    pda::market(market_id).0
+}
+
+/// Warp past the resolution time, close the market and reveal its pools
+pub async fn close_and_reveal(env: &mut TestEnv, authority: &Keypair, market: &Pubkey) -> Vec<u64> {
+    env.warp(MARKET_DURATION_SECS).await;
+    env.send(&[close_market(&authority.pubkey(), market, &[])], &[authority])
+        .await
+        .unwrap();
+
+    let handles = env.account::<Market>(market).await.encrypted_totals;
+    let totals: Vec<u64> = handles.iter().map(|h| mock_inco::decrypt(*h) as u64).collect();
+    env.send(&[reveal(&authority.pubkey(), market, &handles, &totals)], &[authority])
+        .await
+        .unwrap();
+    totals
+}
+
+/// check_winner for a position, returning its is_winner handle
+pub async fn check(env: &mut TestEnv, owner: &Keypair, market: &Pubkey, nonce: u64) -> u128 {
+    let position = pda::position(market, &owner.pubkey(), nonce).0;
+    env.send(&[check_winner(&owner.pubkey(), market, &position, None)], &[owner])
+        .await
+        .unwrap();
+    env.account::<Position>(&position).await.is_winner_handle
+}
+
+/// Assert a transaction failed with the given custom (anchor) error code
+pub fn assert_error(result: std::result::Result<(), BanksClientError>, code: impl Into<u32>) {
+    let code = code.into();
+    match result.map_err(|err| err.unwrap()) {
+        Err(TransactionError::InstructionError(_, InstructionError::Custom(actual))) => {
+            assert_eq!(actual, code, "expected error {code}, got {actual}")
+        }
+        other => panic!("expected error {code}, got {other:?}"),
+    }
+}
+
+// ---- instruction helpers ----
+
+pub struct MarketParams {
+    pub outcomes: Vec<&'static str>,
+    pub kind: MarketKind,
+    pub initial_liquidity: Vec<u64>,
+    pub creator_fee_bps: u16,
+    pub resolution: ResolutionSource,
+}
+
+impl Default for MarketParams {
+    fn default() -> Self {
+        Self {
+            outcomes: vec!["NO", "YES"],
+            kind: MarketKind::Categorical,
+            initial_liquidity: vec![SOL, SOL],
+            creator_fee_bps: 100,
+            resolution: ResolutionSource::Authority,
+        }
+    }
+}
+
+/// create_market arguments for a market that closes and resolves MARKET_DURATION_SECS after `now`
+pub fn market_args(market_id: u64, now: i64, params: MarketParams) -> CreateMarketArgs {
+    CreateMarketArgs {
+        market_id,
+        question: format!("Test market #{market_id}"),
+        outcomes: params.outcomes.into_iter().map(String::from).collect(),
+        kind: params.kind,
+        initial_liquidity: params.initial_liquidity,
+        resolution_time: now + MARKET_DURATION_SECS,
+        betting_closes_at: now + MARKET_DURATION_SECS,
+        creator_fee_bps: params.creator_fee_bps,
+        resolution: params.resolution,
+    }
+}
+
+/// place_bet on `outcome` in a SOL market, without a min_payout guard
+pub fn bet(bettor: &Pubkey, market: &Pubkey, outcome: u8, amount: u64, nonce: u64) -> Instruction {
+    place_bet(bettor, market, encrypted_side(outcome), amount, nonce, 0, None, None)
+}
+
+/// reveal_pools with the mock's plaintext totals of `handles`
+pub fn reveal(revealer: &Pubkey, market: &Pubkey, handles: &[u128], totals: &[u64]) -> Instruction {
+    reveal_pools(
+        revealer,
+        market,
+        handles.iter().map(|handle| encode(*handle)).collect(),
+        totals.iter().map(|total| encode(*total as u128)).collect(),
+    )
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use common::*;
+use dake::error::DakeError;
+use dake::state::{Config, ConfigParams};
+use dake_client::instructions::*;
+use dake_client::pda;
+use solana_sdk::signature::Signer;
+
+fn params() -> ConfigParams {
+    ConfigParams {
+        protocol_fee_bps: 200,
+        max_creator_fee_bps: MAX_CREATOR_FEE_BPS,
+        exit_fee_bps: 300,
+        min_bet: MIN_BET,
+        max_bet: 5 * SOL,
+        default_liquidity: DEFAULT_LIQUIDITY,
+        allowed_mints: vec![],
+        resolution_bond: RESOLUTION_BOND,
+        dispute_window_secs: DISPUTE_WINDOW_SECS,
+    }
+}
+
+#[tokio::test]
+async fn only_the_admin_updates_the_config() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let outsider = env.user(SOL).await;
+
+    let result = env.send(&[update_config(&outsider.pubkey(), params())], &[&outsider]).await;
+    assert_error(result, DakeError::Unauthorized);
+
+    env.send(&[update_config(&admin.pubkey(), params())], &[&admin]).await.unwrap();
+    let config: Config = env.account(&pda::config().0).await;
+    assert_eq!(config.protocol_fee_bps, 200);
+    assert_eq!(config.max_bet, 5 * SOL);
+    assert_eq!(config.exit_fee_bps, 300);
+
+    let invalid = ConfigParams {
+        min_bet: 10 * SOL,
+        ..params()
+    };
+    let result = env.send(&[update_config(&admin.pubkey(), invalid)], &[&admin]).await;
+    assert_error(result, DakeError::InvalidBetLimits);
+
+    let invalid = ConfigParams {
+        exit_fee_bps: 1_001,
+        ..params()
+    };
+    let result = env.send(&[update_config(&admin.pubkey(), invalid)], &[&admin]).await;
+    assert_error(result, DakeError::InvalidFeeBps);
+}
+
+#[tokio::test]
+async fn pausing_blocks_new_markets_and_bets() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let creator = env.user(10 * SOL).await;
+    let bettor = env.user(10 * SOL).await;
+    let market = open_market(&mut env, &creator, 1, MarketParams::default()).await;
+
+    let result = env.send(&[set_paused(&creator.pubkey(), true)], &[&creator]).await;
+    assert_error(result, DakeError::Unauthorized);
+    env.send(&[set_paused(&admin.pubkey(), true)], &[&admin]).await.unwrap();
+
+    let now = env.now().await;
+    let result = env
+        .send(&[create_market(&creator.pubkey(), market_args(2, now, MarketParams::default()), None)], &[&creator])
+        .await;
+    assert_error(result, DakeError::ProgramPaused);
+    let result = env
+        .send(&[bet(&bettor.pubkey(), &market, YES, SOL, 0)], &[&bettor])
+        .await;
+    assert_error(result, DakeError::ProgramPaused);
+
+    env.send(&[set_paused(&admin.pubkey(), false)], &[&admin]).await.unwrap();
+    env.send(&[bet(&bettor.pubkey(), &market, YES, SOL, 0)], &[&bettor])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn admin_handover_takes_two_steps() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let successor = env.user(SOL).await;
+    let outsider = env.user(SOL).await;
+
+    let result = env.send(&[accept_admin(&successor.pubkey())], &[&successor]).await;
+    assert_error(result, DakeError::NoPendingAdmin);
+
+    let result = env
+        .send(&[propose_admin(&outsider.pubkey(), outsider.pubkey())], &[&outsider])
+        .await;
+    assert_error(result, DakeError::Unauthorized);
+
+    env.send(&[propose_admin(&admin.pubkey(), successor.pubkey())], &[&admin])
+        .await
+        .unwrap();
+    let result = env.send(&[accept_admin(&outsider.pubkey())], &[&outsider]).await;
+    assert_error(result, DakeError::Unauthorized);
+
+    env.send(&[accept_admin(&successor.pubkey())], &[&successor])
+        .await
+        .unwrap();
+    let config: Config = env.account(&pda::config().0).await;
+    assert_eq!(config.admin, successor.pubkey());
+    assert_eq!(config.pending_admin, None);
+
+    // The previous admin lost its rights
+    let result = env.send(&[set_paused(&admin.pubkey(), true)], &[&admin]).await;
+    assert_error(result, DakeError::Unauthorized);
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use common::*;
+use dake::error::DakeError;
+use dake::lmsr;
+use dake::state::{FeeVault, Market, MarketKind, MarketStatus, Position};
+use dake_client::instructions::*;
+use dake_client::pda;
+use solana_sdk::signature::Signer;
+
+fn lmsr_params() -> MarketParams {
+    MarketParams {
+        kind: MarketKind::Lmsr { liquidity: SOL },
+        initial_liquidity: vec![],
+        ..MarketParams::default()
+    }
+}
+
+#[tokio::test]
+async fn lmsr_market_pays_winning_shares_and_bounds_the_creator_loss() {
+    let mut env = TestEnv::new().await;
+    let admin = env.admin.insecure_clone();
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let bob = env.user(10 * SOL).await;
+
+    // The creator funds b * ln(2) instead of seeding the outcomes
+    let now = env.now().await;
+    for kind in [MarketKind::Lmsr { liquidity: 0 }, MarketKind::Lmsr { liquidity: SOL }] {
+        let params = MarketParams {
+            kind,
+            ..MarketParams::default()
+        };
+        let result = env.send(&[create_market(&creator.pubkey(), market_args(1, now, params), None)], &[&creator]).await;
+        assert_error(result, DakeError::InvalidInitialLiquidity);
+    }
+    let market = open_market(&mut env, &creator, 1, lmsr_params()).await;
+    let subsidy = lmsr::max_loss(SOL, 2).unwrap();
+    let state: Market = env.account(&market).await;
+    assert_eq!(state.total_deposited, subsidy);
+    assert!(state.pools_revealed);
+    assert!(state.encrypted_totals.is_empty());
+    let reserve = anchor_lang::prelude::Rent::default().minimum_balance(0);
+    assert_eq!(env.lamports(&pda::vault(&market).0).await, subsidy + reserve);
+
+    // A quote that moved before the trade landed is not filled
+    let alice_cost = lmsr::buy_cost(&[0, 0], SOL, YES as usize, 2 * SOL).unwrap();
+    let result = env
+        .send(&[buy_shares(&alice.pubkey(), &market, YES, 2 * SOL, alice_cost - 1, 0, None)], &[&alice])
+        .await;
+    assert_error(result, DakeError::MaxCostExceeded);
+    env.send(&[buy_shares(&alice.pubkey(), &market, YES, 2 * SOL, alice_cost, 0, None)], &[&alice])
+        .await
+        .unwrap();
+
+    let result = env.send(&[bet(&bob.pubkey(), &market, NO, SOL, 0)], &[&bob]).await;
+    assert_error(result, DakeError::WrongMarketKind);
+    let bob_cost = lmsr::buy_cost(&[0, 2 * SOL], SOL, NO as usize, SOL).unwrap();
+    env.send(&[buy_shares(&bob.pubkey(), &market, NO, SOL, bob_cost, 0, None)], &[&bob])
+        .await
+        .unwrap();
+
+    let state: Market = env.account(&market).await;
+    assert_eq!(state.outcome_totals, vec![SOL, 2 * SOL]);
+    assert_eq!(state.total_deposited, subsidy + alice_cost + bob_cost);
+    let alice_position = pda::position(&market, &alice.pubkey(), 0).0;
+    let position: Position = env.account(&alice_position).await;
+    assert_eq!((position.outcome, position.shares, position.amount), (YES, 2 * SOL, alice_cost));
+
+    env.warp(MARKET_DURATION_SECS).await;
+    let result = env
+        .send(&[buy_shares(&bob.pubkey(), &market, YES, SOL, SOL, 1, None)], &[&bob])
+        .await;
+    assert_error(result, DakeError::BettingClosed);
+    env.send(&[resolve_market(&creator.pubkey(), &market, YES)], &[&creator]).await.unwrap();
+
+    // Sides are public, so there is nothing to check or decrypt
+    let bob_position = pda::position(&market, &bob.pubkey(), 0).0;
+    let result = env
+        .send(&[check_winner(&bob.pubkey(), &market, &bob_position, None)], &[&bob])
+        .await;
+    assert_error(result, DakeError::WrongMarketKind);
+    let result = env.send(&[redeem_shares(&bob.pubkey(), &market, &bob_position, None)], &[&bob]).await;
+    assert_error(result, DakeError::NotWinner);
+
+    // Every winning share pays one lamport, fees are charged on the profit
+    let before = env.lamports(&alice.pubkey()).await;
+    env.send(&[redeem_shares(&alice.pubkey(), &market, &alice_position, None)], &[&alice])
+        .await
+        .unwrap();
+    let profit = 2 * SOL - alice_cost;
+    let protocol_fee = profit * PROTOCOL_FEE_BPS as u64 / 10_000;
+    let creator_fee = profit / 100;
+    assert_eq!(env.lamports(&alice.pubkey()).await - before, 2 * SOL - protocol_fee - creator_fee);
+    assert_eq!(env.account::<FeeVault>(&pda::protocol_fee_vault(&market).0).await.accrued, protocol_fee);
+    let result = env
+        .send(&[redeem_shares(&alice.pubkey(), &market, &alice_position, None)], &[&alice])
+        .await;
+    assert_error(result, DakeError::AlreadyClaimed);
+
+    // The creator lost less than the subsidy it put up
+    let state: Market = env.account(&market).await;
+    assert_eq!(state.outstanding_liability(), subsidy + alice_cost + bob_cost - 2 * SOL);
+
+    for (owner, position) in [(&alice, &alice_position), (&bob, &bob_position)] {
+        env.send(&[close_position(&owner.pubkey(), &market, position)], &[owner])
+            .await
+            .unwrap();
+    }
+    env.send(&[withdraw_protocol_fees(&admin.pubkey(), &market, None)], &[&admin]).await.unwrap();
+    env.send(&[withdraw_creator_fees(&creator.pubkey(), &market, None)], &[&creator]).await.unwrap();
+    env.send(&[finalize_market(&creator.pubkey(), &market, &env.admin.pubkey(), None, None)], &[&creator]).await.unwrap();
+    assert!(!env.exists(&market).await);
+    assert!(!env.exists(&pda::vault(&market).0).await);
+}
+
+#[tokio::test]
+async fn cancelled_lmsr_market_refunds_what_was_paid() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let market = open_market(&mut env, &creator, 1, lmsr_params()).await;
+
+    let cost = lmsr::buy_cost(&[0, 0], SOL, NO as usize, SOL).unwrap();
+    env.send(&[buy_shares(&alice.pubkey(), &market, NO, SOL, cost, 0, None)], &[&alice])
+        .await
+        .unwrap();
+    let position = pda::position(&market, &alice.pubkey(), 0).0;
+
+    let result = env.send(&[exit_position(&alice.pubkey(), &market, &position, None)], &[&alice]).await;
+    assert_error(result, DakeError::WrongMarketKind);
+
+    // The subsidy goes straight back to the creator
+    let before = env.lamports(&creator.pubkey()).await;
+    env.send(&[cancel_market(&creator.pubkey(), &market, None)], &[&creator]).await.unwrap();
+    assert!(env.account::<Market>(&market).await.status == MarketStatus::Cancelled);
+    assert_eq!(env.lamports(&creator.pubkey()).await - before, lmsr::max_loss(SOL, 2).unwrap());
+
+    let before = env.lamports(&alice.pubkey()).await;
+    env.send(&[refund_position(&alice.pubkey(), &market, &position, None)], &[&alice])
+        .await
+        .unwrap();
+    assert_eq!(env.lamports(&alice.pubkey()).await - before, cost);
+
+    // Nothing is owed any more, so the market can be finalized
+    env.send(&[close_position(&alice.pubkey(), &market, &position)], &[&alice])
+        .await
+        .unwrap();
+    env.send(&[finalize_market(&creator.pubkey(), &market, &env.admin.pubkey(), None, None)], &[&creator]).await.unwrap();
+    assert!(!env.exists(&market).await);
+}
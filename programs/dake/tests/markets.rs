@@ -0,0 +1,559 @@
+mod common;
+
+use common::mock_inco::{self, INVALID_DECRYPTION};
+use common::*;
+use dake::error::DakeError;
+use dake::state::{
+    FeeVault, Market, MarketKind, MarketStatus, Position, SCALAR_SETTLE_LONG, SCALAR_SETTLE_SHORT, SETTLE_REFUND,
+};
+use dake_client::instructions::*;
+use dake_client::pda;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn binary_market_pays_winner_minus_fees() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let bob = env.user(10 * SOL).await;
+    let market = open_market(&mut env, &creator, 1, MarketParams::default()).await;
+
+    env.send(&[bet(&alice.pubkey(), &market, YES, 2 * SOL, 0)], &[&alice]).await.unwrap();
+    env.send(&[bet(&bob.pubkey(), &market, NO, SOL, 0)], &[&bob]).await.unwrap();
+
+    // Seeds of 1 SOL per outcome plus the bets, routed by the encrypted side
+    let totals = close_and_reveal(&mut env, &creator, &market).await;
+    assert_eq!(totals, vec![2 * SOL, 3 * SOL]);
+
+    env.send(&[resolve_market(&creator.pubkey(), &market, YES)], &[&creator]).await.unwrap();
+
+    let handle = check(&mut env, &alice, &market, 0).await;
+    assert_eq!(mock_inco::decrypt(handle), 1);
+
+    let position = pda::position(&market, &alice.pubkey(), 0).0;
+    let before = env.lamports(&alice.pubkey()).await;
+    env.send(&[claim_winnings(&alice.pubkey(), &market, &position, encode(handle), encode(1), None)], &[&alice])
+        .await
+        .unwrap();
+
+    // 2 SOL of a 3 SOL winning pool takes 2/3 of the 5 SOL total pool
+    // Fees are charged on the profit only
+    let payout = 2 * SOL * 5 / 3;
+    let protocol_fee = (payout - 2 * SOL) * PROTOCOL_FEE_BPS as u64 / 10_000;
+    let creator_fee = (payout - 2 * SOL) / 100;
+    assert_eq!(env.lamports(&alice.pubkey()).await - before, payout - protocol_fee - creator_fee);
+
+    let protocol_vault: FeeVault = env.account(&pda::protocol_fee_vault(&market).0).await;
+    let creator_vault: FeeVault = env.account(&pda::creator_fee_vault(&market).0).await;
+    assert_eq!(protocol_vault.accrued, protocol_fee);
+    assert_eq!(creator_vault.accrued, creator_fee);
+
+    let state: Market = env.account(&market).await;
+    assert_eq!(state.total_paid_out, payout);
+    assert!(env.account::<Position>(&position).await.claimed);
+
+    // A second claim is rejected
+    let result = env
+        .send(&[claim_winnings(&alice.pubkey(), &market, &position, encode(handle), encode(1), None)], &[&alice])
+        .await;
+    assert_error(result, DakeError::AlreadyClaimed);
+}
+
+#[tokio::test]
+async fn every_winner_of_a_zero_seed_market_can_claim() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let params = MarketParams {
+        initial_liquidity: vec![0, 0],
+        ..MarketParams::default()
+    };
+    let market = open_market(&mut env, &creator, 1, params).await;
+
+    let mut winners = Vec::new();
+    for _ in 0..3 {
+        let winner = env.user(10 * SOL).await;
+        env.send(&[bet(&winner.pubkey(), &market, YES, SOL, 0)], &[&winner]).await.unwrap();
+        winners.push(winner);
+    }
+    let loser = env.user(10 * SOL).await;
+    env.send(&[bet(&loser.pubkey(), &market, NO, SOL, 0)], &[&loser]).await.unwrap();
+
+    close_and_reveal(&mut env, &creator, &market).await;
+    env.send(&[resolve_market(&creator.pubkey(), &market, YES)], &[&creator]).await.unwrap();
+
+    for winner in &winners {
+        let handle = check(&mut env, winner, &market, 0).await;
+        let position = pda::position(&market, &winner.pubkey(), 0).0;
+        env.send(&[claim_winnings(&winner.pubkey(), &market, &position, encode(handle), encode(1), None)], &[winner])
+            .await
+            .unwrap();
+    }
+
+    // Only the rounding dust is left on top of the vault's rent reserve
+    let reserve = anchor_lang::prelude::Rent::default().minimum_balance(0);
+    assert_eq!(env.lamports(&pda::vault(&market).0).await, reserve + 1);
+}
+
+#[tokio::test]
+async fn winners_who_only_get_their_stake_back_pay_no_fees() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let bob = env.user(10 * SOL).await;
+    let params = MarketParams {
+        initial_liquidity: vec![0, 0],
+        ..MarketParams::default()
+    };
+    let market = open_market(&mut env, &creator, 1, params).await;
+
+    // Everyone picks the winning side, so there is no profit to take fees from
+    for (bettor, amount) in [(&alice, 2 * SOL), (&bob, SOL)] {
+        env.send(&[bet(&bettor.pubkey(), &market, YES, amount, 0)], &[bettor])
+            .await
+            .unwrap();
+    }
+    close_and_reveal(&mut env, &creator, &market).await;
+    env.send(&[resolve_market(&creator.pubkey(), &market, YES)], &[&creator]).await.unwrap();
+
+    for (bettor, amount) in [(&alice, 2 * SOL), (&bob, SOL)] {
+        let handle = check(&mut env, bettor, &market, 0).await;
+        let position = pda::position(&market, &bettor.pubkey(), 0).0;
+        let before = env.lamports(&bettor.pubkey()).await;
+        env.send(&[claim_winnings(&bettor.pubkey(), &market, &position, encode(handle), encode(1), None)], &[bettor])
+            .await
+            .unwrap();
+        assert_eq!(env.lamports(&bettor.pubkey()).await - before, amount);
+    }
+
+    assert_eq!(env.account::<FeeVault>(&pda::protocol_fee_vault(&market).0).await.accrued, 0);
+    assert_eq!(env.account::<FeeVault>(&pda::creator_fee_vault(&market).0).await.accrued, 0);
+}
+
+#[tokio::test]
+async fn bets_below_their_min_payout_join_no_pool_and_are_refunded() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let bob = env.user(10 * SOL).await;
+    let market = open_market(&mut env, &creator, 1, MarketParams::default()).await;
+
+    // No pool could ever pay more than the market holds
+    let result = env
+        .send(&[place_bet(&alice.pubkey(), &market, encrypted_side(YES), SOL, 0, 100 * SOL, None, None)], &[&alice])
+        .await;
+    assert_error(result, DakeError::PayoutBelowMinimum);
+
+    // YES would pay 3/2 SOL on a 1 SOL bet: below 3 SOL, so the bet is not filled
+    env.send(&[place_bet(&alice.pubkey(), &market, encrypted_side(YES), SOL, 0, 3 * SOL, None, None)], &[&alice])
+        .await
+        .unwrap();
+    // NO pays 4/2 SOL, which meets 3/2 SOL
+    env.send(&[place_bet(&bob.pubkey(), &market, encrypted_side(NO), SOL, 0, 3 * SOL / 2, None, None)], &[&bob])
+        .await
+        .unwrap();
+
+    let totals = close_and_reveal(&mut env, &creator, &market).await;
+    assert_eq!(totals, vec![2 * SOL, SOL]);
+
+    env.send(&[resolve_market(&creator.pubkey(), &market, YES)], &[&creator]).await.unwrap();
+
+    let handle = check(&mut env, &alice, &market, 0).await;
+    assert_eq!(mock_inco::decrypt(handle), SETTLE_REFUND);
+    assert_eq!(mock_inco::decrypt(check(&mut env, &bob, &market, 0).await), 0);
+
+    // The unfilled stake comes back in full, fee free
+    let position = pda::position(&market, &alice.pubkey(), 0).0;
+    let before = env.lamports(&alice.pubkey()).await;
+    env.send(
+        &[claim_winnings(&alice.pubkey(), &market, &position, encode(handle), encode(SETTLE_REFUND), None)],
+        &[&alice],
+    )
+    .await
+    .unwrap();
+    assert_eq!(env.lamports(&alice.pubkey()).await - before, SOL);
+    assert_eq!(env.account::<FeeVault>(&pda::protocol_fee_vault(&market).0).await.accrued, 0);
+}
+
+#[tokio::test]
+async fn losers_and_forged_decryptions_cannot_claim() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let bob = env.user(10 * SOL).await;
+    let market = open_market(&mut env, &creator, 1, MarketParams::default()).await;
+
+    env.send(&[bet(&bob.pubkey(), &market, NO, SOL, 0)], &[&bob]).await.unwrap();
+    close_and_reveal(&mut env, &creator, &market).await;
+    env.send(&[resolve_market(&creator.pubkey(), &market, YES)], &[&creator]).await.unwrap();
+
+    let position = pda::position(&market, &bob.pubkey(), 0).0;
+    let result = env
+        .send(&[claim_winnings(&bob.pubkey(), &market, &position, encode(0), encode(0), None)], &[&bob])
+        .await;
+    assert_error(result, DakeError::NotChecked);
+
+    let handle = check(&mut env, &bob, &market, 0).await;
+    assert_eq!(mock_inco::decrypt(handle), 0);
+
+    let result = env
+        .send(&[claim_winnings(&bob.pubkey(), &market, &position, encode(handle), encode(0), None)], &[&bob])
+        .await;
+    assert_error(result, DakeError::NotWinner);
+
+    // Claiming "1" does not match the attested decryption
+    let result = env
+        .send(&[claim_winnings(&bob.pubkey(), &market, &position, encode(handle), encode(1), None)], &[&bob])
+        .await;
+    assert_error(result, INVALID_DECRYPTION);
+
+    // Nor can the handle be swapped for another one
+    let result = env
+        .send(&[claim_winnings(&bob.pubkey(), &market, &position, encode(mock_inco::encrypt(1)), encode(1), None)], &[&bob])
+        .await;
+    assert_error(result, DakeError::HandleMismatch);
+
+    // Someone else cannot claim bob's position
+    let result = env
+        .send(&[claim_winnings(&creator.pubkey(), &market, &position, encode(handle), encode(0), None)], &[&creator])
+        .await;
+    assert_error(result, DakeError::NotOwner);
+}
+
+#[tokio::test]
+async fn grant_decrypt_access_requires_a_checked_position() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let market = open_market(&mut env, &creator, 1, MarketParams::default()).await;
+    env.send(&[bet(&alice.pubkey(), &market, YES, SOL, 0)], &[&alice]).await.unwrap();
+    let position = pda::position(&market, &alice.pubkey(), 0).0;
+
+    let result = env.send(&[grant_decrypt_access(&alice.pubkey(), &position, 0)], &[&alice]).await;
+    assert_error(result, DakeError::NotChecked);
+
+    close_and_reveal(&mut env, &creator, &market).await;
+    env.send(&[resolve_market(&creator.pubkey(), &market, YES)], &[&creator]).await.unwrap();
+    let handle = check(&mut env, &alice, &market, 0).await;
+
+    env.send(&[grant_decrypt_access(&alice.pubkey(), &position, handle)], &[&alice])
+        .await
+        .unwrap();
+    let result = env.send(&[grant_decrypt_access(&creator.pubkey(), &position, handle)], &[&creator]).await;
+    assert_error(result, DakeError::NotOwner);
+}
+
+#[tokio::test]
+async fn bets_are_validated() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let market = open_market(&mut env, &creator, 1, MarketParams::default()).await;
+
+    let result = env.send(&[bet(&alice.pubkey(), &market, YES, 0, 0)], &[&alice]).await;
+    assert_error(result, DakeError::InvalidBetAmount);
+
+    let result = env
+        .send(&[bet(&alice.pubkey(), &market, YES, MIN_BET - 1, 0)], &[&alice])
+        .await;
+    assert_error(result, DakeError::BetOutOfRange);
+
+    // Several positions per wallet, one per nonce
+    env.send(&[bet(&alice.pubkey(), &market, YES, SOL, 0)], &[&alice]).await.unwrap();
+    env.send(&[bet(&alice.pubkey(), &market, NO, SOL, 1)], &[&alice]).await.unwrap();
+    assert_eq!(env.account::<Market>(&market).await.participant_count, 2);
+
+    env.warp(MARKET_DURATION_SECS).await;
+    let result = env.send(&[bet(&alice.pubkey(), &market, YES, SOL, 2)], &[&alice]).await;
+    assert_error(result, DakeError::BettingClosed);
+
+    env.send(&[close_market(&creator.pubkey(), &market, &[])], &[&creator]).await.unwrap();
+    let result = env.send(&[bet(&alice.pubkey(), &market, YES, SOL, 3)], &[&alice]).await;
+    assert_error(result, DakeError::MarketNotOpen);
+}
+
+#[tokio::test]
+async fn market_creation_is_validated() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let now = env.now().await;
+
+    let params = MarketParams {
+        outcomes: vec!["ONLY"],
+        initial_liquidity: vec![SOL],
+        ..Default::default()
+    };
+    let result = env.send(&[create_market(&creator.pubkey(), market_args(1, now, params), None)], &[&creator]).await;
+    assert_error(result, DakeError::InvalidOutcomeCount);
+
+    let params = MarketParams {
+        creator_fee_bps: MAX_CREATOR_FEE_BPS + 1,
+        ..Default::default()
+    };
+    let result = env.send(&[create_market(&creator.pubkey(), market_args(1, now, params), None)], &[&creator]).await;
+    assert_error(result, DakeError::InvalidFeeBps);
+
+    let params = MarketParams {
+        kind: MarketKind::Scalar { lower_bound: 10, upper_bound: 10 },
+        ..Default::default()
+    };
+    let result = env.send(&[create_market(&creator.pubkey(), market_args(1, now, params), None)], &[&creator]).await;
+    assert_error(result, DakeError::InvalidScalarBounds);
+
+    let params = MarketParams {
+        initial_liquidity: vec![SOL],
+        ..Default::default()
+    };
+    let result = env.send(&[create_market(&creator.pubkey(), market_args(1, now, params), None)], &[&creator]).await;
+    assert_error(result, DakeError::InvalidInitialLiquidity);
+
+    // An empty liquidity list seeds every outcome with the config default
+    let params = MarketParams {
+        initial_liquidity: vec![],
+        ..Default::default()
+    };
+    env.send(&[create_market(&creator.pubkey(), market_args(1, now, params), None)], &[&creator]).await.unwrap();
+    let state: Market = env.account(&pda::market(1).0).await;
+    assert_eq!(state.initial_liquidity, vec![DEFAULT_LIQUIDITY; 2]);
+    let reserve = anchor_lang::prelude::Rent::default().minimum_balance(0);
+    assert_eq!(env.lamports(&pda::vault(&pda::market(1).0).0).await, 2 * DEFAULT_LIQUIDITY + reserve);
+}
+
+#[tokio::test]
+async fn resolution_is_gated() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let market = open_market(&mut env, &creator, 1, MarketParams::default()).await;
+
+    let result = env.send(&[resolve_market(&creator.pubkey(), &market, YES)], &[&creator]).await;
+    assert_error(result, DakeError::ResolutionTimeNotReached);
+
+    env.warp(MARKET_DURATION_SECS).await;
+    let result = env.send(&[resolve_market(&env.admin.pubkey(), &market, YES)], &[&env.admin.insecure_clone()]).await;
+    assert_error(result, DakeError::Unauthorized);
+
+    let result = env.send(&[resolve_market(&creator.pubkey(), &market, 2)], &[&creator]).await;
+    assert_error(result, DakeError::InvalidOutcome);
+
+    let result = env.send(&[resolve_scalar_market(&creator.pubkey(), &market, 5)], &[&creator]).await;
+    assert_error(result, DakeError::WrongMarketKind);
+
+    let result = env.send(&[reveal(&creator.pubkey(), &market, &[], &[])], &[&creator]).await;
+    assert_error(result, DakeError::MarketStillOpen);
+
+    env.send(&[close_market(&creator.pubkey(), &market, &[])], &[&creator]).await.unwrap();
+    let handles = env.account::<Market>(&market).await.encrypted_totals;
+
+    // Totals that don't match the handles fail verification
+    let result = env
+        .send(&[reveal(&creator.pubkey(), &market, &handles, &[SOL, 2 * SOL])], &[&creator])
+        .await;
+    assert_error(result, INVALID_DECRYPTION);
+
+    env.send(&[resolve_market(&creator.pubkey(), &market, NO)], &[&creator]).await.unwrap();
+    let result = env.send(&[resolve_market(&creator.pubkey(), &market, YES)], &[&creator]).await;
+    assert_error(result, DakeError::MarketAlreadyResolved);
+}
+
+#[tokio::test]
+async fn categorical_market_routes_bets_to_their_outcome() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let bob = env.user(10 * SOL).await;
+    let params = MarketParams {
+        outcomes: vec!["RED", "GREEN", "BLUE", "NONE"],
+        initial_liquidity: vec![0, SOL, 0, SOL],
+        ..Default::default()
+    };
+    let market = open_market(&mut env, &creator, 1, params).await;
+
+    env.send(&[bet(&alice.pubkey(), &market, 2, 3 * SOL, 0)], &[&alice]).await.unwrap();
+    env.send(&[bet(&bob.pubkey(), &market, 0, SOL, 0)], &[&bob]).await.unwrap();
+    // An out-of-range side adds to no pool and can never win
+    env.send(&[bet(&bob.pubkey(), &market, 9, SOL, 1)], &[&bob]).await.unwrap();
+
+    let totals = close_and_reveal(&mut env, &creator, &market).await;
+    assert_eq!(totals, vec![SOL, SOL, 3 * SOL, SOL]);
+
+    env.send(&[resolve_market(&creator.pubkey(), &market, 2)], &[&creator]).await.unwrap();
+    let handle = check(&mut env, &alice, &market, 0).await;
+    assert_eq!(mock_inco::decrypt(handle), 1);
+    let stray = check(&mut env, &bob, &market, 1).await;
+    assert_eq!(mock_inco::decrypt(stray), 0);
+
+    // Alice holds the whole BLUE pool and takes the whole 6 SOL pool
+    let position = pda::position(&market, &alice.pubkey(), 0).0;
+    let before = env.lamports(&alice.pubkey()).await;
+    env.send(&[claim_winnings(&alice.pubkey(), &market, &position, encode(handle), encode(1), None)], &[&alice])
+        .await
+        .unwrap();
+    // 2% fees on the 3 SOL profit
+    let payout = 6 * SOL;
+    assert_eq!(env.lamports(&alice.pubkey()).await - before, payout - 3 * SOL * 2 / 100);
+}
+
+#[tokio::test]
+async fn scalar_market_pays_both_sides() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let long = env.user(10 * SOL).await;
+    let short = env.user(10 * SOL).await;
+    let params = MarketParams {
+        outcomes: vec!["SHORT", "LONG"],
+        kind: MarketKind::Scalar { lower_bound: 0, upper_bound: 100 },
+        initial_liquidity: vec![0, 0],
+        creator_fee_bps: 0,
+        ..Default::default()
+    };
+    let market = open_market(&mut env, &creator, 1, params).await;
+
+    env.send(&[bet(&long.pubkey(), &market, 1, 2 * SOL, 0)], &[&long]).await.unwrap();
+    env.send(&[bet(&short.pubkey(), &market, 0, 2 * SOL, 0)], &[&short]).await.unwrap();
+    close_and_reveal(&mut env, &creator, &market).await;
+
+    let result = env.send(&[resolve_market(&creator.pubkey(), &market, 1)], &[&creator]).await;
+    assert_error(result, DakeError::WrongMarketKind);
+    // 75 of [0, 100]: LONG takes 3/4 of the pool, SHORT the rest
+    env.send(&[resolve_scalar_market(&creator.pubkey(), &market, 75)], &[&creator]).await.unwrap();
+
+    // LONG pays the protocol fee on its 1 SOL profit; SHORT lost money and pays none
+    for (owner, settle, net) in [
+        (&long, SCALAR_SETTLE_LONG, 3 * SOL - SOL / 100),
+        (&short, SCALAR_SETTLE_SHORT, SOL),
+    ] {
+        let handle = check(&mut env, owner, &market, 0).await;
+        assert_eq!(mock_inco::decrypt(handle), settle);
+
+        let position = pda::position(&market, &owner.pubkey(), 0).0;
+        let before = env.lamports(&owner.pubkey()).await;
+        env.send(&[claim_winnings(&owner.pubkey(), &market, &position, encode(handle), encode(settle), None)], &[owner])
+            .await
+            .unwrap();
+        assert_eq!(env.lamports(&owner.pubkey()).await - before, net);
+    }
+}
+
+#[tokio::test]
+async fn cancelled_market_refunds_stakes_and_seed() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let market = open_market(&mut env, &creator, 1, MarketParams::default()).await;
+    env.send(&[bet(&alice.pubkey(), &market, YES, 3 * SOL, 0)], &[&alice]).await.unwrap();
+    let position = pda::position(&market, &alice.pubkey(), 0).0;
+
+    let result = env.send(&[refund_position(&alice.pubkey(), &market, &position, None)], &[&alice]).await;
+    assert_error(result, DakeError::MarketNotCancelled);
+
+    let result = env.send(&[cancel_market(&alice.pubkey(), &market, None)], &[&alice]).await;
+    assert_error(result, DakeError::Unauthorized);
+
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    env.send(&[cancel_market(&creator.pubkey(), &market, None)], &[&creator]).await.unwrap();
+    assert_eq!(env.lamports(&creator.pubkey()).await - creator_before, 2 * SOL);
+    assert!(env.account::<Market>(&market).await.status == MarketStatus::Cancelled);
+
+    let alice_before = env.lamports(&alice.pubkey()).await;
+    env.send(&[refund_position(&alice.pubkey(), &market, &position, None)], &[&alice]).await.unwrap();
+    assert_eq!(env.lamports(&alice.pubkey()).await - alice_before, 3 * SOL);
+
+    let result = env.send(&[refund_position(&alice.pubkey(), &market, &position, None)], &[&alice]).await;
+    assert_error(result, DakeError::AlreadyClaimed);
+}
+
+#[tokio::test]
+async fn exit_refunds_stake_minus_fee_and_removes_it_from_the_pool() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let market = open_market(&mut env, &creator, 1, MarketParams::default()).await;
+    env.send(&[bet(&alice.pubkey(), &market, YES, 2 * SOL, 0)], &[&alice]).await.unwrap();
+    let position = pda::position(&market, &alice.pubkey(), 0).0;
+
+    let (refund, fee) = dake::instructions::exit_position::exit_quote(2 * SOL, EXIT_FEE_BPS);
+    let before = env.lamports(&alice.pubkey()).await;
+    env.send(&[exit_position(&alice.pubkey(), &market, &position, None)], &[&alice]).await.unwrap();
+
+    // The refund plus the position's rent comes back; the position is closed
+    assert!(!env.exists(&position).await);
+    assert!(env.lamports(&alice.pubkey()).await - before > refund);
+    assert_eq!(env.account::<FeeVault>(&pda::creator_fee_vault(&market).0).await.accrued, fee);
+
+    let totals = close_and_reveal(&mut env, &creator, &market).await;
+    assert_eq!(totals, vec![SOL, SOL]);
+}
+
+#[tokio::test]
+async fn settled_market_is_withdrawn_closed_and_finalized() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let admin = env.admin.insecure_clone();
+    let market = open_market(&mut env, &creator, 1, MarketParams::default()).await;
+    env.send(&[bet(&alice.pubkey(), &market, YES, SOL, 0)], &[&alice]).await.unwrap();
+    let position = pda::position(&market, &alice.pubkey(), 0).0;
+
+    let result = env.send(&[close_position(&alice.pubkey(), &market, &position)], &[&alice]).await;
+    assert_error(result, DakeError::PositionNotSettled);
+
+    close_and_reveal(&mut env, &creator, &market).await;
+    env.send(&[resolve_market(&creator.pubkey(), &market, YES)], &[&creator]).await.unwrap();
+    let handle = check(&mut env, &alice, &market, 0).await;
+    env.send(&[claim_winnings(&alice.pubkey(), &market, &position, encode(handle), encode(1), None)], &[&alice])
+        .await
+        .unwrap();
+
+    let result = env
+        .send(&[finalize_market(&creator.pubkey(), &market, &admin.pubkey(), None, None)], &[&creator])
+        .await;
+    assert_error(result, DakeError::ClaimPeriodNotOver);
+
+    env.send(&[close_position(&alice.pubkey(), &market, &position)], &[&alice]).await.unwrap();
+    assert!(!env.exists(&position).await);
+    assert_eq!(env.account::<Market>(&market).await.open_positions, 0);
+
+    let finalize = finalize_market(&creator.pubkey(), &market, &admin.pubkey(), None, None);
+    let result = env.send(std::slice::from_ref(&finalize), &[&creator]).await;
+    assert_error(result, DakeError::FeesNotWithdrawn);
+
+    let result = env.send(&[withdraw_protocol_fees(&creator.pubkey(), &market, None)], &[&creator]).await;
+    assert_error(result, DakeError::Unauthorized);
+
+    env.send(&[withdraw_creator_fees(&creator.pubkey(), &market, None)], &[&creator]).await.unwrap();
+    let result = env.send(&[withdraw_creator_fees(&creator.pubkey(), &market, None)], &[&creator]).await;
+    assert_error(result, DakeError::NoFeesToWithdraw);
+
+    // Protocol fees the admin has not withdrawn go to the admin, not the creator
+    let result = env
+        .send(&[finalize_market(&creator.pubkey(), &market, &creator.pubkey(), None, None)], &[&creator])
+        .await;
+    assert_error(result, DakeError::Unauthorized);
+
+    let fee = env.account::<FeeVault>(&pda::protocol_fee_vault(&market).0).await.accrued;
+    let before = env.lamports(&admin.pubkey()).await;
+    env.send(&[finalize], &[&creator]).await.unwrap();
+    assert_eq!(env.lamports(&admin.pubkey()).await - before, fee);
+    assert!(!env.exists(&market).await);
+    assert!(!env.exists(&pda::vault(&market).0).await);
+    assert!(!env.exists(&pda::protocol_fee_vault(&market).0).await);
+}
+
+#[tokio::test]
+async fn markets_with_unrevealed_pools_are_not_finalized() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let market = open_market(&mut env, &creator, 1, MarketParams::default()).await;
+    env.warp(MARKET_DURATION_SECS).await;
+    env.send(&[close_market(&creator.pubkey(), &market, &[])], &[&creator]).await.unwrap();
+    env.send(&[resolve_market(&creator.pubkey(), &market, YES)], &[&creator]).await.unwrap();
+
+    let finalize = finalize_market(&creator.pubkey(), &market, &env.admin.pubkey(), None, None);
+    let result = env.send(std::slice::from_ref(&finalize), &[&creator]).await;
+    assert_error(result, DakeError::PoolsNotRevealed);
+
+    let handles = env.account::<Market>(&market).await.encrypted_totals;
+    env.send(&[reveal(&creator.pubkey(), &market, &handles, &[SOL, SOL])], &[&creator])
+        .await
+        .unwrap();
+    env.send(&[finalize], &[&creator]).await.unwrap();
+    assert!(!env.exists(&market).await);
+}
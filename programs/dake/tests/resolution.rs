@@ -0,0 +1,623 @@
+mod common;
+
+use anchor_lang::prelude::Pubkey;
+use common::*;
+use dake::error::DakeError;
+use dake::oracle::{PRICE_UPDATE_V2_DISCRIMINATOR, PYTH_RECEIVER_ID};
+use dake::state::{
+    Market, MarketStatus, OracleComparator, OracleConfig, ResolutionSource, ARBITRATION_WINDOW_SECS,
+};
+use dake_client::instructions::*;
+use dake_client::pda;
+use solana_sdk::account::Account as SolanaAccount;
+use solana_sdk::signature::{Keypair, Signer};
+
+const VOTE_WINDOW_SECS: u32 = 3_600;
+
+fn optimistic() -> MarketParams {
+    MarketParams {
+        resolution: ResolutionSource::Optimistic,
+        ..MarketParams::default()
+    }
+}
+
+/// Propose `outcome` on an optimistic market once its resolution time has passed
+async fn propose(env: &mut TestEnv, proposer: &Keypair, market: &Pubkey, outcome: u8) {
+    let created_at = env.account::<Market>(market).await.created_at;
+    env.send(&[propose_resolution(&proposer.pubkey(), market, created_at, outcome, 0)], &[proposer])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn undisputed_proposal_finalizes_after_the_window() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let proposer = env.user(SOL).await;
+    let disputer = env.user(SOL).await;
+    let market = open_market(&mut env, &creator, 1, optimistic()).await;
+    let created_at = env.account::<Market>(&market).await.created_at;
+
+    let result = env
+        .send(&[propose_resolution(&proposer.pubkey(), &market, created_at, YES, 0)], &[&proposer])
+        .await;
+    assert_error(result, DakeError::ResolutionTimeNotReached);
+
+    env.warp(MARKET_DURATION_SECS).await;
+    let result = env
+        .send(&[propose_resolution(&proposer.pubkey(), &market, created_at, 2, 0)], &[&proposer])
+        .await;
+    assert_error(result, DakeError::InvalidOutcome);
+
+    let before = env.lamports(&proposer.pubkey()).await;
+    propose(&mut env, &proposer, &market, YES).await;
+    assert!(env.account::<Market>(&market).await.status == MarketStatus::PendingResolution);
+
+    // The authority can no longer resolve it directly
+    let result = env
+        .send(&[resolve_market(&creator.pubkey(), &market, NO)], &[&creator])
+        .await;
+    assert!(result.is_err());
+
+    let result = env
+        .send(&[finalize_resolution(&disputer.pubkey(), &market, created_at, &proposer.pubkey())], &[&disputer])
+        .await;
+    assert_error(result, DakeError::DisputeWindowOpen);
+
+    env.warp(DISPUTE_WINDOW_SECS as i64).await;
+    let result = env
+        .send(&[dispute_resolution(&disputer.pubkey(), &market, created_at)], &[&disputer])
+        .await;
+    assert_error(result, DakeError::DisputeWindowClosed);
+
+    env.send(&[finalize_resolution(&disputer.pubkey(), &market, created_at, &proposer.pubkey())], &[&disputer])
+        .await
+        .unwrap();
+
+    let state: Market = env.account(&market).await;
+    assert!(state.status == MarketStatus::Resolved);
+    assert_eq!(state.winning_outcome, YES);
+
+    // Closing the proposal returns the bond and its rent
+    assert!(!env.exists(&pda::proposal(&market, created_at).0).await);
+    assert_eq!(env.lamports(&proposer.pubkey()).await, before);
+}
+
+#[tokio::test]
+async fn overturned_proposal_pays_both_bonds_to_the_disputer() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let proposer = env.user(SOL).await;
+    let disputer = env.user(SOL).await;
+    let outsider = env.user(SOL).await;
+    let market = open_market(&mut env, &creator, 1, optimistic()).await;
+    let created_at = env.account::<Market>(&market).await.created_at;
+    env.warp(MARKET_DURATION_SECS).await;
+
+    let proposer_before = env.lamports(&proposer.pubkey()).await;
+    let disputer_before = env.lamports(&disputer.pubkey()).await;
+    propose(&mut env, &proposer, &market, YES).await;
+    env.send(&[dispute_resolution(&disputer.pubkey(), &market, created_at)], &[&disputer])
+        .await
+        .unwrap();
+    assert_eq!(
+        env.lamports(&disputer.pubkey()).await,
+        disputer_before - RESOLUTION_BOND
+    );
+
+    let result = env
+        .send(&[dispute_resolution(&outsider.pubkey(), &market, created_at)], &[&outsider])
+        .await;
+    assert_error(result, DakeError::AlreadyDisputed);
+
+    // Disputed proposals can only be settled by the arbitrator
+    env.warp(DISPUTE_WINDOW_SECS as i64).await;
+    let result = env
+        .send(&[finalize_resolution(&outsider.pubkey(), &market, created_at, &proposer.pubkey())], &[&outsider])
+        .await;
+    assert_error(result, DakeError::ResolutionDisputed);
+
+    let (proposer_key, disputer_key) = (proposer.pubkey(), disputer.pubkey());
+    let result = env
+        .send(
+            &[arbitrate_resolution(&outsider.pubkey(), &market, created_at, &proposer_key, &disputer_key, NO, 0)],
+            &[&outsider],
+        )
+        .await;
+    assert_error(result, DakeError::Unauthorized);
+
+    let admin = env.admin.insecure_clone();
+    let result = env
+        .send(
+            &[arbitrate_resolution(&admin.pubkey(), &market, created_at, &proposer_key, &outsider.pubkey(), NO, 0)],
+            &[&admin],
+        )
+        .await;
+    assert_error(result, DakeError::NotDisputed);
+
+    env.send(
+        &[arbitrate_resolution(&admin.pubkey(), &market, created_at, &proposer_key, &disputer_key, NO, 0)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+
+    let state: Market = env.account(&market).await;
+    assert!(state.status == MarketStatus::Resolved);
+    assert_eq!(state.winning_outcome, NO);
+
+    // The proposer only gets the proposal's rent back
+    assert_eq!(env.lamports(&proposer_key).await, proposer_before - RESOLUTION_BOND);
+    assert_eq!(env.lamports(&disputer_key).await, disputer_before + RESOLUTION_BOND);
+    assert!(!env.exists(&pda::proposal(&market, created_at).0).await);
+}
+
+#[tokio::test]
+async fn upheld_proposal_pays_both_bonds_to_the_proposer() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let proposer = env.user(SOL).await;
+    let disputer = env.user(SOL).await;
+    let market = open_market(&mut env, &creator, 1, optimistic()).await;
+    let created_at = env.account::<Market>(&market).await.created_at;
+    env.warp(MARKET_DURATION_SECS).await;
+
+    let proposer_before = env.lamports(&proposer.pubkey()).await;
+    let disputer_before = env.lamports(&disputer.pubkey()).await;
+    propose(&mut env, &proposer, &market, YES).await;
+    env.send(&[dispute_resolution(&disputer.pubkey(), &market, created_at)], &[&disputer])
+        .await
+        .unwrap();
+
+    let admin = env.admin.insecure_clone();
+    env.send(
+        &[arbitrate_resolution(&admin.pubkey(), &market, created_at, &proposer.pubkey(), &disputer.pubkey(), YES, 0)],
+        &[&admin],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(env.account::<Market>(&market).await.winning_outcome, YES);
+    assert_eq!(env.lamports(&proposer.pubkey()).await, proposer_before + RESOLUTION_BOND);
+    assert_eq!(env.lamports(&disputer.pubkey()).await, disputer_before - RESOLUTION_BOND);
+}
+
+#[tokio::test]
+async fn unarbitrated_dispute_voids_the_market_after_the_deadline() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let proposer = env.user(SOL).await;
+    let disputer = env.user(SOL).await;
+    let caller = env.user(SOL).await;
+    let market = open_market(&mut env, &creator, 1, optimistic()).await;
+    let created_at = env.account::<Market>(&market).await.created_at;
+    env.send(&[bet(&alice.pubkey(), &market, YES, SOL, 0)], &[&alice]).await.unwrap();
+    env.warp(MARKET_DURATION_SECS).await;
+
+    let proposer_before = env.lamports(&proposer.pubkey()).await;
+    let disputer_before = env.lamports(&disputer.pubkey()).await;
+    propose(&mut env, &proposer, &market, YES).await;
+    env.send(&[dispute_resolution(&disputer.pubkey(), &market, created_at)], &[&disputer])
+        .await
+        .unwrap();
+
+    let (proposer_key, disputer_key) = (proposer.pubkey(), disputer.pubkey());
+    let expire = expire_arbitration(&caller.pubkey(), &market, created_at, &creator.pubkey(), &proposer_key, &disputer_key, None);
+    let result = env.send(std::slice::from_ref(&expire), &[&caller]).await;
+    assert_error(result, DakeError::ArbitrationStillOpen);
+
+    // Once the deadline passes the arbitrator can no longer rule
+    env.warp(ARBITRATION_WINDOW_SECS).await;
+    let admin = env.admin.insecure_clone();
+    let result = env
+        .send(
+            &[arbitrate_resolution(&admin.pubkey(), &market, created_at, &proposer_key, &disputer_key, NO, 0)],
+            &[&admin],
+        )
+        .await;
+    assert_error(result, DakeError::ArbitrationDeadlinePassed);
+
+    // Anyone can void the market: seed back to the authority, each bond to its poster
+    let creator_before = env.lamports(&creator.pubkey()).await;
+    env.send(&[expire], &[&caller]).await.unwrap();
+    assert!(env.account::<Market>(&market).await.status == MarketStatus::Cancelled);
+    assert_eq!(env.lamports(&creator.pubkey()).await - creator_before, 2 * SOL);
+    assert_eq!(env.lamports(&proposer_key).await, proposer_before);
+    assert_eq!(env.lamports(&disputer_key).await, disputer_before);
+    assert!(!env.exists(&pda::proposal(&market, created_at).0).await);
+
+    // Bets are refunded
+    let position = pda::position(&market, &alice.pubkey(), 0).0;
+    let before = env.lamports(&alice.pubkey()).await;
+    env.send(&[refund_position(&alice.pubkey(), &market, &position, None)], &[&alice]).await.unwrap();
+    assert_eq!(env.lamports(&alice.pubkey()).await - before, SOL);
+}
+
+#[tokio::test]
+async fn proposals_require_an_optimistic_market() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let proposer = env.user(SOL).await;
+    let market = open_market(&mut env, &creator, 1, MarketParams::default()).await;
+    let created_at = env.account::<Market>(&market).await.created_at;
+    env.warp(MARKET_DURATION_SECS).await;
+
+    let result = env
+        .send(&[propose_resolution(&proposer.pubkey(), &market, created_at, YES, 0)], &[&proposer])
+        .await;
+    assert_error(result, DakeError::WrongResolutionSource);
+}
+
+#[tokio::test]
+async fn trustless_markets_settle_without_their_creator() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let alice = env.user(10 * SOL).await;
+    let keeper = env.user(SOL).await;
+    let market = open_market(&mut env, &creator, 1, optimistic()).await;
+    env.send(&[bet(&alice.pubkey(), &market, YES, SOL, 0)], &[&alice]).await.unwrap();
+
+    // Once someone has bet, the creator cannot void the market
+    let result = env.send(&[cancel_market(&creator.pubkey(), &market, None)], &[&creator]).await;
+    assert_error(result, DakeError::CancelNotAllowed);
+
+    let result = env.send(&[close_market(&keeper.pubkey(), &market, &[])], &[&keeper]).await;
+    assert_error(result, DakeError::Unauthorized);
+    let handles = env.account::<Market>(&market).await.encrypted_totals;
+    let result = env.send(&[grant_pool_access(&keeper.pubkey(), &market, &handles)], &[&keeper]).await;
+    assert_error(result, DakeError::MarketStillOpen);
+
+    // After the betting cutoff anyone can close, get decrypt access and reveal
+    env.warp(MARKET_DURATION_SECS).await;
+    env.send(&[close_market(&keeper.pubkey(), &market, &[])], &[&keeper]).await.unwrap();
+    let handles = env.account::<Market>(&market).await.encrypted_totals;
+    let result = env.send(&[grant_pool_access(&alice.pubkey(), &market, &handles[..1])], &[&alice]).await;
+    assert_error(result, DakeError::MissingAllowanceAccounts);
+    // Each allowance account is followed by its grantee, which must be the caller
+    let mut for_keeper = grant_pool_access(&alice.pubkey(), &market, &handles);
+    for_keeper.accounts[5].pubkey = keeper.pubkey();
+    let result = env.send(&[for_keeper], &[&alice]).await;
+    assert_error(result, DakeError::GranteeNotCaller);
+    env.send(&[grant_pool_access(&alice.pubkey(), &market, &handles)], &[&alice]).await.unwrap();
+    env.send(&[reveal(&alice.pubkey(), &market, &handles, &[SOL, 2 * SOL])], &[&alice])
+        .await
+        .unwrap();
+
+    let state: Market = env.account(&market).await;
+    assert!(state.pools_revealed);
+    assert_eq!(state.outcome_totals, vec![SOL, 2 * SOL]);
+}
+
+/// Create a committee of `members` (as `authority`) and a market resolved by it
+async fn committee_market(env: &mut TestEnv, authority: &Keypair, members: &[&Keypair], threshold: u8) -> (Pubkey, Pubkey) {
+    let keys = members.iter().map(|member| member.pubkey()).collect();
+    env.send(&[create_resolver_set(&authority.pubkey(), 0, keys, threshold)], &[authority])
+        .await
+        .unwrap();
+    let resolver_set = pda::resolver_set(&authority.pubkey(), 0).0;
+
+    let params = MarketParams {
+        resolution: ResolutionSource::Committee {
+            resolver_set,
+            vote_window_secs: VOTE_WINDOW_SECS,
+        },
+        ..MarketParams::default()
+    };
+    let market = open_market(env, authority, 1, params).await;
+    (market, resolver_set)
+}
+
+#[tokio::test]
+async fn resolver_sets_are_validated() {
+    let mut env = TestEnv::new().await;
+    let authority = env.user(10 * SOL).await;
+    let (a, b, c, d) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+    // A threshold of half the members or less would let two outcomes both pass
+    for (members, threshold) in [
+        (vec![a, b], 0),
+        (vec![a, b], 1),
+        (vec![a, b, c, d], 2),
+        (vec![a, b], 3),
+        (vec![a, a], 1),
+        (vec![], 1),
+    ] {
+        let result = env
+            .send(&[create_resolver_set(&authority.pubkey(), 0, members, threshold)], &[&authority])
+            .await;
+        assert_error(result, DakeError::InvalidResolverSet);
+    }
+}
+
+#[tokio::test]
+async fn committee_resolves_once_the_threshold_agrees() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let members = [env.user(SOL).await, env.user(SOL).await, env.user(SOL).await];
+    let outsider = env.user(SOL).await;
+    let (market, resolver_set) =
+        committee_market(&mut env, &creator, &[&members[0], &members[1], &members[2]], 2).await;
+    let created_at = env.account::<Market>(&market).await.created_at;
+
+    let vote = |member: &Keypair, outcome: u8| vote_resolution(&member.pubkey(), &market, created_at, &resolver_set, outcome, 0);
+
+    let result = env.send(&[vote(&members[0], YES)], &[&members[0]]).await;
+    assert_error(result, DakeError::ResolutionTimeNotReached);
+
+    env.warp(MARKET_DURATION_SECS).await;
+    let result = env.send(&[vote(&outsider, YES)], &[&outsider]).await;
+    assert_error(result, DakeError::NotResolver);
+
+    env.send(&[vote(&members[0], YES)], &[&members[0]]).await.unwrap();
+    let result = env.send(&[vote(&members[0], YES)], &[&members[0]]).await;
+    assert_error(result, DakeError::AlreadyVoted);
+
+    // Conflicting votes don't count towards each other
+    env.send(&[vote(&members[1], NO)], &[&members[1]]).await.unwrap();
+    assert!(env.account::<Market>(&market).await.status == MarketStatus::Open);
+
+    env.send(&[vote(&members[2], YES)], &[&members[2]]).await.unwrap();
+    let state: Market = env.account(&market).await;
+    assert!(state.status == MarketStatus::Resolved);
+    assert_eq!(state.winning_outcome, YES);
+
+    let result = env
+        .send(&[expire_resolution_vote(&outsider.pubkey(), &market, &creator.pubkey(), None)], &[&outsider])
+        .await;
+    assert_error(result, DakeError::MarketAlreadyResolved);
+}
+
+#[tokio::test]
+async fn split_committee_never_resolves() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let members = [env.user(SOL).await, env.user(SOL).await, env.user(SOL).await, env.user(SOL).await];
+    let caller = env.user(SOL).await;
+    let (market, resolver_set) = committee_market(
+        &mut env,
+        &creator,
+        &[&members[0], &members[1], &members[2], &members[3]],
+        3,
+    )
+    .await;
+    let created_at = env.account::<Market>(&market).await.created_at;
+    env.warp(MARKET_DURATION_SECS).await;
+
+    for (member, outcome) in members.iter().zip([YES, NO, YES, NO]) {
+        env.send(
+            &[vote_resolution(&member.pubkey(), &market, created_at, &resolver_set, outcome, 0)],
+            &[member],
+        )
+        .await
+        .unwrap();
+        assert!(env.account::<Market>(&market).await.status == MarketStatus::Open);
+    }
+
+    // A 2-2 split leaves the market to be voided once the window closes
+    env.warp(VOTE_WINDOW_SECS as i64).await;
+    env.send(&[expire_resolution_vote(&caller.pubkey(), &market, &creator.pubkey(), None)], &[&caller])
+        .await
+        .unwrap();
+    assert!(env.account::<Market>(&market).await.status == MarketStatus::Cancelled);
+}
+
+#[tokio::test]
+async fn expired_committee_vote_cancels_the_market() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let members = [env.user(SOL).await, env.user(SOL).await];
+    let caller = env.user(SOL).await;
+    let (market, resolver_set) = committee_market(&mut env, &creator, &[&members[0], &members[1]], 2).await;
+    let created_at = env.account::<Market>(&market).await.created_at;
+    env.warp(MARKET_DURATION_SECS).await;
+
+    env.send(
+        &[vote_resolution(&members[0].pubkey(), &market, created_at, &resolver_set, YES, 0)],
+        &[&members[0]],
+    )
+    .await
+    .unwrap();
+
+    let expire = expire_resolution_vote(&caller.pubkey(), &market, &creator.pubkey(), None);
+    let result = env.send(std::slice::from_ref(&expire), &[&caller]).await;
+    assert_error(result, DakeError::VoteStillOpen);
+
+    env.warp(VOTE_WINDOW_SECS as i64).await;
+    let result = env
+        .send(
+            &[vote_resolution(&members[1].pubkey(), &market, created_at, &resolver_set, YES, 0)],
+            &[&members[1]],
+        )
+        .await;
+    assert_error(result, DakeError::VoteDeadlinePassed);
+
+    // The seed liquidity goes back to the authority
+    let before = env.lamports(&creator.pubkey()).await;
+    env.send(&[expire], &[&caller]).await.unwrap();
+    assert!(env.account::<Market>(&market).await.status == MarketStatus::Cancelled);
+    assert_eq!(env.lamports(&creator.pubkey()).await - before, 2 * SOL);
+}
+
+#[tokio::test]
+async fn recreated_market_starts_with_fresh_votes() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let members = [env.user(SOL).await, env.user(SOL).await];
+    let (market, resolver_set) = committee_market(&mut env, &creator, &[&members[0], &members[1]], 2).await;
+    let created_at = env.account::<Market>(&market).await.created_at;
+    env.warp(MARKET_DURATION_SECS).await;
+    env.send(
+        &[vote_resolution(&members[0].pubkey(), &market, created_at, &resolver_set, YES, 0)],
+        &[&members[0]],
+    )
+    .await
+    .unwrap();
+
+    env.warp(VOTE_WINDOW_SECS as i64).await;
+    env.send(&[expire_resolution_vote(&creator.pubkey(), &market, &creator.pubkey(), None)], &[&creator])
+        .await
+        .unwrap();
+    env.send(&[finalize_market(&creator.pubkey(), &market, &env.admin.pubkey(), None, None)], &[&creator]).await.unwrap();
+
+    // Same id, same PDA - but the old votes belong to the previous instance
+    let params = MarketParams {
+        resolution: ResolutionSource::Committee {
+            resolver_set,
+            vote_window_secs: VOTE_WINDOW_SECS,
+        },
+        ..MarketParams::default()
+    };
+    let market = open_market(&mut env, &creator, 1, params).await;
+    let recreated_at = env.account::<Market>(&market).await.created_at;
+    assert_ne!(pda::votes(&market, recreated_at).0, pda::votes(&market, created_at).0);
+
+    env.warp(MARKET_DURATION_SECS).await;
+    for member in &members {
+        env.send(
+            &[vote_resolution(&member.pubkey(), &market, recreated_at, &resolver_set, NO, 0)],
+            &[member],
+        )
+        .await
+        .unwrap();
+    }
+    let state: Market = env.account(&market).await;
+    assert!(state.status == MarketStatus::Resolved);
+    assert_eq!(state.winning_outcome, NO);
+}
+
+/// Pyth feed id the oracle markets below are configured with
+const FEED_ID: [u8; 32] = [7u8; 32];
+
+/// PriceUpdateV2 account data for `feed_id`, fully verified
+fn price_update_for(feed_id: [u8; 32], price: i64, conf: u64, exponent: i32, publish_time: i64) -> Vec<u8> {
+    let mut data = PRICE_UPDATE_V2_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(Pubkey::new_unique().as_ref());
+    data.push(1);
+    data.extend_from_slice(&feed_id);
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(&conf.to_le_bytes());
+    data.extend_from_slice(&exponent.to_le_bytes());
+    data.extend_from_slice(&publish_time.to_le_bytes());
+    data.extend_from_slice(&(publish_time - 1).to_le_bytes());
+    data.extend_from_slice(&price.to_le_bytes());
+    data.extend_from_slice(&conf.to_le_bytes());
+    data.extend_from_slice(&42u64.to_le_bytes());
+    data
+}
+
+fn price_update(price: i64, conf: u64, exponent: i32, publish_time: i64) -> Vec<u8> {
+    price_update_for(FEED_ID, price, conf, exponent, publish_time)
+}
+
+fn oracle_config(feed: Pubkey, evaluation_time: i64) -> OracleConfig {
+    // "Will the price be above 150.00000000?"
+    OracleConfig {
+        feed,
+        feed_id: FEED_ID,
+        comparator: OracleComparator::GreaterThan,
+        threshold: 150_00000000,
+        upper_threshold: 0,
+        exponent: -8,
+        evaluation_time,
+        max_staleness_secs: 60,
+        max_confidence_bps: 100,
+    }
+}
+
+fn price_feed(owner: Pubkey, data: Vec<u8>) -> SolanaAccount {
+    SolanaAccount {
+        lamports: SOL,
+        data,
+        owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[tokio::test]
+async fn oracle_market_resolves_from_a_fresh_price() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let resolver = env.user(SOL).await;
+    let feed = Pubkey::new_unique();
+    let evaluation_time = env.now().await + MARKET_DURATION_SECS;
+
+    let params = MarketParams {
+        resolution: ResolutionSource::Oracle(oracle_config(feed, evaluation_time)),
+        ..MarketParams::default()
+    };
+    let market = open_market(&mut env, &creator, 1, params).await;
+    env.set_account(&feed, price_feed(PYTH_RECEIVER_ID, price_update(175_00000000, 1_00000000, -8, evaluation_time)));
+
+    let result = env.send(&[resolve_from_oracle(&resolver.pubkey(), &market, &feed)], &[&resolver]).await;
+    assert_error(result, DakeError::ResolutionTimeNotReached);
+    env.warp(MARKET_DURATION_SECS).await;
+
+    // Only the configured feed, owned by the Pyth receiver, is accepted
+    let other = Pubkey::new_unique();
+    env.set_account(&other, price_feed(PYTH_RECEIVER_ID, price_update(175_00000000, 1_00000000, -8, evaluation_time)));
+    let result = env.send(&[resolve_from_oracle(&resolver.pubkey(), &market, &other)], &[&resolver]).await;
+    assert_error(result, DakeError::InvalidOracleAccount);
+
+    let spoofed = Pubkey::new_unique();
+    env.set_account(&spoofed, price_feed(Pubkey::new_unique(), price_update(175_00000000, 1_00000000, -8, evaluation_time)));
+    let result = env.send(&[resolve_from_oracle(&resolver.pubkey(), &market, &spoofed)], &[&resolver]).await;
+    assert_error(result, DakeError::InvalidOracleAccount);
+
+    // Prices published too far from evaluation_time are rejected
+    env.set_account(&feed, price_feed(PYTH_RECEIVER_ID, price_update(175_00000000, 1_00000000, -8, evaluation_time + 61)));
+    let result = env.send(&[resolve_from_oracle(&resolver.pubkey(), &market, &feed)], &[&resolver]).await;
+    assert_error(result, DakeError::StaleOraclePrice);
+
+    env.set_account(&feed, price_feed(PYTH_RECEIVER_ID, price_update(175_00000000, 1_00000000, -8, evaluation_time)));
+    env.send(&[resolve_from_oracle(&resolver.pubkey(), &market, &feed)], &[&resolver])
+        .await
+        .unwrap();
+
+    let state: Market = env.account(&market).await;
+    assert!(state.status == MarketStatus::Resolved);
+    assert_eq!(state.winning_outcome, YES);
+}
+
+#[tokio::test]
+async fn oracle_market_rejects_a_price_for_another_feed() {
+    let mut env = TestEnv::new().await;
+    let creator = env.user(10 * SOL).await;
+    let resolver = env.user(SOL).await;
+    let feed = Pubkey::new_unique();
+    let evaluation_time = env.now().await + MARKET_DURATION_SECS;
+
+    // A feed id is required to create the market
+    let unpinned = OracleConfig {
+        feed_id: [0; 32],
+        ..oracle_config(feed, evaluation_time)
+    };
+    let params = MarketParams {
+        resolution: ResolutionSource::Oracle(unpinned),
+        ..MarketParams::default()
+    };
+    let now = env.now().await;
+    let result = env.send(&[create_market(&creator.pubkey(), market_args(1, now, params), None)], &[&creator]).await;
+    assert_error(result, DakeError::InvalidOracleConfig);
+
+    let params = MarketParams {
+        resolution: ResolutionSource::Oracle(oracle_config(feed, evaluation_time)),
+        ..MarketParams::default()
+    };
+    let market = open_market(&mut env, &creator, 1, params).await;
+    env.warp(MARKET_DURATION_SECS).await;
+
+    // A verified update in the configured account, but for a different asset
+    let other_asset = price_update_for([8u8; 32], 175_00000000, 1_00000000, -8, evaluation_time);
+    env.set_account(&feed, price_feed(PYTH_RECEIVER_ID, other_asset));
+    let result = env.send(&[resolve_from_oracle(&resolver.pubkey(), &market, &feed)], &[&resolver]).await;
+    assert_error(result, DakeError::InvalidOracleAccount);
+    assert!(env.account::<Market>(&market).await.status != MarketStatus::Resolved);
+
+    env.set_account(&feed, price_feed(PYTH_RECEIVER_ID, price_update(175_00000000, 1_00000000, -8, evaluation_time)));
+    env.send(&[resolve_from_oracle(&resolver.pubkey(), &market, &feed)], &[&resolver])
+        .await
+        .unwrap();
+    assert_eq!(env.account::<Market>(&market).await.winning_outcome, YES);
+}
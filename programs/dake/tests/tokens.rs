@@ -0,0 +1,153 @@
+mod common;
+
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::Pubkey;
+use common::mock_inco;
+use common::*;
+use dake::error::DakeError;
+use dake::state::{FeeVault, Market, MarketStatus};
+use dake_client::instructions::*;
+use dake_client::pda;
+use solana_sdk::signature::{Keypair, Signer};
+
+/// A wallet with SOL for rent and fees plus a token account holding 10 tokens
+async fn holder(env: &mut TestEnv, collateral: &Collateral) -> (Keypair, Pubkey) {
+    let wallet = env.user(SOL).await;
+    let account = env.token_account(collateral, &wallet.pubkey(), 10 * SOL).await;
+    (wallet, account)
+}
+
+async fn token_market(env: &mut TestEnv, collateral: &Collateral, creator: &Keypair, creator_account: &Pubkey) -> Pubkey {
+    let market = pda::market(1).0;
+    let now = env.now().await;
+    let args = market_args(1, now, MarketParams::default());
+    env.send(&[create_market(&creator.pubkey(), args, Some(collateral.accounts(creator_account)))], &[creator])
+        .await
+        .unwrap();
+    market
+}
+
+async fn bet(env: &mut TestEnv, collateral: &Collateral, market: &Pubkey, bettor: &(Keypair, Pubkey), outcome: u8, amount: u64) {
+    let token = Some(collateral.accounts(&bettor.1));
+    let ix = place_bet(&bettor.0.pubkey(), market, encrypted_side(outcome), amount, 0, 0, token, None);
+    env.send(&[ix], &[&bettor.0])
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn spl_token_market_pays_winners_and_finalizes() {
+    let mut env = TestEnv::new().await;
+    let collateral = env.create_mint(anchor_spl::token::ID, None).await;
+    env.allow_mints(vec![collateral.mint]).await;
+    let admin = env.admin.insecure_clone();
+    let admin_account = env.token_account(&collateral, &admin.pubkey(), 0).await;
+    let (creator, creator_account) = holder(&mut env, &collateral).await;
+    let alice = holder(&mut env, &collateral).await;
+    let bob = holder(&mut env, &collateral).await;
+
+    let market = token_market(&mut env, &collateral, &creator, &creator_account).await;
+    assert_eq!(env.token_balance(&pda::vault_token(&market).0).await, 2 * SOL);
+
+    bet(&mut env, &collateral, &market, &alice, YES, 2 * SOL).await;
+    bet(&mut env, &collateral, &market, &bob, NO, SOL).await;
+    assert_eq!(env.token_balance(&alice.1).await, 8 * SOL);
+
+    let totals = close_and_reveal(&mut env, &creator, &market).await;
+    assert_eq!(totals, vec![2 * SOL, 3 * SOL]);
+    env.send(&[resolve_market(&creator.pubkey(), &market, YES)], &[&creator]).await.unwrap();
+
+    let handle = check(&mut env, &alice.0, &market, 0).await;
+    assert_eq!(mock_inco::decrypt(handle), 1);
+    let position = pda::position(&market, &alice.0.pubkey(), 0).0;
+    let token = Some(collateral.accounts(&alice.1));
+    let claim = claim_winnings(&alice.0.pubkey(), &market, &position, encode(handle), encode(1), token);
+    env.send(&[claim], &[&alice.0])
+        .await
+        .unwrap();
+
+    let payout = 2 * SOL * 5 / 3;
+    let protocol_fee = env.account::<FeeVault>(&pda::protocol_fee_vault(&market).0).await.accrued;
+    let creator_fee = env.account::<FeeVault>(&pda::creator_fee_vault(&market).0).await.accrued;
+    assert_eq!(env.token_balance(&alice.1).await, 8 * SOL + payout - protocol_fee - creator_fee);
+
+    for (owner, _) in [&alice, &bob] {
+        let position = pda::position(&market, &owner.pubkey(), 0).0;
+        env.send(&[close_position(&owner.pubkey(), &market, &position)], &[owner])
+            .await
+            .unwrap();
+    }
+
+    let withdraw = withdraw_creator_fees(&creator.pubkey(), &market, Some(collateral.accounts(&creator_account)));
+    env.send(&[withdraw], &[&creator])
+        .await
+        .unwrap();
+
+    // The admin gets the protocol fees it did not withdraw, the creator what the
+    // winners did not take, and the vault is closed
+    let token = Some(collateral.accounts(&creator_account));
+    let finalize = finalize_market(&creator.pubkey(), &market, &admin.pubkey(), token, Some(admin_account));
+    env.send(&[finalize], &[&creator])
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(&admin_account).await, protocol_fee);
+    assert_eq!(env.token_balance(&creator_account).await, 8 * SOL + creator_fee + 5 * SOL - payout);
+    assert!(!env.exists(&pda::vault_token(&market).0).await);
+    assert!(!env.exists(&market).await);
+}
+
+#[tokio::test]
+async fn token_2022_market_refunds_stakes_after_cancel() {
+    let mut env = TestEnv::new().await;
+    let collateral = env.create_mint(anchor_spl::token_2022::ID, None).await;
+    env.allow_mints(vec![collateral.mint]).await;
+    let (creator, creator_account) = holder(&mut env, &collateral).await;
+    let alice = holder(&mut env, &collateral).await;
+
+    let market = token_market(&mut env, &collateral, &creator, &creator_account).await;
+    bet(&mut env, &collateral, &market, &alice, YES, 3 * SOL).await;
+
+    // The seed refund cannot be redirected to someone else's token account
+    let cancel = |account| cancel_market(&creator.pubkey(), &market, Some(collateral.accounts(account)));
+    let result = env.send(&[cancel(&alice.1)], &[&creator]).await;
+    assert_error(result, ErrorCode::ConstraintTokenOwner);
+    env.send(&[cancel(&creator_account)], &[&creator])
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(&creator_account).await, 10 * SOL);
+    assert!(env.account::<Market>(&market).await.status == MarketStatus::Cancelled);
+
+    let position = pda::position(&market, &alice.0.pubkey(), 0).0;
+    let refund = |account| refund_position(&alice.0.pubkey(), &market, &position, Some(collateral.accounts(account)));
+    let result = env.send(&[refund(&creator_account)], &[&alice.0]).await;
+    assert_error(result, DakeError::InvalidTokenAccountOwner);
+    env.send(&[refund(&alice.1)], &[&alice.0])
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(&alice.1).await, 10 * SOL);
+
+    env.send(&[close_position(&alice.0.pubkey(), &market, &position)], &[&alice.0])
+        .await
+        .unwrap();
+    let token = Some(collateral.accounts(&creator_account));
+    let finalize = finalize_market(&creator.pubkey(), &market, &env.admin.pubkey(), token, None);
+    env.send(&[finalize], &[&creator])
+        .await
+        .unwrap();
+    assert!(!env.exists(&pda::vault_token(&market).0).await);
+    assert!(!env.exists(&market).await);
+}
+
+#[tokio::test]
+async fn transfer_fee_mints_are_rejected() {
+    let mut env = TestEnv::new().await;
+    let collateral = env.create_mint(anchor_spl::token_2022::ID, Some(100)).await;
+    env.allow_mints(vec![collateral.mint]).await;
+    let (creator, creator_account) = holder(&mut env, &collateral).await;
+
+    let now = env.now().await;
+    let token = Some(collateral.accounts(&creator_account));
+    let ix = create_market(&creator.pubkey(), market_args(1, now, MarketParams::default()), token);
+    let result = env.send(&[ix], &[&creator]).await;
+    assert_error(result, DakeError::UnsupportedMintExtension);
+}
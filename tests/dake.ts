@@ -42,8 +42,28 @@ describe("dake", () => {
   // Market parameters
   const marketId = Math.floor(Date.now() / 1000);
   const QUESTION = "Will SOL hit $500 by March 2026?";
-  const RESOLUTION_TIME = Math.floor(Date.now() / 1000) + 86400; // 24 hours from now
+  const OUTCOMES = ["NO", "YES"];
+  const CATEGORICAL = { categorical: {} };
+  const AUTHORITY_RESOLVED = { authority: {} };
+  const INITIAL_LIQUIDITY = [
+    new anchor.BN(500_000_000),
+    new anchor.BN(500_000_000),
+  ]; // 0.5 SOL per side
+  // Betting closes and resolution opens shortly after each market is created
+  const RESOLUTION_DELAY_SECS = 45;
   const BET_AMOUNT = 50_000_000; // 0.05 SOL
+  const CREATOR_FEE_BPS = 100; // 1% of winnings
+  const CONFIG_PARAMS = {
+    protocolFeeBps: 100, // 1% of winnings
+    maxCreatorFeeBps: 500,
+    exitFeeBps: 200, // 2% of the stake on early exits
+    minBet: new anchor.BN(1_000_000), // 0.001 SOL
+    maxBet: new anchor.BN(0), // no limit
+    defaultLiquidity: new anchor.BN(500_000_000),
+    allowedMints: [] as PublicKey[],
+    resolutionBond: new anchor.BN(10_000_000), // 0.01 SOL
+    disputeWindowSecs: 10,
+  };
 
   // User's bet: YES (1) or NO (0)
   const MY_SIDE = 1; // Betting YES
@@ -51,10 +71,33 @@ describe("dake", () => {
   let marketPda: PublicKey;
   let vaultPda: PublicKey;
   let positionPda: PublicKey;
+  let configPda: PublicKey;
 
-  before(() => {
+  before(async () => {
     wallet = (provider.wallet as any).payer as Keypair;
 
+    // The config is created once per deployment, by the upgrade authority
+    [configPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      program.programId
+    );
+    if (!(await connection.getAccountInfo(configPda))) {
+      const [programData] = PublicKey.findProgramAddressSync(
+        [program.programId.toBuffer()],
+        new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+      );
+      await program.methods
+        .initializeConfig(CONFIG_PARAMS)
+        .accounts({
+          admin: wallet.publicKey,
+          config: configPda,
+          program: program.programId,
+          programData,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+    }
+
     const idBuffer = Buffer.alloc(8);
     idBuffer.writeBigUInt64LE(BigInt(marketId));
 
@@ -67,11 +110,22 @@ describe("dake", () => {
       program.programId
     );
     [positionPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("position"), marketPda.toBuffer(), wallet.publicKey.toBuffer()],
+      [
+        Buffer.from("position"),
+        marketPda.toBuffer(),
+        wallet.publicKey.toBuffer(),
+        nonceBuffer(0),
+      ],
       program.programId
     );
   });
 
+  function nonceBuffer(nonce: number): Buffer {
+    const buf = Buffer.alloc(8);
+    buf.writeBigUInt64LE(BigInt(nonce));
+    return buf;
+  }
+
   function deriveAllowancePda(handle: bigint): [PublicKey, number] {
     const buf = Buffer.alloc(16);
     let v = handle;
@@ -123,23 +177,135 @@ describe("dake", () => {
     return null;
   }
 
+  async function emittedEvents(sig: string): Promise<string[]> {
+    const tx = await connection.getTransaction(sig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(
+      program.programId,
+      new anchor.BorshCoder(program.idl)
+    );
+    return Array.from(parser.parseLogs(tx?.meta?.logMessages || [])).map(
+      (event) => event.name
+    );
+  }
+
+  function marketTimes(): [anchor.BN, anchor.BN] {
+    const resolutionTime = Math.floor(Date.now() / 1000) + RESOLUTION_DELAY_SECS;
+    // [resolution_time, betting_closes_at]
+    return [new anchor.BN(resolutionTime), new anchor.BN(resolutionTime)];
+  }
+
+  async function waitForResolutionTime(market: PublicKey): Promise<void> {
+    const marketAccount = await program.account.market.fetch(market);
+    const waitMs =
+      (marketAccount.resolutionTime.toNumber() + 2) * 1000 - Date.now();
+    if (waitMs > 0) {
+      console.log("   Waiting", Math.ceil(waitMs / 1000), "s for resolution time");
+      await new Promise((r) => setTimeout(r, waitMs));
+    }
+  }
+
+  async function closeMarket(market: PublicKey): Promise<string> {
+    const marketAccount = await program.account.market.fetch(market);
+    const allowanceAccounts = marketAccount.encryptedTotals.flatMap(
+      (handle: anchor.BN) => [
+        {
+          pubkey: deriveAllowancePda(BigInt(handle.toString()))[0],
+          isSigner: false,
+          isWritable: true,
+        },
+        { pubkey: wallet.publicKey, isSigner: false, isWritable: false },
+      ]
+    );
+
+    return program.methods
+      .closeMarket()
+      .accounts({
+        closer: wallet.publicKey,
+        market,
+        systemProgram: SystemProgram.programId,
+        incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+      } as any)
+      .remainingAccounts(allowanceAccounts)
+      .rpc();
+  }
+
+  async function revealPools(market: PublicKey): Promise<string> {
+    const marketAccount = await program.account.market.fetch(market);
+    const handles = marketAccount.encryptedTotals.map((h: anchor.BN) =>
+      h.toString()
+    );
+
+    await new Promise((r) => setTimeout(r, 2000));
+    const result = await decrypt(handles, {
+      address: wallet.publicKey,
+      signMessage: async (msg: Uint8Array) =>
+        nacl.sign.detached(msg, wallet.secretKey),
+    });
+
+    const revealIx = await program.methods
+      .revealPools(
+        handles.map((h) => handleToBuffer(h)),
+        result.plaintexts.map((p: string) => plaintextToBuffer(p))
+      )
+      .accounts({
+        revealer: wallet.publicKey,
+        market,
+        instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+      } as any)
+      .instruction();
+
+    const tx = new Transaction();
+    result.ed25519Instructions.forEach((ix) => tx.add(ix));
+    tx.add(revealIx);
+
+    const { blockhash } = await connection.getLatestBlockhash();
+    tx.recentBlockhash = blockhash;
+    tx.feePayer = wallet.publicKey;
+
+    const signedTx = await provider.wallet.signTransaction(tx);
+    const sig = await connection.sendRawTransaction(signedTx.serialize());
+    await connection.confirmTransaction(sig, "confirmed");
+
+    const revealed = await program.account.market.fetch(market);
+    console.log(
+      "   Pool totals:",
+      revealed.outcomeTotals.map((t: anchor.BN) => t.toString())
+    );
+    return sig;
+  }
+
   it("1. Create prediction market", async () => {
     const tx = await program.methods
       .createMarket(
         new anchor.BN(marketId),
         QUESTION,
-        new anchor.BN(RESOLUTION_TIME)
+        OUTCOMES,
+        CATEGORICAL,
+        INITIAL_LIQUIDITY,
+        ...marketTimes(),
+        CREATOR_FEE_BPS,
+        AUTHORITY_RESOLVED
       )
       .accounts({
         authority: wallet.publicKey,
         market: marketPda,
         vault: vaultPda,
         systemProgram: SystemProgram.programId,
+        incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
       } as any)
       .rpc();
 
     console.log("Market created:", tx);
     console.log("   Question:", QUESTION);
+
+    const events = await emittedEvents(tx);
+    if (!events.includes("marketCreated")) {
+      throw new Error("MarketCreated event not emitted");
+    }
     console.log("   Place your bets!");
   });
 
@@ -151,7 +317,11 @@ describe("dake", () => {
     const encryptedSide = await encryptValue(BigInt(MY_SIDE));
 
     const tx = await program.methods
-      .placeBet(hexToBuffer(encryptedSide), new anchor.BN(BET_AMOUNT), MY_SIDE)
+      .placeBet(
+        hexToBuffer(encryptedSide),
+        new anchor.BN(BET_AMOUNT),
+        new anchor.BN(0)
+      )
       .accounts({
         bettor: wallet.publicKey,
         market: marketPda,
@@ -167,21 +337,21 @@ describe("dake", () => {
   });
 
   it("3. Close market for betting", async () => {
-    const tx = await program.methods
-      .closeMarket()
-      .accounts({
-        authority: wallet.publicKey,
-        market: marketPda,
-      } as any)
-      .rpc();
-
+    const tx = await closeMarket(marketPda);
     console.log("Market closed for betting:", tx);
   });
 
+  it("3b. Reveal pool totals", async () => {
+    const tx = await revealPools(marketPda);
+    console.log("Pool totals revealed:", tx);
+  });
+
   it("4. Resolve market (YES wins)", async () => {
-    // Resolve with YES winning (outcome = true)
+    await waitForResolutionTime(marketPda);
+
+    // Resolve with YES winning (outcome = 1)
     const tx = await program.methods
-      .resolveMarket(true)
+      .resolveMarket(1)
       .accounts({
         authority: wallet.publicKey,
         market: marketPda,
@@ -313,7 +483,12 @@ describe("dake", () => {
         program.programId
       );
       [position2Pda] = PublicKey.findProgramAddressSync(
-        [Buffer.from("position"), market2Pda.toBuffer(), wallet.publicKey.toBuffer()],
+        [
+          Buffer.from("position"),
+          market2Pda.toBuffer(),
+          wallet.publicKey.toBuffer(),
+          nonceBuffer(0),
+        ],
         program.programId
       );
     });
@@ -323,13 +498,19 @@ describe("dake", () => {
         .createMarket(
           new anchor.BN(marketId2),
           "Will BTC hit $200k by 2026?",
-          new anchor.BN(RESOLUTION_TIME)
+          OUTCOMES,
+          CATEGORICAL,
+          INITIAL_LIQUIDITY,
+          ...marketTimes(),
+          CREATOR_FEE_BPS,
+          AUTHORITY_RESOLVED
         )
         .accounts({
           authority: wallet.publicKey,
           market: market2Pda,
           vault: vault2Pda,
           systemProgram: SystemProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
         } as any)
         .rpc();
 
@@ -344,7 +525,7 @@ describe("dake", () => {
         .placeBet(
           hexToBuffer(encryptedSide),
           new anchor.BN(BET_AMOUNT),
-          LOSER_SIDE
+          new anchor.BN(0)
         )
         .accounts({
           bettor: wallet.publicKey,
@@ -360,16 +541,12 @@ describe("dake", () => {
     });
 
     it("9. Close and resolve market (YES wins)", async () => {
-      await program.methods
-        .closeMarket()
-        .accounts({
-          authority: wallet.publicKey,
-          market: market2Pda,
-        } as any)
-        .rpc();
+      await closeMarket(market2Pda);
+      await revealPools(market2Pda);
+      await waitForResolutionTime(market2Pda);
 
       const tx = await program.methods
-        .resolveMarket(true) // YES wins, but we bet NO
+        .resolveMarket(1) // YES wins, but we bet NO
         .accounts({
           authority: wallet.publicKey,
           market: market2Pda,
@@ -486,4 +663,609 @@ describe("dake", () => {
       }
     });
   });
+
+  // ========== CANCEL / REFUND TEST ==========
+  describe("Cancelled market flow", () => {
+    const marketId3 = marketId + 2;
+
+    let market3Pda: PublicKey;
+    let vault3Pda: PublicKey;
+    let position3Pda: PublicKey;
+
+    before(() => {
+      const idBuffer = Buffer.alloc(8);
+      idBuffer.writeBigUInt64LE(BigInt(marketId3));
+
+      [market3Pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), idBuffer],
+        program.programId
+      );
+      [vault3Pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), market3Pda.toBuffer()],
+        program.programId
+      );
+      [position3Pda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("position"),
+          market3Pda.toBuffer(),
+          wallet.publicKey.toBuffer(),
+          nonceBuffer(0),
+        ],
+        program.programId
+      );
+    });
+
+    it("12. Create market and place bet", async () => {
+      await program.methods
+        .createMarket(
+          new anchor.BN(marketId3),
+          "Will this ambiguous question resolve?",
+          OUTCOMES,
+          CATEGORICAL,
+          INITIAL_LIQUIDITY,
+          ...marketTimes(),
+          CREATOR_FEE_BPS,
+          AUTHORITY_RESOLVED
+        )
+        .accounts({
+          authority: wallet.publicKey,
+          market: market3Pda,
+          vault: vault3Pda,
+          systemProgram: SystemProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        } as any)
+        .rpc();
+
+      const encryptedSide = await encryptValue(BigInt(MY_SIDE));
+      const tx = await program.methods
+        .placeBet(
+          hexToBuffer(encryptedSide),
+          new anchor.BN(BET_AMOUNT),
+          new anchor.BN(0)
+        )
+        .accounts({
+          bettor: wallet.publicKey,
+          market: market3Pda,
+          position: position3Pda,
+          vault: vault3Pda,
+          systemProgram: SystemProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        } as any)
+        .rpc();
+
+      console.log("Bet placed on market 3:", tx);
+    });
+
+    it("13. Cancel market", async () => {
+      const tx = await program.methods
+        .cancelMarket()
+        .accounts({
+          authority: wallet.publicKey,
+          market: market3Pda,
+          vault: vault3Pda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+
+      console.log("Market cancelled, seed liquidity returned:", tx);
+    });
+
+    it("14. Refund position", async () => {
+      const before = await connection.getBalance(vault3Pda);
+
+      const tx = await program.methods
+        .refundPosition()
+        .accounts({
+          owner: wallet.publicKey,
+          market: market3Pda,
+          position: position3Pda,
+          vault: vault3Pda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+
+      const after = await connection.getBalance(vault3Pda);
+      const position = await program.account.position.fetch(position3Pda);
+      console.log("Position refunded:", tx);
+      console.log("   Refunded:", before - after, "lamports");
+
+      if (before - after !== BET_AMOUNT || !position.claimed) {
+        throw new Error("Refund did not return the bet amount");
+      }
+    });
+
+    it("14b. Close refunded position", async () => {
+      const tx = await program.methods
+        .closePosition()
+        .accounts({
+          owner: wallet.publicKey,
+          market: market3Pda,
+          position: position3Pda,
+        } as any)
+        .rpc();
+
+      console.log("Position closed, rent reclaimed:", tx);
+      if (await connection.getAccountInfo(position3Pda)) {
+        throw new Error("Position account not closed");
+      }
+    });
+
+    it("14c. Finalize cancelled market", async () => {
+      const tx = await program.methods
+        .finalizeMarket()
+        .accounts({
+          authority: wallet.publicKey,
+          market: market3Pda,
+          vault: vault3Pda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+
+      console.log("Market finalized:", tx);
+      if (await connection.getAccountInfo(market3Pda)) {
+        throw new Error("Market account not closed");
+      }
+    });
+  });
+
+  // ========== CROSS-MARKET VALIDATION ==========
+  describe("Cross-market validation", () => {
+    // Position from the non-winner flow (market 2); the cancelled market's
+    // position is closed by then
+    let position2Pda: PublicKey;
+
+    before(() => {
+      const positionFor = (id: number) => {
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(id));
+        const [market] = PublicKey.findProgramAddressSync(
+          [Buffer.from("market"), idBuffer],
+          program.programId
+        );
+        return PublicKey.findProgramAddressSync(
+          [
+            Buffer.from("position"),
+            market.toBuffer(),
+            wallet.publicKey.toBuffer(),
+            nonceBuffer(0),
+          ],
+          program.programId
+        )[0];
+      };
+      position2Pda = positionFor(marketId + 1);
+    });
+
+    async function expectMismatch(action: Promise<string>) {
+      try {
+        await action;
+      } catch (e: any) {
+        if (e.message.includes("PositionMarketMismatch")) {
+          console.log("   ✓ Rejected - PositionMarketMismatch");
+          return;
+        }
+        throw e;
+      }
+      throw new Error("Cross-market call should have failed!");
+    }
+
+    it("15. check_winner rejects a position from another market", async () => {
+      await expectMismatch(
+        program.methods
+          .checkWinner()
+          .accounts({
+            checker: wallet.publicKey,
+            market: marketPda,
+            position: position2Pda,
+            systemProgram: SystemProgram.programId,
+            incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+          } as any)
+          .rpc()
+      );
+    });
+
+    it("16. claim_winnings rejects a position from another market", async () => {
+      await expectMismatch(
+        program.methods
+          .claimWinnings(Buffer.alloc(16), Buffer.from([1]))
+          .accounts({
+            winner: wallet.publicKey,
+            market: marketPda,
+            position: position2Pda,
+            vault: vaultPda,
+            instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+          } as any)
+          .rpc()
+      );
+    });
+
+    it("17. refund_position rejects a position from another market", async () => {
+      await expectMismatch(
+        program.methods
+          .refundPosition()
+          .accounts({
+            owner: wallet.publicKey,
+            market: marketPda,
+            position: position2Pda,
+            vault: vaultPda,
+            systemProgram: SystemProgram.programId,
+          } as any)
+          .rpc()
+      );
+    });
+  });
+
+  // ========== EARLY EXIT TEST ==========
+  describe("Early exit flow", () => {
+    const marketId4 = marketId + 3;
+    const EXIT_FEE_BPS = CONFIG_PARAMS.exitFeeBps;
+
+    let market4Pda: PublicKey;
+    let vault4Pda: PublicKey;
+    let position4Pda: PublicKey;
+
+    before(() => {
+      const idBuffer = Buffer.alloc(8);
+      idBuffer.writeBigUInt64LE(BigInt(marketId4));
+
+      [market4Pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), idBuffer],
+        program.programId
+      );
+      [vault4Pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("vault"), market4Pda.toBuffer()],
+        program.programId
+      );
+      [position4Pda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("position"),
+          market4Pda.toBuffer(),
+          wallet.publicKey.toBuffer(),
+          nonceBuffer(0),
+        ],
+        program.programId
+      );
+    });
+
+    it("18. Create market and place bet", async () => {
+      await program.methods
+        .createMarket(
+          new anchor.BN(marketId4),
+          "Will I change my mind before close?",
+          OUTCOMES,
+          CATEGORICAL,
+          INITIAL_LIQUIDITY,
+          ...marketTimes(),
+          CREATOR_FEE_BPS,
+          AUTHORITY_RESOLVED
+        )
+        .accounts({
+          authority: wallet.publicKey,
+          market: market4Pda,
+          vault: vault4Pda,
+          systemProgram: SystemProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        } as any)
+        .rpc();
+
+      const encryptedSide = await encryptValue(BigInt(MY_SIDE));
+      const tx = await program.methods
+        .placeBet(
+          hexToBuffer(encryptedSide),
+          new anchor.BN(BET_AMOUNT),
+          new anchor.BN(0)
+        )
+        .accounts({
+          bettor: wallet.publicKey,
+          market: market4Pda,
+          position: position4Pda,
+          vault: vault4Pda,
+          systemProgram: SystemProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        } as any)
+        .rpc();
+
+      console.log("Bet placed on market 4:", tx);
+    });
+
+    it("19. Exit position before close", async () => {
+      const before = await connection.getBalance(vault4Pda);
+
+      const tx = await program.methods
+        .exitPosition()
+        .accounts({
+          owner: wallet.publicKey,
+          market: market4Pda,
+          position: position4Pda,
+          vault: vault4Pda,
+          systemProgram: SystemProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        } as any)
+        .rpc();
+
+      const after = await connection.getBalance(vault4Pda);
+      const market = await program.account.market.fetch(market4Pda);
+      const position = await connection.getAccountInfo(position4Pda);
+      const fee = Math.floor((BET_AMOUNT * EXIT_FEE_BPS) / 10_000);
+      console.log("Position exited:", tx);
+      console.log("   Refunded:", before - after, "lamports (fee", fee, ")");
+
+      if (before - after !== BET_AMOUNT - fee || position !== null) {
+        throw new Error("Exit did not refund the bet minus the exit fee");
+      }
+      if (market.exitFeesCollected.toNumber() !== fee) {
+        throw new Error("Exit fee not recorded on the market");
+      }
+    });
+
+    it("20. Withdraw creator fees (exit fee)", async () => {
+      const [creatorFeeVault] = PublicKey.findProgramAddressSync(
+        [Buffer.from("creator_fee_vault"), market4Pda.toBuffer()],
+        program.programId
+      );
+      const before = await connection.getBalance(creatorFeeVault);
+
+      const tx = await program.methods
+        .withdrawCreatorFees()
+        .accounts({
+          authority: wallet.publicKey,
+          market: market4Pda,
+          creatorFeeVault,
+          vault: vault4Pda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+
+      const after = await connection.getBalance(creatorFeeVault);
+      const feeVault = await program.account.feeVault.fetch(creatorFeeVault);
+      console.log("Creator fees withdrawn:", tx);
+      console.log("   Withdrawn:", before - after, "lamports");
+
+      if (before - after !== feeVault.withdrawn.toNumber()) {
+        throw new Error("Withdrawal did not match the accrued fees");
+      }
+    });
+  });
+
+  // ========== PROGRAM CONFIG ==========
+  describe("Program config", () => {
+    it("21. Paused program rejects new markets", async () => {
+      await program.methods
+        .setPaused(true)
+        .accounts({ admin: wallet.publicKey, config: configPda } as any)
+        .rpc();
+
+      try {
+        const idBuffer = Buffer.alloc(8);
+        idBuffer.writeBigUInt64LE(BigInt(marketId + 4));
+        const [pausedMarket] = PublicKey.findProgramAddressSync(
+          [Buffer.from("market"), idBuffer],
+          program.programId
+        );
+
+        await program.methods
+          .createMarket(
+            new anchor.BN(marketId + 4),
+            "Can I create a market while paused?",
+            OUTCOMES,
+            CATEGORICAL,
+            [],
+            ...marketTimes(),
+            CREATOR_FEE_BPS,
+            AUTHORITY_RESOLVED
+          )
+          .accounts({
+            authority: wallet.publicKey,
+            market: pausedMarket,
+            systemProgram: SystemProgram.programId,
+            incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+          } as any)
+          .rpc();
+        throw new Error("Market created while paused");
+      } catch (e: any) {
+        if (!e.message.includes("ProgramPaused")) throw e;
+        console.log("   Rejected as expected: ProgramPaused");
+      } finally {
+        await program.methods
+          .setPaused(false)
+          .accounts({ admin: wallet.publicKey, config: configPda } as any)
+          .rpc();
+      }
+    });
+
+    it("22. Two-step admin rotation", async () => {
+      // Rotate to the same wallet so the rest of the suite keeps working
+      await program.methods
+        .proposeAdmin(wallet.publicKey)
+        .accounts({ admin: wallet.publicKey, config: configPda } as any)
+        .rpc();
+      await program.methods
+        .acceptAdmin()
+        .accounts({ newAdmin: wallet.publicKey, config: configPda } as any)
+        .rpc();
+
+      const config = await program.account.config.fetch(configPda);
+      if (!config.admin.equals(wallet.publicKey) || config.pendingAdmin !== null) {
+        throw new Error("Admin rotation did not complete");
+      }
+    });
+  });
+
+  // ========== OPTIMISTIC RESOLUTION ==========
+  describe("Optimistic resolution", () => {
+    const marketId5 = marketId + 5;
+
+    let market5Pda: PublicKey;
+    let proposalPda: PublicKey;
+
+    before(() => {
+      const idBuffer = Buffer.alloc(8);
+      idBuffer.writeBigUInt64LE(BigInt(marketId5));
+
+      [market5Pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), idBuffer],
+        program.programId
+      );
+    });
+
+    it("23. Create optimistic market", async () => {
+      await program.methods
+        .createMarket(
+          new anchor.BN(marketId5),
+          "Will this be resolved without a trusted authority?",
+          OUTCOMES,
+          CATEGORICAL,
+          [],
+          ...marketTimes(),
+          CREATOR_FEE_BPS,
+          { optimistic: {} }
+        )
+        .accounts({
+          authority: wallet.publicKey,
+          market: market5Pda,
+          systemProgram: SystemProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        } as any)
+        .rpc();
+
+      // The authority can no longer resolve it directly
+      await waitForResolutionTime(market5Pda);
+      try {
+        await program.methods
+          .resolveMarket(1)
+          .accounts({ authority: wallet.publicKey, market: market5Pda } as any)
+          .rpc();
+        throw new Error("Authority resolved an optimistic market");
+      } catch (e: any) {
+        if (!e.message.includes("WrongResolutionSource")) throw e;
+      }
+    });
+
+    it("24. Propose, dispute and arbitrate", async () => {
+      // Proposals are scoped to this instance of the market
+      const { createdAt } = await program.account.market.fetch(market5Pda);
+      [proposalPda] = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("resolution"),
+          market5Pda.toBuffer(),
+          createdAt.toArrayLike(Buffer, "le", 8),
+        ],
+        program.programId
+      );
+
+      await program.methods
+        .proposeResolution(1, new anchor.BN(0))
+        .accounts({
+          proposer: wallet.publicKey,
+          market: market5Pda,
+          proposal: proposalPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+
+      await program.methods
+        .disputeResolution()
+        .accounts({
+          disputer: wallet.publicKey,
+          market: market5Pda,
+          proposal: proposalPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+
+      const tx = await program.methods
+        .arbitrateResolution(0, new anchor.BN(0))
+        .accounts({
+          admin: wallet.publicKey,
+          market: market5Pda,
+          proposal: proposalPda,
+          proposer: wallet.publicKey,
+          disputer: wallet.publicKey,
+        } as any)
+        .rpc();
+
+      const market = await program.account.market.fetch(market5Pda);
+      console.log("Dispute settled:", tx);
+      if (!("resolved" in market.status) || market.winningOutcome !== 0) {
+        throw new Error("Arbitrated outcome not applied");
+      }
+      if (await connection.getAccountInfo(proposalPda)) {
+        throw new Error("Proposal account not closed");
+      }
+    });
+  });
+
+  // ========== COMMITTEE RESOLUTION ==========
+  describe("Committee resolution", () => {
+    const marketId6 = marketId + 6;
+    const setId = new anchor.BN(marketId6);
+
+    let market6Pda: PublicKey;
+    let resolverSetPda: PublicKey;
+
+    before(() => {
+      const idBuffer = Buffer.alloc(8);
+      idBuffer.writeBigUInt64LE(BigInt(marketId6));
+
+      [market6Pda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("market"), idBuffer],
+        program.programId
+      );
+      [resolverSetPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("resolver_set"), wallet.publicKey.toBuffer(), idBuffer],
+        program.programId
+      );
+    });
+
+    it("25. Create 1-of-1 committee and market", async () => {
+      await program.methods
+        .createResolverSet(setId, [wallet.publicKey], 1)
+        .accounts({
+          authority: wallet.publicKey,
+          resolverSet: resolverSetPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+
+      await program.methods
+        .createMarket(
+          new anchor.BN(marketId6),
+          "Will the committee agree?",
+          OUTCOMES,
+          CATEGORICAL,
+          [],
+          ...marketTimes(),
+          CREATOR_FEE_BPS,
+          { committee: { resolverSet: resolverSetPda, voteWindowSecs: 600 } }
+        )
+        .accounts({
+          authority: wallet.publicKey,
+          market: market6Pda,
+          systemProgram: SystemProgram.programId,
+          incoLightningProgram: INCO_LIGHTNING_PROGRAM_ID,
+        } as any)
+        .rpc();
+    });
+
+    it("26. Vote reaches the threshold and resolves", async () => {
+      await waitForResolutionTime(market6Pda);
+
+      const tx = await program.methods
+        .voteResolution(1, new anchor.BN(0))
+        .accounts({
+          resolver: wallet.publicKey,
+          market: market6Pda,
+          resolverSet: resolverSetPda,
+          systemProgram: SystemProgram.programId,
+        } as any)
+        .rpc();
+
+      const market = await program.account.market.fetch(market6Pda);
+      console.log("Committee vote:", tx);
+      if (!("resolved" in market.status) || market.winningOutcome !== 1) {
+        throw new Error("Committee vote did not resolve the market");
+      }
+    });
+  });
 });